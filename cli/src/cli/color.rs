@@ -34,3 +34,14 @@ pub const fn get_styles() -> clap::builder::Styles {
         )
         .placeholder(Style::new().fg_color(Some(Color::Ansi(AnsiColor::White))))
 }
+
+/// Wrap `text` in `color` if stdout is a terminal.
+pub fn paint(text: &str, color: AnsiColor) -> String {
+    use std::io::IsTerminal as _;
+
+    if !std::io::stdout().is_terminal() {
+        return text.into();
+    }
+    let style = Style::new().bold().fg_color(Some(Color::Ansi(color)));
+    format!("{style}{text}{style:#}")
+}
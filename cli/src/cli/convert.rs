@@ -1,9 +1,15 @@
 use dar2oar_core::{
-    Closure, ConvertOptions, convert_dar_to_oar, error::Result, read_mapping_table,
+    CancellationToken, ConvertOptions, ConvertReport, FormIdStyle, OarVersion, ProgressEvent,
+    SectionNaming, convert_dar_to_oar, error::Result, read_editor_id_dictionary,
+    read_mapping_table,
 };
 use std::path::PathBuf;
 
-pub(crate) async fn dar2oar(args: CliArgs) -> Result<()> {
+pub(crate) async fn dar2oar(
+    args: CliArgs,
+    mut on_event: impl FnMut(&ProgressEvent),
+    cancel_token: CancellationToken,
+) -> Result<ConvertReport> {
     let CliArgs {
         source,
         destination,
@@ -12,6 +18,15 @@ pub(crate) async fn dar2oar(args: CliArgs) -> Result<()> {
         description,
         mapping_file,
         mapping_1person_file,
+        section_naming,
+        editor_id_file,
+        data_dir,
+        target_oar_version,
+        form_id_case,
+        form_id_width,
+        extract_presets,
+        collapse_variants,
+        dedupe_motions,
         run_parallel,
         hide_dar,
     } = args;
@@ -24,6 +39,10 @@ pub(crate) async fn dar2oar(args: CliArgs) -> Result<()> {
         Some(mapping_file) => Some(read_mapping_table(&mapping_file).await?),
         None => None,
     };
+    let editor_ids = match editor_id_file {
+        Some(path) => Some(read_editor_id_dictionary(&path).await?),
+        None => None,
+    };
 
     let config = ConvertOptions {
         dar_dir: source,
@@ -33,11 +52,37 @@ pub(crate) async fn dar2oar(args: CliArgs) -> Result<()> {
         description,
         section_table,
         section_1person_table,
+        section_naming: section_naming.into(),
+        editor_ids,
+        target_oar_version,
+        form_id_style: FormIdStyle {
+            uppercase: matches!(form_id_case, FormIdCase::Upper),
+            width: form_id_width,
+        },
+        data_dir,
+        extract_presets,
+        collapse_variants,
+        dedupe_motions,
         run_parallel,
         hide_dar,
     };
 
-    convert_dar_to_oar(config, Closure::default).await
+    let reporter = |event: ProgressEvent| {
+        on_event(&event);
+        log_event(event);
+    };
+    convert_dar_to_oar(config, reporter, cancel_token).await
+}
+
+/// Write warnings and the summary to the log.
+fn log_event(event: ProgressEvent) {
+    match event {
+        ProgressEvent::Warning { path, message } => tracing::warn!("{}: {message}", path.display()),
+        ProgressEvent::Finished { report } => tracing::info!("{report:?}"),
+        ProgressEvent::Started { .. }
+        | ProgressEvent::FileStarted { .. }
+        | ProgressEvent::FileDone { .. } => {}
+    }
 }
 
 #[derive(Debug, clap::Args)]
@@ -66,6 +111,48 @@ pub(crate) struct CliArgs {
     #[clap(long)]
     /// Path to section name table(For _1st_person)
     mapping_1person_file: Option<PathBuf>,
+    #[clap(long, value_enum, ignore_case = true, default_value = "priority")]
+    /// How to name the sections that have no entry in the mapping table
+    section_naming: Naming,
+    #[clap(long)]
+    /// Path to `plugin,FormID,EditorID` dictionary(CSV or JSON exported from xEdit)
+    ///
+    /// Keywords are written as EditorID, section descriptions list the EditorIDs,
+    /// and FormIDs not in the dictionary are warned.
+    editor_id_file: Option<PathBuf>,
+    #[clap(long)]
+    /// Skyrim "Data" directory. If specified, the forms in the conditions are checked against its plugins
+    ///
+    /// Missing plugins/forms and unexpected record types(e.g. non KYWD for keywords) are warned,
+    /// and FormIDs of light plugins(ESL) are normalized to the light plugin range.
+    data_dir: Option<String>,
+    #[clap(long, value_parser = parse_oar_version)]
+    /// OAR version the output targets. e.g. "1.0.0.0"
+    ///
+    /// Conditions requiring a newer OAR(e.g. IsMovementDirection: 1.1, keywords as EditorID: 2.0) are warned,
+    /// and condition presets(2.3) are not extracted.
+    target_oar_version: Option<OarVersion>,
+    #[clap(long, value_enum, ignore_case = true, default_value = "upper")]
+    /// Case of the hex digits of FormIDs in config.json
+    ///
+    /// FormIDs are also stripped of the load order byte(e.g. 0x0A012345 -> 12345, 0xFE012800 -> 800 for ESL).
+    form_id_case: FormIdCase,
+    #[clap(long, default_value = "0")]
+    /// Pad FormIDs with zeros to at least this number of digits(e.g. 6: 013BBF)
+    form_id_width: usize,
+    #[clap(long)]
+    /// Hoist the condition blocks repeated across sections into OAR condition presets(OAR 2.3.0+)
+    ///
+    /// The presets are written to the mod's config.json, so the shared lists can be edited in one place.
+    extract_presets: bool,
+    #[clap(long)]
+    /// Don't copy motion files byte-identical to another section's; refer to it via overrideAnimationsFolder
+    dedupe_motions: bool,
+    #[clap(long)]
+    /// Merge the sections that differ only by Random() thresholds into OAR animation variants(_variants_*)
+    ///
+    /// Only groups where each section plays with the same probability are merged. Others are warned.
+    collapse_variants: bool,
     #[clap(long)]
     /// Use multi thread
     ///
@@ -79,3 +166,34 @@ pub(crate) struct CliArgs {
     /// After conversion, add ".mohidden" to all DAR files to hide them(For MO2 user)
     hide_dar: bool,
 }
+
+fn parse_oar_version(version: &str) -> core::result::Result<OarVersion, String> {
+    version.parse().map_err(|err| format!("{err}"))
+}
+
+/// Case of the FormID hex digits.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum FormIdCase {
+    /// e.g. "13BBF"
+    Upper,
+    /// e.g. "13bbf"
+    Lower,
+}
+
+/// [`SectionNaming`] for the command line.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Naming {
+    /// Use the raw priority. e.g. "8107000"
+    Priority,
+    /// Derive a short name from the conditions. e.g. "Dagger_Sneaking"
+    Conditions,
+}
+
+impl From<Naming> for SectionNaming {
+    fn from(naming: Naming) -> Self {
+        match naming {
+            Naming::Priority => Self::Priority,
+            Naming::Conditions => Self::Conditions,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! `mapping` subcommand: generate and check mapping tables
+use super::output::{Outcome, Status};
+use crate::error::Result;
+use dar2oar_core::error::ConvertError;
+use dar2oar_core::read_mapping_table;
+use mapping_table::MappingTable;
+use mapping_table::builder::{MappingStrategy, collect_priorities, generate_mapping_table};
+use mapping_table::check::{CheckReport, check_mapping_table};
+use mapping_table::document::MappingDocument;
+use mapping_table::writer::write_mapping_table;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum MappingCommand {
+    #[clap(arg_required_else_help = true)]
+    /// Generate a mapping table from the dir/file names of a DAR mod
+    Generate(GenerateArgs),
+
+    #[clap(arg_required_else_help = true)]
+    /// Check a mapping table against the priority dirs of a DAR mod
+    Check(CheckArgs),
+}
+
+impl MappingCommand {
+    pub(crate) const fn name(&self) -> &'static str {
+        match self {
+            Self::Generate(_) => "mapping generate",
+            Self::Check(_) => "mapping check",
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct GenerateArgs {
+    #[clap(value_parser)]
+    /// Path containing the "DynamicAnimationReplacer" directory
+    dar_dir: PathBuf,
+
+    #[clap(long, value_enum, value_delimiter = ',', default_value = "txt-stem")]
+    /// How to infer the section names.
+    /// With several (e.g. `conditions-comment,txt-stem`), the first one that finds a name wins.
+    strategy: Vec<Strategy>,
+
+    #[clap(long)]
+    /// Write the table to this file (if none, print it).
+    /// If the file exists, only new priorities are added, keeping its names and comments.
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct CheckArgs {
+    #[clap(value_parser)]
+    /// Mapping table file
+    file: PathBuf,
+
+    #[clap(value_parser)]
+    /// Path containing the "DynamicAnimationReplacer" directory
+    dar_dir: PathBuf,
+}
+
+/// [`MappingStrategy`] for the command line.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Strategy {
+    /// Use txt file name as section name. e.g. "ultimate sit.txt" -> "ultimate sit"
+    TxtStem,
+    /// Use txt file name with digits stripped. e.g. "foo123.txt" -> "foo"
+    TxtStemStripped,
+    /// Parse directory names like `123 - name`
+    DirPattern,
+    /// Use the first non-empty line of `readme.txt` in the priority dir
+    ReadmeTxt,
+    /// Use the first `;` comment at the top of `_conditions.txt`
+    ConditionsComment,
+    /// Use the name of the only subfolder of the priority dir that contains `.hkx` files
+    HkxSubfolder,
+    /// Use the FOMOD option name that installs the priority dir
+    Fomod,
+}
+
+impl From<Strategy> for MappingStrategy {
+    fn from(strategy: Strategy) -> Self {
+        match strategy {
+            Strategy::TxtStem => Self::TxtStem,
+            Strategy::TxtStemStripped => Self::TxtStemStripped,
+            Strategy::DirPattern => Self::DirPattern,
+            Strategy::ReadmeTxt => Self::ReadmeTxt,
+            Strategy::ConditionsComment => Self::ConditionsComment,
+            Strategy::HkxSubfolder => Self::HkxSubfolder,
+            Strategy::Fomod => Self::Fomod,
+        }
+    }
+}
+
+/// A single strategy as is, several as [`MappingStrategy::Composite`].
+fn to_mapping_strategy(strategies: Vec<Strategy>) -> MappingStrategy {
+    let mut strategies: Vec<MappingStrategy> = strategies.into_iter().map(Into::into).collect();
+    match strategies.len() {
+        1 => strategies.swap_remove(0),
+        _ => MappingStrategy::Composite(strategies),
+    }
+}
+
+/// Result of `mapping generate` in `--format json`.
+#[derive(Debug, serde::Serialize)]
+struct GenerateResult {
+    /// priority -> name
+    entries: BTreeMap<String, String>,
+    /// priority -> strategy that produced the name
+    provenance: BTreeMap<String, MappingStrategy>,
+    output: Option<PathBuf>,
+}
+
+pub(crate) async fn run(command: MappingCommand, is_text: bool) -> Result<Outcome> {
+    match command {
+        MappingCommand::Generate(args) => generate(args, is_text).await,
+        MappingCommand::Check(args) => check(args, is_text).await,
+    }
+}
+
+async fn generate(args: GenerateArgs, is_text: bool) -> Result<Outcome> {
+    let GenerateArgs {
+        dar_dir,
+        strategy,
+        output,
+    } = args;
+
+    let generated = generate_mapping_table(&dar_dir, &to_mapping_strategy(strategy))?;
+    let table = generated.table;
+    match &output {
+        Some(output) if output.exists() => {
+            let mut document = read_document(output).await?;
+            merge(&mut document, &table);
+            tokio::fs::write(output, document.to_string()).await?;
+        }
+        Some(output) => tokio::fs::write(output, write_mapping_table(&table)).await?,
+        None if is_text => print_text(&write_mapping_table(&table)),
+        None => {}
+    }
+
+    let result = GenerateResult {
+        entries: table.into_iter().collect(),
+        provenance: generated.provenance.into_iter().collect(),
+        output,
+    };
+    Outcome::new(Status::Success, &result)
+}
+
+async fn read_document(path: &Path) -> Result<MappingDocument> {
+    let text = tokio::fs::read_to_string(path).await?;
+    MappingDocument::parse(&text).map_err(|source| {
+        ConvertError::MappingTableError {
+            path: path.to_path_buf(),
+            source,
+        }
+        .into()
+    })
+}
+
+/// Add the named entries of `table` whose priority is not in `document` yet.
+fn merge(document: &mut MappingDocument, table: &MappingTable) {
+    let existing = document.to_table();
+    let mut entries: Vec<_> = table
+        .iter()
+        .filter(|(priority, name)| !name.trim().is_empty() && !existing.contains_key(*priority))
+        .collect();
+    entries.sort();
+    for (priority, name) in entries {
+        document.set(priority, name);
+    }
+}
+
+async fn check(args: CheckArgs, is_text: bool) -> Result<Outcome> {
+    let table = read_mapping_table(&args.file).await?;
+    let priorities = collect_priorities(&args.dar_dir)?;
+    let report = check_mapping_table(&table, &priorities);
+
+    if is_text {
+        print_check_report(&report);
+    }
+    let status = match report.is_ok() {
+        true => Status::Success,
+        false => Status::Partial,
+    };
+    Outcome::new(status, &report)
+}
+
+#[allow(clippy::print_stdout)]
+fn print_text(text: &str) {
+    print!("{text}");
+}
+
+#[allow(clippy::print_stdout)]
+fn print_check_report(report: &CheckReport) {
+    if report.is_ok() {
+        println!("The mapping table matches the DAR directory.");
+        return;
+    }
+
+    if !report.unmapped.is_empty() {
+        println!("Priorities without a name in the table:");
+        for priority in &report.unmapped {
+            println!("  {priority}");
+        }
+    }
+    if !report.missing.is_empty() {
+        println!("Entries without a matching directory:");
+        for priority in &report.missing {
+            println!("  {priority}");
+        }
+    }
+    if !report.duplicates.is_empty() {
+        println!("Names used by more than one priority:");
+        for duplicate in &report.duplicates {
+            println!("  {}: {}", duplicate.name, duplicate.priorities.join(", "));
+        }
+    }
+}
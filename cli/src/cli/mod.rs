@@ -1,23 +1,158 @@
 #[cfg(feature = "color")]
 mod color;
 mod convert;
+mod mapping;
+mod output;
+mod progress;
+mod schema;
 
 use crate::error::Result;
 use crate::logger::LogLevel;
 use convert::dar2oar;
-use dar2oar_core::{Closure, remove_oar, unhide_dar};
+use dar2oar_core::fs::hide_journal::UnhideReport;
+use dar2oar_core::{
+    CancellationToken, ProgressEvent, RemoveOarOptions, remove_oar, unhide_dar,
+    unhide_dar_from_journal,
+};
+use output::{Outcome, Output, OutputFormat, Status};
+use progress::{Progress, print_summary};
 use std::path::PathBuf;
 
-pub(crate) async fn run_cli(args: Cli) -> Result<()> {
+pub(crate) async fn run_cli(args: Cli) -> Result<Status> {
     crate::logger::init(args.log_file, args.log_level, args.stdout)?;
 
-    match args.command {
-        Commands::Convert(args) => dar2oar(args).await?,
-        Commands::UnhideDar(args) => unhide_dar(args.dar_dir, Closure::default).await?,
-        Commands::RemoveOar(args) => remove_oar(args.target_path, Closure::default).await?,
+    let cancel_token = cancel_on_ctrl_c();
+    let mut output = Output::new(args.format, args.command.name());
+    // Logs written to stdout would break the bar.
+    let progress = Progress::new(output.is_text() && !args.no_progress && !args.stdout);
+    let outcome = run_command(args.command, &progress, &mut output, cancel_token).await;
+    progress.finish();
+    output.finish(outcome)
+}
+
+async fn run_command(
+    command: Commands,
+    progress: &Progress,
+    output: &mut Output,
+    cancel_token: CancellationToken,
+) -> Result<Outcome> {
+    let is_text = output.is_text();
+    let mut progress_fn = {
+        let mut bar = progress.index_fn();
+        let mut events = output.index_fn();
+        move |index| {
+            bar(index);
+            events(index);
+        }
+    };
+
+    Ok(match command {
+        Commands::Convert(args) => {
+            let on_event = |event: &ProgressEvent| {
+                progress.on_event(event);
+                output.on_event(event);
+            };
+            let report = dar2oar(args, on_event, cancel_token).await?;
+            progress.finish();
+            if is_text {
+                print_summary(&report);
+            }
+            let status = match report.warnings {
+                0 => Status::Success,
+                _ => Status::Partial,
+            };
+            Outcome::new(status, &report)?
+        }
+        Commands::UnhideDar(args) if args.from_journal => {
+            let report = unhide_dar_from_journal(args.dar_dir, progress_fn, cancel_token).await?;
+            progress.finish();
+            if is_text {
+                print_unhide_report(&report);
+            }
+            let status = match report.conflicts.len() {
+                0 => Status::Success,
+                _ => Status::Partial,
+            };
+            Outcome::new(status, &report)?
+        }
+        Commands::UnhideDar(args) => {
+            unhide_dar(args.dar_dir, &mut progress_fn, cancel_token).await?;
+            Outcome::new(Status::Success, &())?
+        }
+        Commands::RemoveOar(args) => {
+            let options = RemoveOarOptions {
+                force: args.force,
+                dry_run: args.dry_run,
+            };
+            let removed = remove_oar(args.target_path, options, progress_fn, cancel_token).await?;
+            progress.finish();
+            if is_text {
+                print_removed(&removed, args.dry_run);
+            }
+            Outcome::new(
+                Status::Success,
+                &RemoveOarResult {
+                    removed,
+                    dry_run: args.dry_run,
+                },
+            )?
+        }
+        Commands::Mapping(command) => mapping::run(command, is_text).await?,
+        Commands::Schema(args) => schema::run(args, is_text).await?,
+    })
+}
+
+/// Result of `remove-oar` in `--format json`.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoveOarResult {
+    removed: Vec<PathBuf>,
+    dry_run: bool,
+}
+
+/// Print the paths removed by `remove-oar`.
+#[allow(clippy::print_stdout)]
+fn print_removed(paths: &[PathBuf], dry_run: bool) {
+    if paths.is_empty() {
+        println!("No OAR directories generated by dar2oar were found.");
+        return;
+    }
+
+    let action = if dry_run { "Would remove" } else { "Removed" };
+    for path in paths {
+        println!("{action}: {}", path.display());
+    }
+}
+
+/// Print the result of `unhide-dar --from-journal`.
+#[allow(clippy::print_stdout)]
+fn print_unhide_report(report: &UnhideReport) {
+    for path in &report.restored {
+        println!("Restored: {}", path.display());
     }
+    for conflict in &report.conflicts {
+        println!(
+            "Conflict({:?}): {} -> {}",
+            conflict.kind,
+            conflict.hidden.display(),
+            conflict.unhidden.display()
+        );
+    }
+}
 
-    Ok(())
+/// Returns a token that is cancelled when `Ctrl+C` is pressed.
+fn cancel_on_ctrl_c() -> CancellationToken {
+    let cancel_token = CancellationToken::new();
+    let _handle = tokio::spawn({
+        let cancel_token = cancel_token.clone();
+        async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                tracing::warn!("Ctrl+C received. Cancelling...");
+                cancel_token.cancel();
+            }
+        }
+    });
+    cancel_token
 }
 
 /// Converter CLI version
@@ -42,6 +177,17 @@ pub(crate) struct Cli {
     #[clap(default_value = "./convert.log")]
     /// Output path of log file
     pub log_file: Option<PathBuf>,
+
+    #[clap(global = true, long, display_order = 103)]
+    /// Disable the progress bar (always disabled if stdout is not a terminal)
+    pub no_progress: bool,
+
+    #[clap(global = true, long, display_order = 104)]
+    #[clap(value_enum, ignore_case = true, default_value = "text")]
+    /// Output format. `json`/`ndjson` print the result (and events) as JSON to stdout.
+    ///
+    /// Exit codes: 0 = success, 1 = failure, 2 = invalid arguments, 3 = partial success (warnings or conflicts)
+    pub format: OutputFormat,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -57,8 +203,28 @@ enum Commands {
     UnhideDar(UnhideDarOption),
 
     #[clap(arg_required_else_help = true)]
-    /// Find and delete `OpenAnimationReplacer` directory
+    /// Find and delete the OAR namespaces generated by dar2oar in `OpenAnimationReplacer` directory
     RemoveOar(RemoveOarOption),
+
+    /// Generate or check the mapping table of priority -> section name
+    #[clap(subcommand)]
+    Mapping(mapping::MappingCommand),
+
+    /// Print the JSON Schema of OAR `config.json` to validate and complete it in editors
+    Schema(schema::SchemaArgs),
+}
+
+impl Commands {
+    /// Subcommand name in the JSON output.
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Convert(_) => "convert",
+            Self::UnhideDar(_) => "unhide-dar",
+            Self::RemoveOar(_) => "remove-oar",
+            Self::Mapping(command) => command.name(),
+            Self::Schema(_) => "schema",
+        }
+    }
 }
 
 #[derive(Debug, clap::Args)]
@@ -66,6 +232,10 @@ struct UnhideDarOption {
     #[clap(value_parser)]
     /// DAR directory containing files with ".mohidden" extension
     pub dar_dir: String,
+
+    #[clap(long)]
+    /// Reverse only the renames recorded by `convert --hide-dar` in the journal of this directory
+    pub from_journal: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -73,4 +243,12 @@ struct RemoveOarOption {
     #[clap(value_parser)]
     /// Path containing the "OpenAnimationReplacer" directory
     pub target_path: String,
+
+    #[clap(long)]
+    /// Delete whole "OpenAnimationReplacer" directories, including mods not generated by dar2oar
+    pub force: bool,
+
+    #[clap(long)]
+    /// Only print the paths that would be deleted
+    pub dry_run: bool,
 }
@@ -0,0 +1,250 @@
+//! Machine-readable output of the subcommands(`--format json|ndjson`)
+//!
+//! - `json`: A single result document is printed when the command ends.
+//! - `ndjson`: Progress events are printed line by line, followed by the result document.
+//!
+//! The result document always has `"type": "result"`, so it can be told apart from the events.
+use crate::error::Result;
+use dar2oar_core::ProgressEvent;
+use dar2oar_core::error::Diagnostic;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Output format of the subcommands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// One JSON document
+    Json,
+    /// JSON lines of the progress events and the result
+    Ndjson,
+}
+
+/// How the command ended. Also decides the exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Status {
+    /// Everything succeeded.
+    Success,
+    /// Succeeded, but with warnings or conflicts.
+    Partial,
+    /// Failed.
+    Failure,
+}
+
+impl Status {
+    /// - Success: 0
+    /// - Failure: 1
+    /// - Partial: 3(`2` is used by clap for usage errors)
+    pub(crate) const fn exit_code(self) -> i32 {
+        match self {
+            Self::Success => 0,
+            Self::Failure => 1,
+            Self::Partial => 3,
+        }
+    }
+}
+
+/// Result of a subcommand.
+#[derive(Debug)]
+pub(crate) struct Outcome {
+    pub(crate) status: Status,
+    pub(crate) result: serde_json::Value,
+}
+
+impl Outcome {
+    /// Serialize `result` into an outcome.
+    ///
+    /// # Errors
+    /// `result` cannot be serialized. e.g. non UTF-8 path
+    pub(crate) fn new(status: Status, result: &impl Serialize) -> Result<Self> {
+        Ok(Self {
+            status,
+            result: serde_json::to_value(result)?,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Warning {
+    path: PathBuf,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename = "result", rename_all = "camelCase")]
+struct Document<'a> {
+    command: &'a str,
+    status: Status,
+    result: Option<serde_json::Value>,
+    warnings: &'a [Warning],
+    error: Option<Diagnostic>,
+}
+
+/// Collects the output of a subcommand.
+#[derive(Debug)]
+pub(crate) struct Output {
+    format: OutputFormat,
+    command: &'static str,
+    warnings: Vec<Warning>,
+}
+
+impl Output {
+    pub(crate) const fn new(format: OutputFormat, command: &'static str) -> Self {
+        Self {
+            format,
+            command,
+            warnings: vec![],
+        }
+    }
+
+    pub(crate) fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    /// Record a converter event, printing it in `ndjson`.
+    pub(crate) fn on_event(&mut self, event: &ProgressEvent) {
+        if self.format == OutputFormat::Ndjson {
+            print_line(event);
+        }
+        if let ProgressEvent::Warning { path, message } = event {
+            self.warnings.push(Warning {
+                path: path.clone(),
+                message: message.clone(),
+            });
+        }
+    }
+
+    /// Returns a callback printing the `FnMut(usize)` progress of the support commands in `ndjson`.
+    ///
+    /// The first call is the total count and the later ones are indexes.
+    pub(crate) fn index_fn(&self) -> impl FnMut(usize) + use<> {
+        #[derive(Serialize)]
+        #[serde(tag = "type", rename_all = "camelCase")]
+        enum Event {
+            Started { total: usize },
+            Progress { index: usize },
+        }
+
+        let is_ndjson = self.format == OutputFormat::Ndjson;
+        let mut is_first = true;
+        move |index| {
+            if !is_ndjson {
+                return;
+            }
+            if is_first {
+                is_first = false;
+                print_line(&Event::Started { total: index });
+            } else {
+                print_line(&Event::Progress { index });
+            }
+        }
+    }
+
+    /// Print the result document and returns the status.
+    ///
+    /// In `text` format, errors are returned as is.
+    ///
+    /// # Errors
+    /// - `text`: the error of the command
+    /// - `json`/`ndjson`: failed to serialize the document
+    pub(crate) fn finish(self, outcome: Result<Outcome>) -> Result<Status> {
+        if self.is_text() {
+            return outcome.map(|outcome| outcome.status);
+        }
+
+        let (status, document) = self.document(outcome)?;
+        print_json(&document);
+        Ok(status)
+    }
+
+    /// Serialize the result document of `outcome`.
+    fn document(&self, outcome: Result<Outcome>) -> Result<(Status, String)> {
+        let (status, result, error) = match outcome {
+            Ok(Outcome { status, result }) => (status, Some(result), None),
+            Err(err) => {
+                tracing::error!("{err}");
+                (Status::Failure, None, Some(err.diagnostic()))
+            }
+        };
+        let document = Document {
+            command: self.command,
+            status,
+            result,
+            warnings: &self.warnings,
+            error,
+        };
+        Ok((status, serde_json::to_string(&document)?))
+    }
+}
+
+/// Print a JSON line. Values that cannot be serialized are only logged.
+fn print_line(value: &impl Serialize) {
+    match serde_json::to_string(value) {
+        Ok(json) => print_json(&json),
+        Err(err) => tracing::error!("Failed to serialize an event: {err}"),
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn print_json(json: &str) {
+    println!("{json}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn should_map_status_to_exit_code() {
+        assert_eq!(Status::Success.exit_code(), 0);
+        assert_eq!(Status::Failure.exit_code(), 1);
+        assert_eq!(Status::Partial.exit_code(), 3);
+    }
+
+    #[test]
+    fn should_serialize_result_document() -> Result<()> {
+        let mut output = Output::new(OutputFormat::Json, "convert");
+        output.on_event(&ProgressEvent::Warning {
+            path: "a/config.json".into(),
+            message: "warn".into(),
+        });
+        let outcome = Outcome::new(Status::Partial, &json!({ "files": 1 }))?;
+
+        let (status, document) = output.document(Ok(outcome))?;
+        assert_eq!(status, Status::Partial);
+        let expected = json!({
+            "type": "result",
+            "command": "convert",
+            "status": "partial",
+            "result": { "files": 1 },
+            "warnings": [{ "path": "a/config.json", "message": "warn" }],
+            "error": null,
+        });
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&document)?,
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_error_document() -> Result<()> {
+        let output = Output::new(OutputFormat::Json, "remove");
+        let err = std::io::Error::new(std::io::ErrorKind::NotFound, "not found");
+
+        let (status, document) = output.document(Err(err.into()))?;
+        assert_eq!(status, Status::Failure);
+        let document: serde_json::Value = serde_json::from_str(&document)?;
+        assert_eq!(document["type"], "result");
+        assert_eq!(document["status"], "failure");
+        assert_eq!(document["result"], serde_json::Value::Null);
+        assert_eq!(document["error"]["code"], "D2O0018");
+        assert_eq!(document["error"]["message"], "not found");
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Terminal progress display
+use dar2oar_core::{ConvertReport, ProgressEvent};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::io::IsTerminal as _;
+use std::path::Path;
+use std::time::Duration;
+
+#[cfg(feature = "color")]
+use super::color::paint;
+use clap::builder::styling::AnsiColor;
+
+#[cfg(feature = "color")]
+const TEMPLATE: &str =
+    "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} (ETA {eta}) {msg}";
+#[cfg(not(feature = "color"))]
+const TEMPLATE: &str = "{spinner} [{elapsed_precise}] [{wide_bar}] {pos}/{len} (ETA {eta}) {msg}";
+
+#[cfg(not(feature = "color"))]
+fn paint(text: &str, _color: AnsiColor) -> String {
+    text.into()
+}
+
+/// Progress bar drawn to stdout. Does nothing if disabled or stdout is not a terminal.
+#[derive(Debug, Clone)]
+pub(crate) struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let bar = (enabled && std::io::stdout().is_terminal()).then(|| {
+            let style = ProgressStyle::with_template(TEMPLATE)
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> ");
+            let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stdout());
+            bar.set_style(style);
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        });
+        Self { bar }
+    }
+
+    /// Update the bar with a converter event.
+    pub(crate) fn on_event(&self, event: &ProgressEvent) {
+        let Some(bar) = &self.bar else {
+            return;
+        };
+
+        match event {
+            ProgressEvent::Started { total } => bar.set_length(*total as u64),
+            ProgressEvent::FileStarted { path } => bar.set_message(file_name(path)),
+            ProgressEvent::FileDone { .. } => bar.inc(1),
+            ProgressEvent::Warning { path, message } => bar.println(format!(
+                "{} {}: {message}",
+                paint("warning:", AnsiColor::Yellow),
+                path.display()
+            )),
+            ProgressEvent::Finished { .. } => bar.finish_and_clear(),
+        }
+    }
+
+    /// Returns a callback for the `FnMut(usize)` progress of the support commands.
+    ///
+    /// The first call is the total count and the later ones are indexes.
+    pub(crate) fn index_fn(&self) -> impl FnMut(usize) + '_ {
+        let mut is_first = true;
+        move |index| {
+            let Some(bar) = &self.bar else {
+                return;
+            };
+            if is_first {
+                is_first = false;
+                bar.set_length(index as u64);
+            } else {
+                bar.set_position(index as u64 + 1);
+            }
+        }
+    }
+
+    /// Remove the bar, e.g. on an error or after a support command.
+    pub(crate) fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+/// Print the summary of a conversion.
+#[allow(clippy::print_stdout)]
+pub(crate) fn print_summary(report: &ConvertReport) {
+    print!("{}", summary(report, paint));
+}
+
+/// Render the summary lines of a conversion, coloring the labels with `paint`.
+fn summary(report: &ConvertReport, paint: fn(&str, AnsiColor) -> String) -> String {
+    let warnings = match report.warnings {
+        0 => paint("0 warnings", AnsiColor::Green),
+        n => paint(&format!("{n} warnings"), AnsiColor::Yellow),
+    };
+    let mut summary = format!(
+        "{} {} sections, {} files, {warnings}\n",
+        paint("Converted:", AnsiColor::Green),
+        report.conditions,
+        report.files(),
+    );
+    if report.variants > 0 {
+        summary += &format!(
+            "{} {} sections merged into animation variants\n",
+            paint("Variants:", AnsiColor::Green),
+            report.variants,
+        );
+    }
+    if report.deduplicated > 0 {
+        summary += &format!(
+            "{} {} sections, {} bytes saved\n",
+            paint("Deduplicated:", AnsiColor::Green),
+            report.deduplicated,
+            report.bytes_saved,
+        );
+    }
+    summary
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str, _color: AnsiColor) -> String {
+        text.into()
+    }
+
+    #[test]
+    fn should_render_summary() {
+        let report = ConvertReport {
+            conditions: 2,
+            motions: 5,
+            memos: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            summary(&report, plain),
+            "Converted: 2 sections, 8 files, 0 warnings\n"
+        );
+
+        let report = ConvertReport {
+            warnings: 3,
+            variants: 1,
+            deduplicated: 2,
+            bytes_saved: 1024,
+            ..report
+        };
+        assert_eq!(
+            summary(&report, plain),
+            "Converted: 2 sections, 8 files, 3 warnings
+Variants: 1 sections merged into animation variants
+Deduplicated: 2 sections, 1024 bytes saved
+"
+        );
+    }
+}
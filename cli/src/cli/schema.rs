@@ -0,0 +1,44 @@
+//! `schema` subcommand: dump the JSON Schema of OAR `config.json`
+use super::output::{Outcome, Status};
+use crate::error::Result;
+use oar_conditions::schema::{namespace_config_schema, section_config_schema};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct SchemaArgs {
+    #[clap(value_enum, ignore_case = true, default_value = "section")]
+    /// Which `config.json` the schema is for
+    kind: SchemaKind,
+
+    #[clap(long)]
+    /// Write the schema to this file (if none, print it)
+    output: Option<PathBuf>,
+}
+
+/// Kinds of OAR `config.json`.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum SchemaKind {
+    /// `config.json` of a section(animation root). e.g. `OpenAnimationReplacer/<mod>/<section>/config.json`
+    Section,
+    /// `config.json` of a namespace(mod root). e.g. `OpenAnimationReplacer/<mod>/config.json`
+    Namespace,
+}
+
+pub(crate) async fn run(args: SchemaArgs, is_text: bool) -> Result<Outcome> {
+    let schema = match args.kind {
+        SchemaKind::Section => section_config_schema(),
+        SchemaKind::Namespace => namespace_config_schema(),
+    };
+
+    match &args.output {
+        Some(output) => tokio::fs::write(output, serde_json::to_string_pretty(&schema)?).await?,
+        None if is_text => print_text(&serde_json::to_string_pretty(&schema)?),
+        None => {}
+    }
+    Outcome::new(Status::Success, &schema)
+}
+
+#[allow(clippy::print_stdout)]
+fn print_text(text: &str) {
+    println!("{text}");
+}
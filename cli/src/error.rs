@@ -1,4 +1,5 @@
 //! errors of `This crate`
+use dar2oar_core::error::{ConvertError, Diagnostic};
 
 /// GUI Error
 #[derive(Debug, snafu::Snafu)]
@@ -9,10 +10,17 @@ pub enum Error {
     FailedIo { source: std::io::Error },
 
     #[snafu(transparent)]
-    FailedConvert {
-        source: dar2oar_core::error::ConvertError,
+    FailedConvert { source: ConvertError },
+
+    #[snafu(transparent)]
+    FailedMapping {
+        source: mapping_table::builder::Error,
     },
 
+    /// JSON output error
+    #[snafu(transparent)]
+    FailedJson { source: serde_json::Error },
+
     ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
     /// Tracing log error
     #[snafu(transparent)]
@@ -27,5 +35,34 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Returns the structured form of the error.
+    ///
+    /// Errors of this crate use the same codes as [`ConvertError`] where they overlap,
+    /// and `D2O1xxx` for the CLI-only ones.
+    pub fn diagnostic(&self) -> Diagnostic {
+        let (code, help) = match self {
+            Self::FailedConvert { source } => return source.diagnostic(),
+            Self::FailedIo { .. } => ("D2O0018", None),
+            Self::FailedJson { .. } => ("D2O0016", None),
+            Self::FailedMapping { .. } => (
+                "D2O1002",
+                Some("Specify the mod dir containing `DynamicAnimationReplacer`."),
+            ),
+            Self::UnableSetTracing { .. } | Self::FailedReloadTracingSub { .. } => (
+                "D2O1001",
+                Some("Check the `--log-file` path and its write permission."),
+            ),
+        };
+        Diagnostic {
+            code,
+            message: self.to_string(),
+            path: None,
+            help,
+            related: vec![],
+        }
+    }
+}
+
 /// `Result` for this crate.
 pub type Result<T, E = Error> = core::result::Result<T, E>;
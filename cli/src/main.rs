@@ -13,15 +13,19 @@ async fn main() {
 
     #[allow(clippy::print_stderr)]
     match run_cli(Cli::parse()).await {
-        Ok(()) => {
+        Ok(status) => {
             let elapsed = start.elapsed();
             let time = (elapsed.as_secs(), elapsed.subsec_millis());
             tracing::info!("Elapsed time: {}.{}secs.", time.0, time.1);
-            exit(0);
+            exit(status.exit_code());
         }
         Err(err) => {
             tracing::error!("{err}");
-            eprintln!("{err}");
+            let diagnostic = err.diagnostic();
+            eprintln!("error[{}]: {}", diagnostic.code, diagnostic.message);
+            if let Some(help) = diagnostic.help {
+                eprintln!("  help: {help}");
+            }
             exit(1);
         }
     }
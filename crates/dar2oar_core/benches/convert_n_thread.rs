@@ -1,6 +1,8 @@
 use criterion::{Criterion, criterion_group, criterion_main};
 use dar2oar_core::fs::converter::{parallel, sequential};
-use dar2oar_core::{Closure, ConvertOptions, read_mapping_table, remove_oar};
+use dar2oar_core::{
+    CancellationToken, Closure, ConvertOptions, RemoveOarOptions, read_mapping_table, remove_oar,
+};
 use std::hint::black_box;
 use std::time::Duration;
 
@@ -19,7 +21,14 @@ fn criterion_benchmark(c: &mut Criterion) {
         let rt = tokio::runtime::Runtime::new().unwrap();
         b.to_async(rt).iter(|| async {
             if std::path::Path::new(REMOVE_TARGET).exists() {
-                remove_oar(REMOVE_TARGET, Closure::default).await.unwrap();
+                remove_oar(
+                    REMOVE_TARGET,
+                    RemoveOarOptions::default(),
+                    Closure::default,
+                    CancellationToken::new(),
+                )
+                .await
+                .unwrap();
             }
             let mapping = read_mapping_table(TABLE_PATH).await.unwrap();
 
@@ -29,7 +38,8 @@ fn criterion_benchmark(c: &mut Criterion) {
                     section_table: Some(mapping),
                     ..Default::default()
                 }),
-                Closure::default,
+                Closure::default_event,
+                CancellationToken::new(),
             )
             .await
         })
@@ -39,7 +49,14 @@ fn criterion_benchmark(c: &mut Criterion) {
         let rt = tokio::runtime::Runtime::new().unwrap();
         b.to_async(&rt).iter(|| async {
             if std::path::Path::new(REMOVE_TARGET).exists() {
-                remove_oar(REMOVE_TARGET, Closure::default).await.unwrap();
+                remove_oar(
+                    REMOVE_TARGET,
+                    RemoveOarOptions::default(),
+                    Closure::default,
+                    CancellationToken::new(),
+                )
+                .await
+                .unwrap();
             }
             let mapping = read_mapping_table(TABLE_PATH).await.unwrap();
 
@@ -49,7 +66,8 @@ fn criterion_benchmark(c: &mut Criterion) {
                     section_table: Some(mapping),
                     ..Default::default()
                 }),
-                Closure::default,
+                Closure::default_event,
+                CancellationToken::new(),
             )
             .await
         })
@@ -0,0 +1,345 @@
+//! `(plugin, FormID)` -> `EditorID` dictionary exported from xEdit.
+//!
+//! With the dictionary, the converter
+//! - turns the keyword forms into `{ "editorID": ... }`(OAR resolves them at runtime),
+//! - writes the EditorIDs into the section description,
+//! - warns on the FormIDs that are not in the dictionary.
+use crate::error::{ConvertError, Result};
+use crate::forms::{FormSlot, for_each_form};
+use oar_conditions::conditions::Oar;
+use oar_values::{FormValue, Keyword, LiteralValue, PluginValue};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// `(plugin, FormID)` -> `EditorID`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditorIdDictionary {
+    /// (lowercase plugin name, FormID without load order) -> `EditorID`
+    entries: HashMap<(String, u32), String>,
+}
+
+impl EditorIdDictionary {
+    /// Create an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there is no entry.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Insert an entry. Returns `false` if `form_id` is not hexadecimal.
+    ///
+    /// The load order index of `form_id`(e.g. `01` of `0100ABCD`) is ignored.
+    pub fn insert(&mut self, plugin: &str, form_id: &str, editor_id: impl Into<String>) -> bool {
+        let Some(form_id) = parse_form_id(form_id) else {
+            return false;
+        };
+        self.entries
+            .insert((plugin.to_ascii_lowercase(), form_id), editor_id.into());
+        true
+    }
+
+    /// Look up the `EditorID` of `form_id` in `plugin`.
+    pub fn get(&self, plugin: &str, form_id: &str) -> Option<&str> {
+        let form_id = parse_form_id(form_id)?;
+        self.entries
+            .get(&(plugin.to_ascii_lowercase(), form_id))
+            .map(String::as_str)
+    }
+
+    /// Parse the CSV exported from xEdit.
+    ///
+    /// The columns are found by the header(`Plugin`/`File`, `FormID`, `EditorID`/`EDID`).
+    /// Without a header, they are `plugin,formID,editorID`. `;` and tab are also accepted as separator.
+    ///
+    /// # Info
+    /// Now, `path` is only used in case of errors.
+    ///
+    /// # Errors
+    /// A row has too few columns or an invalid FormID.
+    pub fn parse_csv(path: impl AsRef<Path>, input: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+        let separator = lines.peek().map_or(',', |(_, line)| {
+            [',', ';', '\t']
+                .into_iter()
+                .max_by_key(|separator| line.matches(*separator).count())
+                .unwrap_or(',')
+        });
+
+        let mut columns = [0, 1, 2];
+        if let Some((_, header)) = lines.peek() {
+            let header = split_row(header, separator);
+            if let Some(found) = header_columns(&header) {
+                columns = found;
+                lines.next();
+            }
+        }
+
+        let mut dictionary = Self::new();
+        for (index, line) in lines {
+            let row = split_row(line, separator);
+            let invalid = |reason: &str| ConvertError::InvalidEditorIdDictionary {
+                path: path.to_path_buf(),
+                reason: format!("line {}: {reason}", index + 1),
+            };
+
+            let [plugin, form_id, editor_id] = columns.map(|column| row.get(column));
+            let (Some(plugin), Some(form_id), Some(editor_id)) = (plugin, form_id, editor_id)
+            else {
+                return Err(invalid("expected plugin, FormID and EditorID columns"));
+            };
+            if !dictionary.insert(plugin, form_id, editor_id.as_str()) {
+                return Err(invalid(&format!("invalid FormID `{form_id}`")));
+            }
+        }
+        Ok(dictionary)
+    }
+
+    /// Parse a JSON array of `{ "plugin": ..., "formID": ..., "editorID": ... }`.
+    ///
+    /// # Info
+    /// Now, `path` is only used in case of errors.
+    ///
+    /// # Errors
+    /// Invalid JSON or FormID.
+    pub fn parse_json(path: impl AsRef<Path>, input: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let entries: Vec<JsonEntry> =
+            serde_json::from_str(input).map_err(|err| ConvertError::InvalidEditorIdDictionary {
+                path: path.to_path_buf(),
+                reason: err.to_string(),
+            })?;
+
+        let mut dictionary = Self::new();
+        for JsonEntry {
+            plugin,
+            form_id,
+            editor_id,
+        } in entries
+        {
+            if !dictionary.insert(&plugin, &form_id, editor_id) {
+                return Err(ConvertError::InvalidEditorIdDictionary {
+                    path: path.to_path_buf(),
+                    reason: format!("invalid FormID `{form_id}`"),
+                });
+            }
+        }
+        Ok(dictionary)
+    }
+
+    /// Replace the keyword forms found in the dictionary with their `EditorID`,
+    /// and collect the `EditorID`s & unknown forms of `conditions`.
+    ///
+    /// With `to_literals: false`, the keywords are kept as forms.
+    /// `editorID` literals need OAR 2.0, so pass `false` when targeting an older OAR.
+    pub fn resolve_conditions(&self, conditions: &mut [Oar<'_>], to_literals: bool) -> Resolved {
+        let mut resolved = Resolved::default();
+        let mut record = |form: &PluginValue<'_>| {
+            let plugin = form.plugin_name.as_ref();
+            let form_id = form.form_id.as_str();
+            match self.get(plugin, form_id) {
+                Some(editor_id) => {
+                    if !resolved.editor_ids.iter().any(|id| id == editor_id) {
+                        resolved.editor_ids.push(editor_id.to_string());
+                    }
+                    Some(editor_id.to_string())
+                }
+                None => {
+                    resolved.unknown.push(format!("{plugin}|0x{form_id}"));
+                    None
+                }
+            }
+        };
+
+        for_each_form(conditions, &mut |slot| match slot {
+            FormSlot::Plugin(form, _) => {
+                record(form);
+            }
+            FormSlot::Keyword(keyword, _) => {
+                if let Keyword::Form(FormValue { form }) = keyword
+                    && let Some(editor_id) = record(form)
+                    && to_literals
+                {
+                    *keyword = Keyword::Literal(LiteralValue {
+                        editor_id: editor_id.into(),
+                    });
+                }
+            }
+        });
+
+        let mut seen = HashSet::new();
+        resolved.unknown.retain(|form| seen.insert(form.clone()));
+        resolved
+    }
+}
+
+/// Result of [`EditorIdDictionary::resolve_conditions`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resolved {
+    /// `EditorID`s of the forms in the conditions(without duplicates)
+    pub editor_ids: Vec<String>,
+    /// Forms not found in the dictionary(without duplicates). e.g. `Skyrim.esm|0x13BBF`
+    pub unknown: Vec<String>,
+}
+
+impl Resolved {
+    /// Section description listing the `EditorID`s, or `None` if there is no one.
+    pub fn description(&self) -> Option<String> {
+        (!self.editor_ids.is_empty()).then(|| format!("EditorIDs: {}", self.editor_ids.join(", ")))
+    }
+
+    /// Warnings for the unknown forms.
+    pub fn warnings(&self) -> Vec<String> {
+        self.unknown
+            .iter()
+            .map(|form| format!("{form} is not found in the EditorID dictionary."))
+            .collect()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct JsonEntry {
+    #[serde(alias = "pluginName", alias = "file")]
+    plugin: String,
+    #[serde(rename = "formID", alias = "formId", alias = "form_id")]
+    form_id: String,
+    #[serde(rename = "editorID", alias = "editorId", alias = "editor_id")]
+    editor_id: String,
+}
+
+/// Hex FormID without the load order index. e.g. `0x0100ABCD` -> `0xABCD`, `FE001800` -> `0x800`
+fn parse_form_id(form_id: &str) -> Option<u32> {
+    let form_id = form_id.trim();
+    let hex = form_id
+        .strip_prefix("0x")
+        .or_else(|| form_id.strip_prefix("0X"))
+        .unwrap_or(form_id);
+    let form_id = u32::from_str_radix(hex, 16).ok()?;
+    Some(match form_id >> 24 {
+        0xFE => form_id & 0xFFF, // light plugin
+        _ => form_id & 0x00FF_FFFF,
+    })
+}
+
+/// Indexes of `[plugin, FormID, EditorID]` if `header` is a header row.
+fn header_columns(header: &[String]) -> Option<[usize; 3]> {
+    let find = |names: &[&str]| {
+        header.iter().position(|column| {
+            let column = column.to_ascii_lowercase().replace([' ', '_'], "");
+            names.contains(&column.as_str())
+        })
+    };
+    Some([
+        find(&["plugin", "pluginname", "file", "filename"])?,
+        find(&["formid", "id"])?,
+        find(&["editorid", "edid"])?,
+    ])
+}
+
+/// Split a CSV row, removing the quotes(`"a,b"` -> `a,b`, `""` -> `"`).
+fn split_row(line: &str, separator: char) -> Vec<String> {
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => row.push(core::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    row.push(field);
+    row.into_iter()
+        .map(|field| field.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_dar2oar;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_csv_with_header() -> Result<()> {
+        let input = "EditorID;FormID;Plugin\nWeapTypeDagger;0101E713;\"Skyrim.esm\"\n";
+        let dictionary = EditorIdDictionary::parse_csv("test.csv", input)?;
+        assert_eq!(
+            dictionary.get("skyrim.esm", "1e713"),
+            Some("WeapTypeDagger")
+        );
+        assert_eq!(dictionary.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_form_id() {
+        let input = "Skyrim.esm,XYZ,Foo\n";
+        assert!(EditorIdDictionary::parse_csv("test.csv", input).is_err());
+    }
+
+    #[test]
+    fn should_resolve_conditions() -> Result<()> {
+        let input =
+            r#"[{ "plugin": "Skyrim.esm", "formID": "0001E713", "editorID": "WeapTypeDagger" }]"#;
+        let dictionary = EditorIdDictionary::parse_json("test.json", input)?;
+
+        let dar = r#"IsEquippedRightHasKeyword("Skyrim.esm" | 0x1E713) AND
+IsActorBase("Skyrim.esm" | 0x13BBF) OR
+IsActorBase("Skyrim.esm" | 0x13BBF)"#;
+        let mut conditions = parse_dar2oar("test", dar)?;
+        let resolved = dictionary.resolve_conditions(&mut conditions, true);
+
+        assert_eq!(
+            resolved.description().as_deref(),
+            Some("EditorIDs: WeapTypeDagger")
+        );
+        assert_eq!(resolved.unknown, ["Skyrim.esm|0x13BBF"]);
+        let Some(Oar::IsEquippedHasKeyword(is)) = conditions.first() else {
+            panic!("expected IsEquippedHasKeyword: {conditions:?}");
+        };
+        assert_eq!(
+            is.keyword,
+            Keyword::Literal(LiteralValue {
+                editor_id: "WeapTypeDagger".into()
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_keyword_forms_without_literals() -> Result<()> {
+        let input =
+            r#"[{ "plugin": "Skyrim.esm", "formID": "0001E713", "editorID": "WeapTypeDagger" }]"#;
+        let dictionary = EditorIdDictionary::parse_json("test.json", input)?;
+
+        let dar = r#"IsEquippedRightHasKeyword("Skyrim.esm" | 0x1E713)"#;
+        let mut conditions = parse_dar2oar("test", dar)?;
+        let expected = conditions.clone();
+        let resolved = dictionary.resolve_conditions(&mut conditions, false);
+
+        assert_eq!(
+            resolved.description().as_deref(),
+            Some("EditorIDs: WeapTypeDagger")
+        );
+        assert_eq!(conditions, expected);
+        Ok(())
+    }
+}
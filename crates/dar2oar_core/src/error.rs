@@ -1,7 +1,8 @@
 //! Error types for Converter
 
+use serde::Serialize;
 use snafu::Snafu;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents different types of errors that can occur during the conversion process.
 #[derive(Debug, Snafu)]
@@ -9,6 +10,10 @@ pub enum ConvertError {
     /// Only `And` or `Or` can be converted to Vec.
     CastError,
 
+    /// The operation was cancelled by the user.
+    #[snafu(display("The operation was cancelled by the user."))]
+    Cancelled,
+
     /// Path interpreted as the path to `ActorBase`, but the ID directory is missing.
     #[snafu(display("Path was interpreted as the path to ActorBase, but the ID directory is missing. expected: [..]/DynamicAnimationReplacer/{{ESP name}}/{{ID Number}}, actual: {}", path.display()))]
     MissingBaseId {
@@ -17,7 +22,11 @@ pub enum ConvertError {
     },
 
     /// Never converted.
-    NeverConverted,
+    #[snafu(display("No DAR file was converted in \"{}\"", path.display()))]
+    NeverConverted {
+        /// DAR dir
+        path: PathBuf,
+    },
 
     /// No such paths exist.
     #[snafu(display("No such paths exist: \"{}\"", path.display()))]
@@ -27,25 +36,60 @@ pub enum ConvertError {
     },
 
     /// Nothing in the specified path.
-    NotFoundEntry,
+    #[snafu(display("Failed to read an entry in \"{}\"", path.display()))]
+    NotFoundEntry {
+        /// Searched dir
+        path: PathBuf,
+    },
 
     /// Could not find files with ".mohidden" extension.
-    NotFoundUnhideTarget,
+    #[snafu(display("Could not find files with \".mohidden\" extension in \"{}\"", path.display()))]
+    NotFoundUnhideTarget {
+        /// DAR dir
+        path: PathBuf,
+    },
+
+    /// Not found the journal of the renames made by `hide_dar`.
+    #[snafu(display("Not found the hide journal: \"{}\"", path.display()))]
+    NotFoundHideJournal {
+        /// path
+        path: PathBuf,
+    },
 
     /// Not found `DynamicAnimationReplacer` directory.
-    NotFoundDarDir,
+    #[snafu(display("Not found \"DynamicAnimationReplacer\" directory in \"{}\"", path.display()))]
+    NotFoundDarDir {
+        /// path
+        path: PathBuf,
+    },
 
     /// Not found file name.
-    NotFoundFileName,
+    #[snafu(display("Not found file name: \"{}\"", path.display()))]
+    NotFoundFileName {
+        /// path
+        path: PathBuf,
+    },
 
     /// Not found `OpenAnimationReplacer` directory.
-    NotFoundOarDir,
+    #[snafu(display("Not found \"OpenAnimationReplacer\" directory in \"{}\"", path.display()))]
+    NotFoundOarDir {
+        /// Searched dir
+        path: PathBuf,
+    },
 
     /// Not found DAR priority (Number) directory.
-    NotFoundPriorityDir,
+    #[snafu(display("Not found DAR priority (Number) directory: \"{}\"", path.display()))]
+    NotFoundPriorityDir {
+        /// path
+        path: PathBuf,
+    },
 
     /// This is not valid UTF-8.
-    InvalidUtf8,
+    #[snafu(display("This is not valid UTF-8: \"{}\"", path.display()))]
+    InvalidUtf8 {
+        /// path
+        path: PathBuf,
+    },
 
     /// DAR syntax error with path.
     #[snafu(display("[DAR Syntax Error] {}\n{}", path.display(), source))]
@@ -107,6 +151,146 @@ pub enum ConvertError {
     #[allow(clippy::use_self)]
     #[snafu(display("Errors: \n{}", errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(",\n\n")))]
     NestedError { errors: Vec<ConvertError> },
+
+    /// The `EditorID` dictionary could not be parsed.
+    #[snafu(display("Invalid EditorID dictionary {}: {reason}", path.display()))]
+    InvalidEditorIdDictionary {
+        /// Dictionary file
+        path: PathBuf,
+        /// What is wrong. e.g. the line and the invalid FormID
+        reason: String,
+    },
+}
+
+impl ConvertError {
+    /// Stable code of the error kind(e.g. `D2O0012`), for scripts and localization.
+    ///
+    /// Unlike the messages, codes never change between versions. A new variant gets the next number.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::CastError => "D2O0001",
+            Self::Cancelled => "D2O0002",
+            Self::MissingBaseId { .. } => "D2O0003",
+            Self::NeverConverted { .. } => "D2O0004",
+            Self::NonExistPath { .. } => "D2O0005",
+            Self::NotFoundEntry { .. } => "D2O0006",
+            Self::NotFoundUnhideTarget { .. } => "D2O0007",
+            Self::NotFoundHideJournal { .. } => "D2O0008",
+            Self::NotFoundDarDir { .. } => "D2O0009",
+            Self::NotFoundFileName { .. } => "D2O0010",
+            Self::NotFoundOarDir { .. } => "D2O0011",
+            Self::NotFoundPriorityDir { .. } => "D2O0012",
+            Self::InvalidUtf8 { .. } => "D2O0013",
+            Self::InvalidDarSyntax { .. } => "D2O0014",
+            Self::ConditionError { .. } => "D2O0015",
+            Self::JsonError { .. } => "D2O0016",
+            Self::ParseIntError { .. } => "D2O0017",
+            Self::IOError { .. } => "D2O0018",
+            Self::AsyncWalkDirError { .. } => "D2O0019",
+            Self::JoinError { .. } => "D2O0020",
+            Self::MappingTableError { .. } => "D2O0021",
+            Self::NestedError { .. } => "D2O0022",
+            Self::InvalidEditorIdDictionary { .. } => "D2O0023",
+        }
+    }
+
+    /// The path the error is about, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::MissingBaseId { path }
+            | Self::NeverConverted { path }
+            | Self::NonExistPath { path }
+            | Self::NotFoundEntry { path }
+            | Self::NotFoundUnhideTarget { path }
+            | Self::NotFoundHideJournal { path }
+            | Self::NotFoundDarDir { path }
+            | Self::NotFoundFileName { path }
+            | Self::NotFoundOarDir { path }
+            | Self::NotFoundPriorityDir { path }
+            | Self::InvalidUtf8 { path }
+            | Self::InvalidDarSyntax { path, .. }
+            | Self::MappingTableError { path, .. }
+            | Self::InvalidEditorIdDictionary { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// A hint on how to fix the error, if any.
+    pub const fn help(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::MissingBaseId { .. } => {
+                "Put the files in `DynamicAnimationReplacer/<plugin name>/<FormID>/`."
+            }
+            Self::NeverConverted { .. } | Self::NotFoundDarDir { .. } => {
+                "Specify the mod dir containing `meshes/actors/character/animations/DynamicAnimationReplacer`."
+            }
+            Self::NonExistPath { .. } => "Check that the path is spelled correctly.",
+            Self::NotFoundEntry { .. } => "Check the read permission of the directory.",
+            Self::NotFoundUnhideTarget { .. } => {
+                "Nothing to unhide. The files may already be unhidden."
+            }
+            Self::NotFoundHideJournal { .. } => {
+                "The journal is written by `hide_dar`. Use unhide without the journal instead."
+            }
+            Self::NotFoundOarDir { .. } => {
+                "Specify the dir containing `meshes/actors/character/animations/OpenAnimationReplacer`."
+            }
+            Self::NotFoundPriorityDir { .. } => {
+                "Put the files in `DynamicAnimationReplacer/_CustomConditions/<priority number>/`."
+            }
+            Self::InvalidUtf8 { .. } => "Rename the file using only UTF-8 characters.",
+            Self::InvalidDarSyntax { .. } => {
+                "Fix the syntax of `_conditions.txt` at the shown position."
+            }
+            Self::MappingTableError { .. } => {
+                "Each line of the mapping table must be `<priority> <section name>`."
+            }
+            Self::InvalidEditorIdDictionary { .. } => {
+                "Export `plugin,FormID,EditorID` rows as CSV, or a JSON array of them, from xEdit."
+            }
+            _ => return None,
+        })
+    }
+
+    /// Returns the structured form of the error.
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            code: self.code(),
+            message: self.to_string(),
+            path: self.path().map(Path::to_path_buf),
+            help: self.help(),
+            related: match self {
+                Self::NestedError { errors } => errors.iter().map(Self::diagnostic).collect(),
+                _ => vec![],
+            },
+        }
+    }
+}
+
+/// Structured form of [`ConvertError`], shown by the CLI, the GUI and the JSON output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Stable code. See [`ConvertError::code`].
+    pub code: &'static str,
+    /// Human readable message
+    pub message: String,
+    /// The path the error is about
+    pub path: Option<PathBuf>,
+    /// How to fix it
+    pub help: Option<&'static str>,
+    /// Diagnostics of [`ConvertError::NestedError`]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<Diagnostic>,
+}
+
+/// Serialized as [`Diagnostic`].
+impl Serialize for ConvertError {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.diagnostic().serialize(serializer)
+    }
 }
 
 // Implemented to facilitate testing with the `assert_eq!` macro.
@@ -142,3 +326,24 @@ impl PartialEq for ConvertError {
 /// }
 /// ```
 pub type Result<T, Error = ConvertError> = core::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_serialize_as_diagnostic() -> Result<()> {
+        let err = ConvertError::NotFoundPriorityDir {
+            path: PathBuf::from("DynamicAnimationReplacer/_CustomConditions"),
+        };
+        let expected = serde_json::json!({
+            "code": "D2O0012",
+            "message": "Not found DAR priority (Number) directory: \"DynamicAnimationReplacer/_CustomConditions\"",
+            "path": "DynamicAnimationReplacer/_CustomConditions",
+            "help": "Put the files in `DynamicAnimationReplacer/_CustomConditions/<priority number>/`.",
+        });
+        assert_eq!(serde_json::to_value(&err)?, expected);
+        Ok(())
+    }
+}
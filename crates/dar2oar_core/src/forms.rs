@@ -0,0 +1,327 @@
+//! Forms(`PluginValue`s) referenced by the converted conditions.
+use oar_conditions::conditions::Oar;
+use oar_values::{FormIdStyle, FormValue, Keyword, NumericValue, PluginKind, PluginValue};
+use std::sync::Arc;
+use tes_plugin::{PluginIndex, RecordType};
+
+/// A value that holds a form, with the record type the condition expects(`None` if any).
+pub(crate) enum FormSlot<'s, 'a> {
+    Plugin(&'s mut PluginValue<'a>, Option<RecordType>),
+    Keyword(&'s mut Keyword<'a>, RecordType),
+}
+
+/// Call `f` with every form in `conditions`, including nested `AND`/`OR`.
+pub(crate) fn for_each_form<'a>(conditions: &mut [Oar<'a>], f: &mut impl FnMut(FormSlot<'_, 'a>)) {
+    fn numeric<'a>(value: &mut NumericValue<'a>, f: &mut impl FnMut(FormSlot<'_, 'a>)) {
+        if let NumericValue::GlobalVariable(FormValue { form }) = value {
+            f(FormSlot::Plugin(form, Some(RecordType::GLOB)));
+        }
+    }
+
+    for condition in conditions {
+        match condition {
+            Oar::And(and) => for_each_form(&mut and.conditions, f),
+            Oar::Or(or) => for_each_form(&mut or.conditions, f),
+            Oar::CompareValues(compare) => {
+                numeric(&mut compare.value_a, f);
+                numeric(&mut compare.value_b, f);
+            }
+            Oar::CurrentGameTime(time) => numeric(&mut time.numeric_value, f),
+            Oar::Level(level) => numeric(&mut level.numeric_value, f),
+            Oar::RandomCondition(random) => numeric(&mut random.numeric_value, f),
+            Oar::FactionRank(rank) => {
+                f(FormSlot::Plugin(&mut rank.faction, Some(RecordType::FACT)));
+                numeric(&mut rank.numeric_value, f);
+            }
+            Oar::HasKeyword(has) => f(FormSlot::Keyword(&mut has.keyword, RecordType::KYWD)),
+            Oar::HasMagicEffectWithKeyword(has) => {
+                f(FormSlot::Keyword(&mut has.keyword, RecordType::KYWD));
+            }
+            Oar::HasRefType(has) => {
+                f(FormSlot::Keyword(
+                    &mut has.location_ref_type,
+                    RecordType::LCRT,
+                ));
+            }
+            Oar::IsEquippedHasKeyword(is) => {
+                f(FormSlot::Keyword(&mut is.keyword, RecordType::KYWD));
+            }
+            Oar::IsWornHasKeyword(is) => f(FormSlot::Keyword(&mut is.keyword, RecordType::KYWD)),
+            // Weapon, armor, spell, ...
+            Oar::IsEquipped(is) => f(FormSlot::Plugin(&mut is.form, None)),
+            Oar::CurrentWeather(weather) => f(FormSlot::Plugin(
+                &mut weather.weather,
+                Some(RecordType::WTHR),
+            )),
+            Oar::HasMagicEffect(has) => f(FormSlot::Plugin(
+                &mut has.magic_effect,
+                Some(RecordType::MGEF),
+            )),
+            Oar::HasPerk(has) => f(FormSlot::Plugin(&mut has.perk, Some(RecordType::PERK))),
+            Oar::HasSpell(has) => f(FormSlot::Plugin(&mut has.spell, Some(RecordType::SPEL))),
+            Oar::IsActorBase(is) => f(FormSlot::Plugin(&mut is.actor_base, Some(RecordType::NPC_))),
+            Oar::IsClass(is) => f(FormSlot::Plugin(&mut is.class, Some(RecordType::CLAS))),
+            Oar::IsCombatStyle(is) => f(FormSlot::Plugin(
+                &mut is.combat_style,
+                Some(RecordType::CSTY),
+            )),
+            Oar::IsEquippedShout(is) => f(FormSlot::Plugin(&mut is.shout, Some(RecordType::SHOU))),
+            Oar::IsInFaction(is) => f(FormSlot::Plugin(&mut is.faction, Some(RecordType::FACT))),
+            Oar::IsInLocation(is) => f(FormSlot::Plugin(&mut is.location, Some(RecordType::LCTN))),
+            Oar::IsParentCell(is) => f(FormSlot::Plugin(&mut is.cell, Some(RecordType::CELL))),
+            Oar::IsRace(is) => f(FormSlot::Plugin(&mut is.race, Some(RecordType::RACE))),
+            Oar::IsVoiceType(is) => f(FormSlot::Plugin(&mut is.voice_type, Some(RecordType::VTYP))),
+            Oar::IsWorldSpace(is) => f(FormSlot::Plugin(
+                &mut is.world_space,
+                Some(RecordType::WRLD),
+            )),
+            Oar::IsWorn(is) => f(FormSlot::Plugin(&mut is.form, Some(RecordType::ARMO))),
+            Oar::Condition(_)
+            | Oar::Preset(_)
+            | Oar::IsEquippedType(_)
+            | Oar::IsDirectionMovement(_) => {}
+        }
+    }
+}
+
+/// Read the plugins referenced by `conditions` on a blocking thread,
+/// so that checking the forms afterwards doesn't block the async tasks.
+///
+/// The plugins that fail to be read are kept in `index` as the errors, and warned on the check.
+pub(crate) async fn load_plugins(index: &Arc<PluginIndex>, conditions: &mut [Oar<'_>]) {
+    let mut names = std::collections::BTreeSet::new();
+    for_each_form(conditions, &mut |slot| match slot {
+        FormSlot::Plugin(form, _) | FormSlot::Keyword(Keyword::Form(FormValue { form }), _) => {
+            if index.loaded(&form.plugin_name).is_none() {
+                names.insert(form.plugin_name.to_string());
+            }
+        }
+        FormSlot::Keyword(Keyword::Literal(_), _) => {}
+    });
+    if names.is_empty() {
+        return;
+    }
+
+    let index = Arc::clone(index);
+    let _ = tokio::task::spawn_blocking(move || {
+        for name in names {
+            let _ = index.plugin(&name);
+        }
+    })
+    .await;
+}
+
+/// Canonicalize each FormID of `conditions`: strip the load order byte(s) and write it in `style`.
+///
+/// The plugin kind(master/regular/light) is read from the plugin header if `index` has already
+/// loaded it(see [`load_plugins`]), otherwise guessed by the extension.
+/// Nothing is read here, so this never blocks.
+///
+/// Returns the warnings for the IDs that can't be valid for the plugin(without duplicates).
+pub(crate) fn canonicalize_forms(
+    index: Option<&PluginIndex>,
+    style: FormIdStyle,
+    conditions: &mut [Oar<'_>],
+) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut kinds = std::collections::HashMap::new();
+    let mut canonicalize = |form: &mut PluginValue<'_>| {
+        let kind = *kinds
+            .entry(form.plugin_name.to_ascii_lowercase())
+            .or_insert_with(|| {
+                match index.and_then(|index| index.loaded(&form.plugin_name)?.ok()) {
+                    Some(plugin) if plugin.is_light() => PluginKind::Light,
+                    Some(plugin) if plugin.is_master() => PluginKind::Master,
+                    Some(_) => PluginKind::Plugin,
+                    None => PluginKind::from_name(&form.plugin_name),
+                }
+            });
+
+        let (form_id, warning) = form.form_id.canonicalize(&form.plugin_name, kind, style);
+        if let Some(warning) = warning {
+            warnings.push(format!(
+                "{}|0x{}: {warning}",
+                form.plugin_name,
+                form.form_id.as_str()
+            ));
+        }
+        form.form_id = form_id;
+    };
+
+    for_each_form(conditions, &mut |slot| match slot {
+        FormSlot::Plugin(form, _) => canonicalize(form),
+        FormSlot::Keyword(keyword, _) => {
+            if let Keyword::Form(FormValue { form }) = keyword {
+                canonicalize(form);
+            }
+        }
+    });
+
+    let mut seen = std::collections::HashSet::new();
+    warnings.retain(|warning| seen.insert(warning.clone()));
+    warnings
+}
+
+/// Check that each form of `conditions` exists in the plugins of the `Data` dir
+/// with the expected record type.
+///
+/// FormIDs of light plugins are normalized to the light plugin range in `style`. e.g. `FE000800` -> `800`
+///
+/// Returns the warnings(without duplicates).
+pub(crate) fn validate_forms(
+    index: &PluginIndex,
+    style: FormIdStyle,
+    conditions: &mut [Oar<'_>],
+) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut check = |form: &mut PluginValue<'_>, expected: Option<RecordType>| {
+        let label = format!("{}|0x{}", form.plugin_name, form.form_id.as_str());
+        let Ok(form_id) = u32::from_str_radix(form.form_id.as_str(), 16) else {
+            warnings.push(format!("{label}: Invalid FormID."));
+            return;
+        };
+        let plugin = match index.plugin(&form.plugin_name) {
+            Ok(plugin) => plugin,
+            Err(err) => {
+                warnings.push(format!("{label}: {err}"));
+                return;
+            }
+        };
+
+        if plugin.is_light() {
+            // The out of range IDs are already warned by `canonicalize_forms`.
+            let (form_id, _) =
+                form.form_id
+                    .canonicalize(&form.plugin_name, PluginKind::Light, style);
+            form.form_id = form_id;
+        }
+        match (plugin.get(form_id), expected) {
+            (None, _) => warnings.push(format!("{label}: Not found in {}.", plugin.name)),
+            (Some(found), Some(expected)) if found != expected => {
+                warnings.push(format!(
+                    "{label}: Expected {expected} record, but found {found}."
+                ));
+            }
+            _ => {}
+        }
+    };
+
+    for_each_form(conditions, &mut |slot| match slot {
+        FormSlot::Plugin(form, expected) => check(form, expected),
+        FormSlot::Keyword(keyword, expected) => {
+            if let Keyword::Form(FormValue { form }) = keyword {
+                check(form, Some(expected));
+            }
+        }
+    });
+
+    let mut seen = std::collections::HashSet::new();
+    warnings.retain(|warning| seen.insert(warning.clone()));
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_dar2oar;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    /// `Mini.esl` defining `KYWD 0x800`.
+    fn light_plugin() -> Vec<u8> {
+        let mut tes4 = b"HEDR".to_vec();
+        tes4.extend(12_u16.to_le_bytes());
+        tes4.extend([0; 12]);
+
+        let mut bytes = b"TES4".to_vec();
+        bytes.extend((tes4.len() as u32).to_le_bytes());
+        bytes.extend([0; 16]);
+        bytes.extend(tes4);
+        bytes.extend(b"KYWD");
+        bytes.extend(0_u32.to_le_bytes());
+        bytes.extend(0_u32.to_le_bytes());
+        bytes.extend(0x800_u32.to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes
+    }
+
+    #[test]
+    fn should_validate_and_normalize_forms() -> crate::error::Result<()> {
+        let data_dir = TempDir::new()?;
+        std::fs::write(data_dir.path().join("Mini.esl"), light_plugin())?;
+        let index = PluginIndex::new(data_dir.path());
+
+        let dar = r#"HasKeyword("Mini.esl" | 0xFE000800) AND
+IsRace("Mini.esl" | 0x800) AND
+IsRace("Missing.esp" | 0x1)"#;
+        let mut conditions = parse_dar2oar("test", dar)?;
+        let style = FormIdStyle {
+            uppercase: false,
+            width: 8,
+        };
+        let warnings = validate_forms(&index, style, &mut conditions);
+
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("Expected RACE record, but found KYWD"));
+        assert!(warnings[1].starts_with("Missing.esp|0x1: Plugin Missing.esp is not found"));
+        let Some(Oar::HasKeyword(has)) = conditions.first() else {
+            panic!("expected HasKeyword: {conditions:?}");
+        };
+        let Keyword::Form(FormValue { form }) = &has.keyword else {
+            panic!("expected form: {has:?}");
+        };
+        assert_eq!(form.form_id.as_str(), "00000800");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_load_referenced_plugins() -> crate::error::Result<()> {
+        let data_dir = TempDir::new()?;
+        std::fs::write(data_dir.path().join("Mini.esl"), light_plugin())?;
+        let index = Arc::new(PluginIndex::new(data_dir.path()));
+
+        let dar = r#"HasKeyword("mini.esl" | 0x800) OR IsRace("Missing.esp" | 0x1)"#;
+        let mut conditions = parse_dar2oar("test", dar)?;
+        assert!(index.loaded("Mini.esl").is_none());
+        load_plugins(&index, &mut conditions).await;
+
+        assert!(matches!(index.loaded("Mini.esl"), Some(Ok(_))));
+        assert!(matches!(index.loaded("Missing.esp"), Some(Err(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_canonicalize_forms_by_plugin_kind() -> crate::error::Result<()> {
+        let data_dir = TempDir::new()?;
+        // A light plugin by the header flag, not by the extension.
+        let mut plugin = light_plugin();
+        plugin[8..12].copy_from_slice(&0x200_u32.to_le_bytes()); // Light flag
+        std::fs::write(data_dir.path().join("Mini.esp"), plugin)?;
+        let index = Arc::new(PluginIndex::new(data_dir.path()));
+
+        let dar = r#"IsRace("Mini.esp" | 0xFE012800) AND
+IsActorBase("Skyrim.esm" | 0x0a013bbf) AND
+IsClass("Mod.esp" | 7)"#;
+        let mut conditions = parse_dar2oar("test", dar)?;
+        load_plugins(&index, &mut conditions).await;
+        let warnings = canonicalize_forms(
+            Some(index.as_ref()),
+            FormIdStyle::default(),
+            &mut conditions,
+        );
+
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].starts_with("Mod.esp|0x7: Object IDs below 0x800"));
+        let form_ids: Vec<_> = conditions
+            .iter()
+            .map(|condition| match condition {
+                Oar::IsRace(is) => is.race.form_id.as_str(),
+                Oar::IsActorBase(is) => is.actor_base.form_id.as_str(),
+                Oar::IsClass(is) => is.class.form_id.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(form_ids, ["800", "13BBF", "7"]);
+        Ok(())
+    }
+}
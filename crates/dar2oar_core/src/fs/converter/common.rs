@@ -1,46 +1,57 @@
 //! Common parts for sequential and parallel conversions
+use crate::editor_id::EditorIdDictionary;
 use crate::error::{ConvertError, Result};
+use crate::forms::{canonicalize_forms, load_plugins, validate_forms};
+use crate::fs::converter::progress::{FileKind, FileOutcome};
 use crate::fs::converter::{ConvertOptions, parallel::is_contain_oar};
+use crate::fs::hide_journal::{HideJournal, JOURNAL_FILE};
 use crate::fs::path_changer::ParsedPath;
 use crate::fs::section_writer::{write_name_space_config, write_section_config};
 use crate::parser::parse_dar2oar;
 
 use mapping_table::MappingTable;
-use oar_conditions::conditions::ConditionsConfig;
-use std::path::Path;
+use oar_conditions::conditions::{ConditionsConfig, Oar};
+use oar_conditions::version::{OarVersion, set_required_versions};
+use oar_values::FormIdStyle;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tes_plugin::PluginIndex;
 use tokio::fs;
 
 // ─── Public entry point ───────────────────────────────────────────────────────
 
 #[cfg_attr(feature = "tracing",
-  tracing::instrument(level = "debug", skip(options), fields(specified_output = &options.oar_dir))
+  tracing::instrument(level = "debug", skip(options, plugins), fields(specified_output = &options.oar_dir))
 )]
 /// Common parts of parallel & sequential loop processing.
+///
+/// `oar_name_space` is the (staging) dir to write into, not necessarily the final namespace.
 pub(super) async fn common_process<P>(
     options: &ConvertOptions,
+    plugins: Option<&Arc<PluginIndex>>,
     path: P,
     parsed_path: &ParsedPath,
-) -> Result<()>
+    oar_name_space: &Path,
+) -> Result<FileOutcome>
 where
     P: AsRef<Path> + core::fmt::Debug,
 {
     let path = path.as_ref();
     let ConvertOptions {
-        oar_dir: specified_oar_root,
         mod_name,
         author,
         description,
         section_table,
         section_1person_table,
-        hide_dar,
+        editor_ids,
+        target_oar_version,
+        form_id_style,
         ..
     } = options;
 
     let ParsedPath {
-        oar_root,
         is_1st_person,
         mod_name: parsed_mod_name,
-        actor_name,
         priority,
         remain_dir,
         esp_dir,
@@ -48,35 +59,32 @@ where
         ..
     } = parsed_path;
 
+    let mut warnings = vec![];
+
     let resolved_mod_name = resolve_mod_name(
         mod_name.as_deref(),
         parsed_mod_name.as_deref(),
         *is_1st_person,
     );
 
-    let actor_name = resolve_actor_name(actor_name.as_deref());
-
-    let oar_name_space = build_oar_namespace(
-        specified_oar_root.as_deref(),
-        oar_root,
-        actor_name,
-        *is_1st_person,
-        &resolved_mod_name,
-    );
-
     let file_name = path
         .file_name()
-        .ok_or(ConvertError::NotFoundFileName)?
+        .ok_or_else(|| ConvertError::NotFoundFileName {
+            path: path.to_path_buf(),
+        })?
         .to_str()
-        .ok_or(ConvertError::InvalidUtf8)?;
+        .ok_or_else(|| ConvertError::InvalidUtf8 {
+            path: path.to_path_buf(),
+        })?;
 
-    match priority {
+    let kind = match priority {
         Ok(priority) => {
             let priority_str = priority.to_string();
             let base_id_or_priority_str = base_id.as_ref().unwrap_or(&priority_str);
 
             let section_name = resolve_section_name(
                 base_id_or_priority_str,
+                esp_dir.as_deref(),
                 *is_1st_person,
                 section_table.as_ref(),
                 section_1person_table.as_ref(),
@@ -85,36 +93,45 @@ where
             let section_root = oar_name_space.join(section_name);
             fs::create_dir_all(&section_root).await?;
 
+            let section = Section {
+                name: section_name,
+                priority: *priority,
+                root: &section_root,
+                editor_ids: editor_ids.as_ref(),
+                plugins,
+                target: *target_oar_version,
+                form_id_style: *form_id_style,
+            };
             if esp_dir.is_some() {
-                process_actor_base(
+                let resolved = process_actor_base(
                     path,
                     esp_dir,
                     base_id,
-                    section_name,
-                    *priority,
-                    &section_root,
-                    &oar_name_space,
+                    &section,
+                    oar_name_space,
                     &resolved_mod_name,
                     author.as_deref(),
                     description.as_deref(),
                 )
                 .await?;
+                warnings.extend(resolved);
             }
 
             if file_name.eq_ignore_ascii_case("_conditions.txt") {
-                process_conditions(
+                let resolved = process_conditions(
                     path,
-                    section_name,
-                    *priority,
-                    section_root,
-                    &oar_name_space,
+                    &section,
+                    oar_name_space,
                     &resolved_mod_name,
                     author.as_deref(),
                     description.as_deref(),
                 )
                 .await?;
+                warnings.extend(resolved);
+                FileKind::Conditions
             } else {
                 copy_motion_file(path, file_name, &section_root, remain_dir.as_deref()).await?;
+                FileKind::Motion
             }
         }
         Err(invalid_priority) => {
@@ -122,18 +139,50 @@ where
                 path,
                 file_name,
                 invalid_priority,
-                &oar_name_space,
+                oar_name_space,
                 remain_dir.as_deref(),
             )
             .await?;
+            warnings.push(format!(
+                r#"Invalid priority "{invalid_priority}". Copied as a memo."#
+            ));
+            FileKind::Memo
         }
-    }
+    };
 
-    maybe_hide_path(path, *hide_dar).await
+    Ok(FileOutcome { kind, warnings })
 }
 
 // ─── Name resolution helpers ──────────────────────────────────────────────────
 
+/// Resolve the OAR namespace directory into which `parsed_path` is converted.
+///
+/// This is pure path computation, so the converters can call it before scheduling any work.
+pub(super) fn resolve_oar_namespace(options: &ConvertOptions, parsed_path: &ParsedPath) -> PathBuf {
+    let ParsedPath {
+        oar_root,
+        is_1st_person,
+        mod_name: parsed_mod_name,
+        actor_name,
+        ..
+    } = parsed_path;
+
+    let resolved_mod_name = resolve_mod_name(
+        options.mod_name.as_deref(),
+        parsed_mod_name.as_deref(),
+        *is_1st_person,
+    );
+    let actor_name = resolve_actor_name(actor_name.as_deref());
+
+    build_oar_namespace(
+        options.oar_dir.as_deref(),
+        oar_root,
+        actor_name,
+        *is_1st_person,
+        &resolved_mod_name,
+    )
+}
+
 /// Resolve the final mod name, appending `_1st_person` suffix when needed.
 fn resolve_mod_name(explicit: Option<&str>, parsed: Option<&str>, is_1st_person: bool) -> String {
     let mut name = explicit
@@ -145,11 +194,13 @@ fn resolve_mod_name(explicit: Option<&str>, parsed: Option<&str>, is_1st_person:
     name
 }
 
-/// Resolve the actor name, falling back to `"character"` with a warning.
+/// Resolve the actor name, falling back to `"character"`.
+///
+/// The fallback is only logged, not reported as a warning: it applies to every file of such a mod.
 fn resolve_actor_name(actor_name: Option<&str>) -> &str {
     actor_name.unwrap_or_else(|| {
         #[cfg(feature = "tracing")]
-        tracing::warn!(
+        tracing::debug!(
             "actor_name could not be inferred from the dir name. \
              Using the default value \"character\"."
         );
@@ -165,7 +216,7 @@ fn build_oar_namespace(
     actor_name: &str,
     is_1st_person: bool,
     mod_name: &str,
-) -> std::path::PathBuf {
+) -> PathBuf {
     let base = specified_oar_root
         .map(|root| {
             let rel = if is_1st_person {
@@ -182,9 +233,11 @@ fn build_oar_namespace(
 
 /// Look up a human-readable section name from the priority/base-id tables.
 ///
+/// For ActorBase paths, `<plugin>|<base id>` is preferred over `<base id>`.
 /// Falls back to the raw `base_id_or_priority_str` when no entry exists.
 fn resolve_section_name<'a>(
     base_id_or_priority_str: &'a str,
+    esp_dir: Option<&str>,
     is_1st_person: bool,
     section_table: Option<&'a MappingTable>,
     section_1person_table: Option<&'a MappingTable>,
@@ -195,28 +248,93 @@ fn resolve_section_name<'a>(
         section_table
     };
     table
-        .and_then(|t| t.get(base_id_or_priority_str))
-        .map(|s| s.as_str())
+        .and_then(|t| mapping_table::lookup(t, esp_dir, base_id_or_priority_str))
         .unwrap_or(base_id_or_priority_str)
 }
 
 // ─── Branch processors ────────────────────────────────────────────────────────
 
+/// The section a file is converted into.
+struct Section<'a> {
+    name: &'a str,
+    priority: i32,
+    root: &'a Path,
+    editor_ids: Option<&'a EditorIdDictionary>,
+    plugins: Option<&'a Arc<PluginIndex>>,
+    target: Option<OarVersion>,
+    form_id_style: FormIdStyle,
+}
+
+impl Section<'_> {
+    /// Build `config.json` of the section, canonicalizing the FormIDs, validating the forms with
+    /// the plugins and resolving them with the `EditorID` dictionary.
+    ///
+    /// Returns the config and the warnings for the invalid or unknown forms,
+    /// and for the conditions requiring a newer OAR than the target.
+    async fn config<'c>(
+        &self,
+        mut conditions: Vec<Oar<'c>>,
+    ) -> (ConditionsConfig<'c>, Vec<String>) {
+        if let Some(plugins) = self.plugins {
+            load_plugins(plugins, &mut conditions).await;
+        }
+        let mut warnings = canonicalize_forms(
+            self.plugins.map(Arc::as_ref),
+            self.form_id_style,
+            &mut conditions,
+        );
+        if let Some(plugins) = self.plugins {
+            warnings.extend(validate_forms(plugins, self.form_id_style, &mut conditions));
+        }
+        let description = self.editor_ids.and_then(|dictionary| {
+            // `editorID` literals are not supported before OAR 2.0.
+            let to_literals = self.target.is_none_or(|target| target >= OarVersion::V2);
+            let resolved = dictionary.resolve_conditions(&mut conditions, to_literals);
+            warnings.extend(resolved.warnings());
+            resolved.description()
+        });
+
+        // After the resolution, because it may turn a keyword into an `editorID` literal.
+        let versions = set_required_versions(&mut conditions);
+        if let Some(target) = self.target {
+            warnings.extend(
+                versions
+                    .into_iter()
+                    .filter(|(_, version)| *version > target)
+                    .map(|(condition, version)| {
+                        format!(
+                            "`{condition}` requires OAR {version}, newer than the target {target}."
+                        )
+                    }),
+            );
+        }
+
+        let config = ConditionsConfig {
+            name: self.name.to_string().into(),
+            description: description.unwrap_or_default().into(),
+            priority: self.priority,
+            conditions,
+            ..Default::default()
+        };
+        (config, warnings)
+    }
+}
+
 /// Handle the ActorBase path pattern:
 /// auto-generates an `IsActorBase(...)` condition and writes `config.json`.
+///
+/// Returns the warnings of the `EditorID` dictionary, only when `config.json` is written.
 #[allow(clippy::too_many_arguments)]
 async fn process_actor_base(
     path: &Path,
     esp_dir: &Option<String>,
     base_id: &Option<String>,
-    section_name: &str,
-    priority: i32,
-    section_root: &Path,
+    section: &Section<'_>,
     oar_name_space: &Path,
     mod_name: &str,
     author: Option<&str>,
     description: Option<&str>,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     #[cfg(feature = "tracing")]
     tracing::debug!("This path is ActorBase: {path:?}");
 
@@ -235,43 +353,35 @@ async fn process_actor_base(
     #[cfg(feature = "tracing")]
     tracing::debug!("DAR syntax content auto-generated for ActorBase paths:\n{content}");
 
-    let config_json = ConditionsConfig {
-        name: section_name.into(),
-        priority,
-        conditions: parse_dar2oar(path, &content)?,
-        ..Default::default()
-    };
-
-    if !section_root.join("config.json").exists() {
-        write_section_config(section_root, config_json).await?;
+    let mut warnings = vec![];
+    if !section.root.join("config.json").exists() {
+        let (config_json, resolved) = section.config(parse_dar2oar(path, &content)?).await;
+        write_section_config(section.root, config_json).await?;
+        warnings = resolved;
     }
 
-    write_name_space_config(oar_name_space, mod_name, author, description).await
+    write_name_space_config(oar_name_space, mod_name, author, description).await?;
+    Ok(warnings)
 }
 
 /// Handle the `_conditions.txt` pattern:
 /// reads the file, parses DAR syntax, and writes `config.json`.
-#[allow(clippy::too_many_arguments)]
+///
+/// Returns the warnings of the `EditorID` dictionary.
 async fn process_conditions(
     path: &Path,
-    section_name: &str,
-    priority: i32,
-    section_root: std::path::PathBuf,
+    section: &Section<'_>,
     oar_name_space: &Path,
     mod_name: &str,
     author: Option<&str>,
     description: Option<&str>,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     let content = fs::read_to_string(path).await?;
 
-    let config_json = ConditionsConfig {
-        name: section_name.into(),
-        priority,
-        conditions: parse_dar2oar(path, &content)?,
-        ..Default::default()
-    };
-    write_section_config(section_root, config_json).await?;
-    write_name_space_config(oar_name_space, mod_name, author, description).await
+    let (config_json, warnings) = section.config(parse_dar2oar(path, &content)?).await;
+    write_section_config(section.root, config_json).await?;
+    write_name_space_config(oar_name_space, mod_name, author, description).await?;
+    Ok(warnings)
 }
 
 /// Copy a motion file (`.hkx`, gender dir, etc.) into the section root,
@@ -326,35 +436,74 @@ async fn process_invalid_priority(
 
 // ─── Post-processing ──────────────────────────────────────────────────────────
 
-/// Conditionally hide `path` by appending `.mohidden`, skipping OAR-internal paths.
-async fn maybe_hide_path(path: &Path, hide_dar: bool) -> Result<()> {
-    if hide_dar && is_contain_oar(path).is_none() {
-        hide_path(path).await?;
+/// Hide the converted DAR files by appending `.mohidden`, skipping OAR-internal paths.
+///
+/// Every rename is recorded in the [`HideJournal`] of `dar_dir`, which is saved even if a rename fails.
+pub(super) async fn hide_dar_paths<I, P>(dar_dir: &Path, paths: I) -> Result<()>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    let mut journal = HideJournal::load(dar_dir).await?;
+    let mut result = Ok(());
+    for path in paths {
+        let path = path.as_ref();
+        if is_contain_oar(path).is_some() {
+            continue;
+        }
+        result = hide_path(dar_dir, path, &mut journal).await;
+        if result.is_err() {
+            break;
+        }
     }
-    Ok(())
+    journal.save(dar_dir).await?;
+    result
 }
 
-/// Rename `path` to `<path>.mohidden` (idempotent: skips already-hidden paths).
-async fn hide_path(path: impl AsRef<Path>) -> Result<()> {
-    let path = path.as_ref();
-    // NOTE: Do not use `set_extension` — it replaces rather than appends.
-    let mut hidden_path = path.display().to_string();
-
+/// Rename `path` to `<path>.mohidden` and record it in `journal`
+/// (idempotent: skips already-hidden paths).
+async fn hide_path(dar_dir: &Path, path: &Path, journal: &mut HideJournal) -> Result<()> {
     if path
         .extension()
-        .map(|ext| ext.eq_ignore_ascii_case("mohidden"))
-        != Some(true)
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("mohidden"))
     {
-        hidden_path.push_str(".mohidden");
+        return Ok(());
     }
+
+    // NOTE: Do not use `set_extension` — it replaces rather than appends.
+    let mut hidden_path = path.as_os_str().to_owned();
+    hidden_path.push(".mohidden");
+    let hidden_path = PathBuf::from(hidden_path);
+
     #[cfg(feature = "tracing")]
     tracing::debug!("Rename:\nfrom: {path:?}\nto: {hidden_path:?}");
     fs::rename(path, &hidden_path).await?;
+    journal.record(dar_dir, path, &hidden_path);
     Ok(())
 }
 
 // ─── Path utilities ───────────────────────────────────────────────────────────
 
+/// Whether `path` is a file to be converted (i.e. counted in [`ProgressEvent::Started`]).
+///
+/// [`ProgressEvent::Started`]: crate::fs::converter::progress::ProgressEvent::Started
+pub(super) fn is_dar_file(path: &Path) -> bool {
+    path.is_file() && is_contain_dar(path).is_some() && !path.ends_with(JOURNAL_FILE)
+}
+
+/// Section dirs(with `config.json`) of an OAR namespace, sorted by name.
+pub(super) async fn read_section_dirs(name_space: &Path) -> Result<Vec<PathBuf>> {
+    let mut section_dirs = vec![];
+    let mut entries = fs::read_dir(name_space).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_type().await?.is_dir() && entry.path().join("config.json").exists() {
+            section_dirs.push(entry.path());
+        }
+    }
+    section_dirs.sort();
+    Ok(section_dirs)
+}
+
 /// Return the index of `DynamicAnimationReplacer` in `path`, if present.
 #[inline]
 pub(super) fn is_contain_dar(path: impl AsRef<Path>) -> Option<usize> {
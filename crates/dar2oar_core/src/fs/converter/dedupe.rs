@@ -0,0 +1,218 @@
+//! Deduplicate the motion files shared by sections via `overrideAnimationsFolder`.
+//!
+//! DAR mods often copy the same `.hkx` set into several priority dirs that differ only in conditions.
+//! The first section(by name) keeps the files, and the others with byte-identical sets
+//! refer to it instead of holding copies.
+use crate::error::Result;
+use crate::fs::converter::common::read_section_dirs;
+use crate::fs::converter::progress::ConvertReport;
+use crate::fs::section_writer::write_config_value;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hasher as _};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncReadExt as _;
+
+/// Motion files of a section: (path relative to the section dir, size, content hash)
+type Fingerprint = Vec<(PathBuf, u64, u64)>;
+
+/// Size of the chunks the motion files are read by, not to hold the whole files in memory.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Replace the motion files of the sections of `name_space` that are byte-identical to another section's
+/// with `overrideAnimationsFolder`, and add the deduplicated sections & bytes to `report`.
+///
+/// `overrideAnimationsFolder` is the canonical section dir name, relative to the namespace dir.
+///
+/// # Errors
+/// Failed to read, remove or write the files.
+pub(super) async fn dedupe_motions(name_space: &Path, report: &mut ConvertReport) -> Result<()> {
+    let mut canonicals: HashMap<Fingerprint, PathBuf> = HashMap::new();
+
+    for section_dir in read_section_dirs(name_space).await? {
+        let config_path = section_dir.join("config.json");
+        let mut config: Value = serde_json::from_str(&fs::read_to_string(&config_path).await?)?;
+        if config.get("overrideAnimationsFolder").is_some() {
+            continue;
+        }
+
+        let fingerprint = fingerprint(&section_dir).await?;
+        if fingerprint.is_empty() {
+            continue;
+        }
+        let Some(canonical) = canonicals.get(&fingerprint) else {
+            canonicals.insert(fingerprint, section_dir);
+            continue;
+        };
+        // The hashes match, which may be a collision.
+        if !is_same_files(canonical, &section_dir, &fingerprint).await? {
+            continue;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Motion files of {section_dir:?} are the same as {canonical:?}");
+        let mut entries = fs::read_dir(&section_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            match entry.file_type().await?.is_dir() {
+                true => fs::remove_dir_all(entry.path()).await?,
+                false if entry.path() == config_path => {}
+                false => fs::remove_file(entry.path()).await?,
+            }
+        }
+
+        if let (Value::Object(config), Some(canonical)) = (&mut config, canonical.file_name()) {
+            config.insert(
+                "overrideAnimationsFolder".to_string(),
+                canonical.to_string_lossy().into(),
+            );
+        }
+        write_config_value(&section_dir, &config).await?;
+        report.deduplicated += 1;
+        report.bytes_saved += fingerprint.iter().map(|(_, size, _)| size).sum::<u64>();
+    }
+    Ok(())
+}
+
+/// Motion files(all files except `config.json`) under `section_dir`, sorted by path.
+async fn fingerprint(section_dir: &Path) -> Result<Fingerprint> {
+    let mut fingerprint = vec![];
+    let mut dirs = vec![section_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let relative = path
+                .strip_prefix(section_dir)
+                .unwrap_or(&path)
+                .to_path_buf();
+            if relative == Path::new("config.json") {
+                continue;
+            }
+
+            let (size, hash) = hash_file(&path).await?;
+            fingerprint.push((relative, size, hash));
+        }
+    }
+    fingerprint.sort();
+    Ok(fingerprint)
+}
+
+/// Returns the size and the content hash of the file, reading it by chunks.
+async fn hash_file(path: &Path) -> Result<(u64, u64)> {
+    let mut file = fs::File::open(path).await?;
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut hasher = DefaultHasher::new();
+    let mut size = 0;
+    loop {
+        let len = file.read(&mut buffer).await?;
+        if len == 0 {
+            return Ok((size, hasher.finish()));
+        }
+        hasher.write(&buffer[..len]);
+        size += len as u64;
+    }
+}
+
+/// Compare the bytes of the files in `fingerprint` under both dirs.
+async fn is_same_files(a: &Path, b: &Path, fingerprint: &Fingerprint) -> Result<bool> {
+    for (relative, _, _) in fingerprint {
+        if !is_same_file(&a.join(relative), &b.join(relative)).await? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Compare the bytes of two files by chunks.
+async fn is_same_file(a: &Path, b: &Path) -> Result<bool> {
+    let (mut a, mut b) = (fs::File::open(a).await?, fs::File::open(b).await?);
+    let (mut a_buffer, mut b_buffer) = (vec![0; CHUNK_SIZE], vec![0; CHUNK_SIZE]);
+    loop {
+        let len = a.read(&mut a_buffer).await?;
+        if len == 0 {
+            return Ok(b.read(&mut b_buffer).await? == 0);
+        }
+        match b.read_exact(&mut b_buffer[..len]).await {
+            Ok(_) if a_buffer[..len] == b_buffer[..len] => {}
+            Ok(_) => return Ok(false),
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    async fn write_section(name_space: &Path, name: &str, files: &[(&str, &str)]) -> Result<()> {
+        let section_dir = name_space.join(name);
+        fs::create_dir_all(section_dir.join("female")).await?;
+        fs::write(
+            section_dir.join("config.json"),
+            format!(r#"{{"name":"{name}","priority":{name},"conditions":[]}}"#),
+        )
+        .await?;
+        for (file, content) in files {
+            fs::write(section_dir.join(file), content).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_dedupe_identical_motion_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path();
+        let files = [("mt_idle.hkx", "idle"), ("female/mt_walk.hkx", "walk")];
+        write_section(name_space, "100", &files).await?;
+        write_section(name_space, "200", &files).await?;
+        write_section(name_space, "300", &[("mt_idle.hkx", "other")]).await?;
+
+        let mut report = ConvertReport::default();
+        dedupe_motions(name_space, &mut report).await?;
+
+        assert_eq!(report.deduplicated, 1);
+        assert_eq!(report.bytes_saved, 8);
+        assert!(name_space.join("100/female/mt_walk.hkx").exists());
+        assert!(!name_space.join("200/mt_idle.hkx").exists());
+        assert!(!name_space.join("200/female").exists());
+        assert!(name_space.join("300/mt_idle.hkx").exists());
+
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(name_space.join("200/config.json")).await?)?;
+        assert_eq!(config["overrideAnimationsFolder"], "100");
+        assert_eq!(config["name"], "200");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_compare_files_by_chunks() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = |name: &str| temp_dir.path().join(name);
+        let large = vec![7; CHUNK_SIZE * 2 + 1];
+        let mut changed = large.clone();
+        changed[CHUNK_SIZE + 1] = 0;
+        fs::write(path("a"), &large).await?;
+        fs::write(path("b"), &large).await?;
+        fs::write(path("changed"), &changed).await?;
+        fs::write(path("short"), &large[..CHUNK_SIZE]).await?;
+
+        assert!(is_same_file(&path("a"), &path("b")).await?);
+        assert!(!is_same_file(&path("a"), &path("changed")).await?);
+        assert!(!is_same_file(&path("a"), &path("short")).await?);
+        assert!(!is_same_file(&path("short"), &path("a")).await?);
+
+        let (size, hash) = hash_file(&path("a")).await?;
+        assert_eq!(size, large.len() as u64);
+        assert_eq!(hash_file(&path("b")).await?, (size, hash));
+        assert_ne!(hash_file(&path("changed")).await?.1, hash);
+        Ok(())
+    }
+}
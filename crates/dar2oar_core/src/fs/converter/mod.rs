@@ -1,24 +1,44 @@
 //! Converter system
 mod common;
+mod dedupe;
+mod presets;
+mod staging;
+mod variants;
 
 pub mod parallel;
+pub mod progress;
+pub mod section_naming;
 pub mod sequential;
 pub mod support_cmd;
 
+use crate::editor_id::EditorIdDictionary;
 use crate::error::Result;
+use crate::fs::converter::progress::{ConvertReport, ProgressEvent, ProgressReporter};
+use crate::fs::converter::section_naming::{SectionNaming, fill_section_tables};
 use mapping_table::MappingTable;
+use oar_conditions::version::OarVersion;
+use oar_values::FormIdStyle;
+use tokio_util::sync::CancellationToken;
 
 /// Converts Dynamic Animation Replacer (DAR) files to Overwrite Animation Replacer (OAR) files.
 ///
+/// Progress is reported to `reporter` as [`ProgressEvent`]s, and the summary is returned on success.
+///
+/// The output is written to a staging dir and moved into place only when the whole conversion succeeds.
+/// An OAR namespace that already existed is moved to `animations/.dar2oar_backup/<mod name>` beforehand.
+///
+/// When `cancel_token` is cancelled, no new work is scheduled, in-flight tasks are aborted and
+/// the staged output is discarded.
+///
 /// # Errors
-/// Failed conversion
+/// Failed conversion, or [`ConvertError::Cancelled`](crate::error::ConvertError::Cancelled) if cancelled.
 ///
 /// ## Examples
 ///
 /// ### Sequential Conversion
 ///
 /// ```no_run
-/// use dar2oar_core::{convert_dar_to_oar, ConvertOptions};
+/// use dar2oar_core::{convert_dar_to_oar, CancellationToken, Closure, ConvertOptions};
 ///
 /// #[tokio::main]
 /// async fn main() {
@@ -28,7 +48,7 @@ use mapping_table::MappingTable;
 ///         ..Default::default()
 ///     };
 ///
-///     let result = convert_dar_to_oar(options, |_| {}).await;
+///     let result = convert_dar_to_oar(options, Closure::default_event, CancellationToken::new()).await;
 ///     match result {
 ///         Ok(report) => println!("Conversion Report: {:?}", report),
 ///         Err(err) => eprintln!("Conversion Error: {}", err),
@@ -39,7 +59,7 @@ use mapping_table::MappingTable;
 /// ### Parallel Conversion
 ///
 /// ```no_run
-/// use dar2oar_core::{convert_dar_to_oar, ConvertOptions};
+/// use dar2oar_core::{convert_dar_to_oar, CancellationToken, Closure, ConvertOptions};
 ///
 /// #[tokio::main]
 /// async fn main() {
@@ -50,7 +70,7 @@ use mapping_table::MappingTable;
 ///         ..Default::default()
 ///     };
 ///
-///     let result = convert_dar_to_oar(options, |_| {}).await;
+///     let result = convert_dar_to_oar(options, Closure::default_event, CancellationToken::new()).await;
 ///     match result {
 ///         Ok(report) => println!("Conversion Report: {:?}", report),
 ///         Err(err) => eprintln!("Conversion Error: {}", err),
@@ -58,19 +78,29 @@ use mapping_table::MappingTable;
 /// }
 /// ```
 pub async fn convert_dar_to_oar(
-    options: ConvertOptions,
-    progress_fn: impl FnMut(usize),
-) -> Result<()> {
+    mut options: ConvertOptions,
+    reporter: impl ProgressReporter,
+    cancel_token: CancellationToken,
+) -> Result<ConvertReport> {
     let dar_dir = std::path::Path::new(&options.dar_dir);
     if !dar_dir.exists() {
         return Err(crate::error::ConvertError::NonExistPath {
             path: dar_dir.to_path_buf(),
         })?;
     };
+    if options.section_naming == SectionNaming::Conditions {
+        fill_section_tables(&mut options).await?;
+    }
 
     match options.run_parallel {
-        true => crate::fs::converter::parallel::convert_dar_to_oar(options, progress_fn).await,
-        false => crate::fs::converter::sequential::convert_dar_to_oar(options, progress_fn).await,
+        true => {
+            crate::fs::converter::parallel::convert_dar_to_oar(options, reporter, cancel_token)
+                .await
+        }
+        false => {
+            crate::fs::converter::sequential::convert_dar_to_oar(options, reporter, cancel_token)
+                .await
+        }
     }
 }
 
@@ -81,6 +111,10 @@ impl Closure {
     /// No operation function pointer
     #[inline]
     pub const fn default(_: usize) {}
+
+    /// No operation function pointer for [`ProgressEvent`]
+    #[inline]
+    pub fn default_event(_: ProgressEvent) {}
 }
 
 /// The options for converting a DAR directory to an OAR directory.
@@ -100,6 +134,28 @@ pub struct ConvertOptions {
     pub section_table: Option<MappingTable>,
     /// path to section name table(For `_1st_person`)
     pub section_1person_table: Option<MappingTable>,
+    /// How to name the sections that have no entry in the section tables
+    pub section_naming: SectionNaming,
+    /// `EditorID` dictionary to resolve the keywords and annotate the section descriptions
+    pub editor_ids: Option<EditorIdDictionary>,
+    /// Hoist the condition subtrees repeated across the sections of a namespace into condition presets.
+    pub extract_presets: bool,
+    /// Merge the sections that differ only by `Random` thresholds into OAR animation variants.
+    pub collapse_variants: bool,
+    /// Replace the motion files byte-identical to another section's with `overrideAnimationsFolder`.
+    pub dedupe_motions: bool,
+    /// OAR version the output targets. If specified, the conditions requiring a newer OAR are warned,
+    /// condition presets are not extracted below `2.3.0.0`, and the keywords are not turned into
+    /// `editorID` literals below `2.0.0.0`.
+    pub target_oar_version: Option<OarVersion>,
+    /// How to write the FormIDs. They are also stripped of the load order byte(s) of the plugin kind.
+    ///
+    /// # Note
+    /// The canonicalization is always applied to every FormID of the conditions, so even with the
+    /// default style, e.g. `0x00000007` in DAR is written as `7`.
+    pub form_id_style: FormIdStyle,
+    /// Game `Data` dir. If specified, the forms in the conditions are validated against its plugins.
+    pub data_dir: Option<String>,
     /// use multi thread(Probably effective for those with long DAR syntax. Basically single-threaded is faster.)
     pub run_parallel: bool,
     /// After converting to OAR, add mohidden to the DAR directory before conversion to treat it as a hidden directory. (for MO2 users)
@@ -138,7 +194,14 @@ mod test {
         quick_tracing::try_init(test = "convert_non_mpsc", level = "DEBUG")
     )]
     async fn convert_non_mpsc() -> Result<()> {
-        convert_dar_to_oar(create_options().await?, |_| {}).await
+        let report = convert_dar_to_oar(
+            create_options().await?,
+            Closure::default_event,
+            CancellationToken::new(),
+        )
+        .await?;
+        tracing::info!("{report:?}");
+        Ok(())
     }
 
     #[ignore = "need MOD data"]
@@ -150,10 +213,10 @@ mod test {
     async fn convert_with_mpsc() -> Result<()> {
         let (tx, mut rx) = tokio::sync::mpsc::channel(500);
 
-        let sender = move |idx: usize| {
+        let sender = move |event: ProgressEvent| {
             let tx = tx.clone();
             let handle = tokio::spawn(async move {
-                match tx.send(idx).await {
+                match tx.send(event).await {
                     Ok(ok) => ok,
                     Err(err) => tracing::error!("{}", err),
                 };
@@ -162,18 +225,83 @@ mod test {
             drop(handle);
         };
 
-        let handle = tokio::spawn(convert_dar_to_oar(create_options().await?, sender));
+        let handle = tokio::spawn(convert_dar_to_oar(
+            create_options().await?,
+            sender,
+            CancellationToken::new(),
+        ));
 
-        let mut walk_len = 0;
-        while let Some(idx) = rx.recv().await {
-            if walk_len == 0 {
-                walk_len = idx;
-            } else {
-                tracing::info!("[recv] Converted: {}/{walk_len}", idx + 1);
+        let mut total = 0;
+        let mut done = 0;
+        while let Some(event) = rx.recv().await {
+            match event {
+                ProgressEvent::Started { total: len } => total = len,
+                ProgressEvent::FileDone { path, .. } => {
+                    done += 1;
+                    tracing::info!("[recv] Converted: {done}/{total} {path:?}");
+                }
+                event => tracing::info!("[recv] {event:?}"),
             }
         }
 
         handle.await??;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_leave_existing_oar_dir_on_cancel() -> Result<()> {
+        for run_parallel in [false, true] {
+            let temp_dir = temp_dir::TempDir::new()?;
+            let animations = temp_dir
+                .path()
+                .join("TestMod/meshes/actors/character/animations");
+            for priority in ["100", "200"] {
+                let dir = animations
+                    .join("DynamicAnimationReplacer/_CustomConditions")
+                    .join(priority);
+                tokio::fs::create_dir_all(&dir).await?;
+                tokio::fs::write(dir.join("_conditions.txt"), "IsSneaking()").await?;
+                tokio::fs::write(dir.join("idle.hkx"), "new").await?;
+            }
+            let name_space = animations.join("OpenAnimationReplacer/TestMod");
+            tokio::fs::create_dir_all(&name_space).await?;
+            tokio::fs::write(name_space.join("config.json"), "old").await?;
+
+            let options = ConvertOptions {
+                dar_dir: animations
+                    .join("DynamicAnimationReplacer")
+                    .display()
+                    .to_string(),
+                mod_name: Some("TestMod".into()),
+                run_parallel,
+                ..Default::default()
+            };
+            let cancel_token = CancellationToken::new();
+            let reporter = {
+                let cancel_token = cancel_token.clone();
+                move |event: ProgressEvent| {
+                    if matches!(event, ProgressEvent::FileDone { .. }) {
+                        cancel_token.cancel();
+                    }
+                }
+            };
+            let result = convert_dar_to_oar(options, reporter, cancel_token).await;
+
+            assert!(
+                matches!(result, Err(crate::error::ConvertError::Cancelled)),
+                "{result:?}"
+            );
+            assert_eq!(
+                tokio::fs::read_to_string(name_space.join("config.json")).await?,
+                "old"
+            );
+            let mut entries = tokio::fs::read_dir(&name_space).await?;
+            let mut names = vec![];
+            while let Some(entry) = entries.next_entry().await? {
+                names.push(entry.file_name());
+            }
+            assert_eq!(names, ["config.json"]);
+        }
+        Ok(())
+    }
 }
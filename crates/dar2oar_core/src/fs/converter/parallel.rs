@@ -2,70 +2,127 @@
 use super::common::is_contain_dar;
 use crate::error::{ConvertError, Result};
 use crate::fs::converter::ConvertOptions;
-use crate::fs::converter::common::common_process;
+use crate::fs::converter::common::{
+    common_process, hide_dar_paths, is_dar_file, resolve_oar_namespace,
+};
+use crate::fs::converter::progress::{ConvertReport, ProgressEvent, ProgressReporter};
+use crate::fs::converter::staging::Staging;
+use crate::fs::manifest::Manifest;
 use crate::fs::path_changer::parse_dar_path;
 use jwalk::WalkDirGeneric;
 use std::path::Path;
 use std::sync::Arc;
+use tes_plugin::PluginIndex;
+use tokio_util::sync::CancellationToken;
 
 /// Multi thread converter
 ///
 /// # Parameters
 /// - `options`: Convert options
-/// - `progress_fn`: For progress callback(1st time: max contents count, 2nd~: index)
+/// - `reporter`: Receives [`ProgressEvent`]s. `FileStarted` is reported when a file is scheduled.
+/// - `cancel_token`: Stops scheduling, aborts in-flight tasks and discards the staged output.
 ///
 /// # Errors
-/// Failed to convert
-///
-/// # NOTE
-/// For library reasons, you get the number of DAR dirs and files, not the number of DAR files only
-/// (i.e., the count is different from the Sequential version)
+/// Failed to convert, or [`ConvertError::Cancelled`] if cancelled.
 pub async fn convert_dar_to_oar(
     options: ConvertOptions,
-    mut progress_fn: impl FnMut(usize),
-) -> Result<()> {
+    mut reporter: impl ProgressReporter,
+    cancel_token: CancellationToken,
+) -> Result<ConvertReport> {
     let dar_dir = options.dar_dir.as_str();
 
-    let walk_len = get_dar_files(dar_dir).into_iter().count();
+    let total = get_dar_files(dar_dir)
+        .into_iter()
+        .filter(|entry| entry.as_ref().is_ok_and(|entry| is_dar_file(&entry.path())))
+        .count();
     #[cfg(feature = "tracing")]
-    tracing::info!("Parallel Converter/DAR dir & file counts: {}", walk_len);
-    progress_fn(walk_len);
+    tracing::info!("Parallel Converter/DAR file counts: {}", total);
+    reporter.report(ProgressEvent::Started { total });
 
     let entires = get_dar_files(dar_dir).into_iter();
+    let plugins =
+        (options.data_dir.as_deref()).map(|data_dir| Arc::new(PluginIndex::new(data_dir)));
     let options = Arc::new(options);
     let mut task_handles = tokio::task::JoinSet::new();
+    let mut staging = Staging::default();
+    let mut dar_paths = vec![];
 
     for entry in entires {
-        let path = entry.map_err(|_err| ConvertError::NotFoundEntry)?.path();
-        if !path.is_file() {
+        if cancel_token.is_cancelled() {
+            break;
+        }
+
+        let path = entry
+            .map_err(|_err| ConvertError::NotFoundEntry {
+                path: options.dar_dir.as_str().into(),
+            })?
+            .path();
+        if !is_dar_file(&path) {
             continue;
         }
         let parsed_path = Arc::new(match parse_dar_path(&path) {
             Ok(p) => p,
             Err(_) => continue,
         });
+        let staging_dir = match staging
+            .stage(resolve_oar_namespace(&options, &parsed_path))
+            .await
+        {
+            Ok(staging_dir) => staging_dir,
+            Err(err) => {
+                task_handles.abort_all();
+                while task_handles.join_next().await.is_some() {}
+                staging.rollback().await;
+                return Err(err);
+            }
+        };
+        dar_paths.push(path.clone());
+        reporter.report(ProgressEvent::FileStarted { path: path.clone() });
         let path = Arc::new(path);
 
         task_handles.spawn({
             let path = Arc::clone(&path);
             let parsed_path = Arc::clone(&parsed_path);
             let options = Arc::clone(&options);
+            let plugins = plugins.clone();
 
-            async move { common_process(&options, path.as_ref(), parsed_path.as_ref()).await }
+            async move {
+                let outcome = common_process(
+                    &options,
+                    plugins.as_ref(),
+                    path.as_ref(),
+                    parsed_path.as_ref(),
+                    &staging_dir,
+                )
+                .await;
+                (path, outcome)
+            }
         });
     }
 
     let task_handle_is_empty = task_handles.is_empty(); // Need call before `.join_next()`
 
+    let mut report = ConvertReport::default();
     let mut errors = vec![];
-    let mut idx = 0;
-    while let Some(result) = task_handles.join_next().await {
-        progress_fn(idx);
-        idx += 1;
+    loop {
+        let result = tokio::select! {
+            biased;
+            () = cancel_token.cancelled() => {
+                task_handles.abort_all();
+                while task_handles.join_next().await.is_some() {}
+                break;
+            }
+            result = task_handles.join_next() => match result {
+                Some(result) => result,
+                None => break,
+            },
+        };
 
         match result {
-            Ok(Ok(())) => {}
-            Ok(Err(err)) => {
+            Ok((path, Ok(outcome))) => {
+                outcome.report_to(path.to_path_buf(), &mut report, &mut reporter);
+            }
+            Ok((_, Err(err))) => {
                 errors.push(err);
             }
             Err(err) => {
@@ -74,14 +131,50 @@ pub async fn convert_dar_to_oar(
         }
     }
 
+    if cancel_token.is_cancelled() {
+        staging.rollback().await;
+        return Err(ConvertError::Cancelled);
+    }
+
     if task_handle_is_empty {
-        return Err(ConvertError::NeverConverted);
+        return Err(ConvertError::NeverConverted {
+            path: options.dar_dir.as_str().into(),
+        });
+    }
+
+    if !errors.is_empty() {
+        staging.rollback().await;
+        return Err(ConvertError::NestedError { errors });
+    }
+    if options.extract_presets
+        && let Err(err) = staging
+            .extract_presets(options.target_oar_version, &mut report, &mut reporter)
+            .await
+    {
+        staging.rollback().await;
+        return Err(err);
+    }
+    if options.collapse_variants
+        && let Err(err) = staging.collapse_variants(&mut report, &mut reporter).await
+    {
+        staging.rollback().await;
+        return Err(err);
+    }
+    if options.dedupe_motions
+        && let Err(err) = staging.dedupe_motions(&mut report).await
+    {
+        staging.rollback().await;
+        return Err(err);
     }
 
-    if errors.is_empty() {
-        return Ok(());
+    staging.commit(&Manifest::new(&options.dar_dir)).await?;
+    if options.hide_dar {
+        hide_dar_paths(Path::new(&options.dar_dir), dar_paths).await?;
     }
-    Err(ConvertError::NestedError { errors })
+    reporter.report(ProgressEvent::Finished {
+        report: report.clone(),
+    });
+    Ok(report)
 }
 
 /// Get DAR files using a custom filter.
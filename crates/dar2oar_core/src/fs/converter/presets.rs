@@ -0,0 +1,320 @@
+//! Hoist the condition subtrees repeated across the sections of a namespace into condition presets.
+//!
+//! Large DAR packs repeat the same block(e.g. a long `OR` of `IsActorBase`) in many `_conditions.txt`.
+//! With presets, the shared list is edited in one place in the OAR UI.
+//!
+//! The written `config.json`s are handled as [`Value`], because reading them into `Oar` is lossy.
+use crate::error::Result;
+use crate::fs::converter::common::read_section_dirs;
+use crate::fs::section_writer::write_config_value;
+use oar_conditions::conditions::{ConditionPreset, PresetCondition};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::fs;
+
+/// Minimum number of conditions in a subtree to be hoisted.
+const MIN_PRESET_CONDITIONS: usize = 3;
+/// Minimum number of occurrences of a subtree to be hoisted.
+const MIN_OCCURRENCES: usize = 2;
+
+/// Rewrite the sections of the OAR namespace `name_space` to refer to presets of the repeated subtrees.
+///
+/// Returns the number of presets written into the namespace `config.json`.
+///
+/// # Errors
+/// Failed to read or write `config.json`s.
+pub(super) async fn extract_presets(name_space: &Path) -> Result<usize> {
+    let main_config_path = name_space.join("config.json");
+    if !main_config_path.exists() {
+        return Ok(0);
+    }
+
+    // Sorted, so that the preset names are stable regardless of the dir order.
+    let section_dirs = read_section_dirs(name_space).await?;
+
+    let mut sections = Vec::with_capacity(section_dirs.len());
+    for section_dir in &section_dirs {
+        let json = fs::read_to_string(section_dir.join("config.json")).await?;
+        sections.push(serde_json::from_str::<Value>(&json)?);
+    }
+
+    let presets = hoist_presets(&mut sections)?;
+    if presets.is_empty() {
+        return Ok(0);
+    }
+    #[cfg(feature = "tracing")]
+    tracing::info!("{} condition presets in {name_space:?}", presets.len());
+
+    for (section_dir, section) in section_dirs.iter().zip(&sections) {
+        write_config_value(section_dir, section).await?;
+    }
+    let mut main_config: Value =
+        serde_json::from_str(&fs::read_to_string(&main_config_path).await?)?;
+    let len = presets.len();
+    if let Value::Object(main_config) = &mut main_config {
+        main_config.insert("conditionPresets".to_string(), Value::Array(presets));
+    }
+    write_config_value(name_space, &main_config).await?;
+    Ok(len)
+}
+
+/// Replace the structurally equal `AND`/`OR` subtrees repeated in `sections`(section `config.json`s)
+/// with [`PresetCondition`]s, and returns the [`ConditionPreset`]s.
+///
+/// The largest subtree is hoisted first, so a repeated block is not split into smaller presets.
+///
+/// # Errors
+/// Failed to serialize.
+pub(crate) fn hoist_presets(sections: &mut [Value]) -> Result<Vec<Value>> {
+    let mut presets = vec![];
+
+    loop {
+        // serialized subtree -> (occurrences, subtree)
+        let mut candidates = HashMap::new();
+        for section in sections.iter() {
+            collect_candidates(conditions(section), &mut candidates);
+        }
+
+        let Some(subtree) = candidates
+            .into_iter()
+            .filter(|(_, (count, _))| *count >= MIN_OCCURRENCES)
+            .max_by(|(a_key, (a_count, a)), (b_key, (b_count, b))| {
+                (count_conditions(a), a_count)
+                    .cmp(&(count_conditions(b), b_count))
+                    .then_with(|| b_key.cmp(a_key))
+            })
+            .map(|(_, (_, subtree))| subtree.clone())
+        else {
+            break;
+        };
+
+        let name = format!("Preset_{}", presets.len() + 1);
+        let reference = serde_json::to_value(PresetCondition {
+            preset: name.as_str().into(),
+            ..Default::default()
+        })?;
+        let mut users = vec![];
+        for section in sections.iter_mut() {
+            let replaced = section
+                .get_mut("conditions")
+                .and_then(Value::as_array_mut)
+                .map_or(0, |conditions| {
+                    replace_subtree(conditions, &subtree, &reference)
+                });
+            if let Some(user) = section.get("name").and_then(Value::as_str)
+                && replaced > 0
+                && !users.contains(&user)
+            {
+                users.push(user);
+            }
+        }
+
+        // A top-level `AND` is the same as the preset's own list.
+        let conditions = match (&subtree["condition"], &subtree["negated"]) {
+            (Value::String(and), Value::Null | Value::Bool(false)) if and == "AND" => {
+                conditions(&subtree).to_vec()
+            }
+            _ => vec![subtree],
+        };
+        let preset = serde_json::to_value(ConditionPreset {
+            name: name.into(),
+            description: format!("Used by: {}", users.join(", ")).into(),
+            conditions: vec![],
+        })?;
+        presets.push(with_conditions(preset, conditions));
+    }
+
+    Ok(presets)
+}
+
+/// `conditions` of a section, or `Conditions` of `AND`/`OR`.
+fn conditions(value: &Value) -> &[Value] {
+    value
+        .get("conditions")
+        .or_else(|| value.get("Conditions"))
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// `AND` or `OR`
+fn is_junction(value: &Value) -> bool {
+    value
+        .get("condition")
+        .and_then(Value::as_str)
+        .is_some_and(|condition| condition == "AND" || condition == "OR")
+}
+
+fn with_conditions(mut preset: Value, conditions: Vec<Value>) -> Value {
+    if let Value::Object(preset) = &mut preset {
+        preset.insert("conditions".to_string(), Value::Array(conditions));
+    }
+    preset
+}
+
+/// Count the `AND`/`OR` subtrees large enough to be a preset.
+fn collect_candidates<'v>(
+    conditions: &'v [Value],
+    candidates: &mut HashMap<String, (usize, &'v Value)>,
+) {
+    for condition in conditions.iter().filter(|condition| is_junction(condition)) {
+        if count_conditions(condition) >= MIN_PRESET_CONDITIONS {
+            candidates
+                .entry(condition.to_string())
+                .or_insert((0, condition))
+                .0 += 1;
+        }
+        collect_candidates(self::conditions(condition), candidates);
+    }
+}
+
+/// Replace the subtrees equal to `target` with `reference`.
+///
+/// Returns the number of replaced subtrees.
+fn replace_subtree(conditions: &mut [Value], target: &Value, reference: &Value) -> usize {
+    let mut replaced = 0;
+    for condition in conditions
+        .iter_mut()
+        .filter(|condition| is_junction(condition))
+    {
+        if condition == target {
+            *condition = reference.clone();
+            replaced += 1;
+        } else if let Some(children) = condition
+            .get_mut("Conditions")
+            .and_then(Value::as_array_mut)
+        {
+            replaced += replace_subtree(children, target, reference);
+        }
+    }
+    replaced
+}
+
+/// Number of the leaf conditions.
+fn count_conditions(condition: &Value) -> usize {
+    match is_junction(condition) {
+        true => conditions(condition).iter().map(count_conditions).sum(),
+        false => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_dar2oar;
+    use oar_conditions::conditions::ConditionsConfig;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    const NPCS: &str = r#"IsActorBase("Skyrim.esm" | 0x13BBF) OR
+IsActorBase("Skyrim.esm" | 0x13BC0) OR
+IsActorBase("Skyrim.esm" | 0x13BC1) AND"#;
+
+    fn section(name: &str, dar: &str) -> Result<Value> {
+        Ok(serde_json::to_value(ConditionsConfig {
+            name: name.into(),
+            conditions: parse_dar2oar(name, dar)?,
+            ..Default::default()
+        })?)
+    }
+
+    #[test]
+    fn should_hoist_repeated_subtrees() -> Result<()> {
+        let mut sections = [
+            section("Sneak", &format!("{NPCS}\nIsSneaking()"))?,
+            section("Sprint", &format!("{NPCS}\nIsSprinting()"))?,
+            section("Other", "IsInCombat()")?,
+        ];
+        let npcs = sections[0]["conditions"][0].clone();
+
+        let presets = hoist_presets(&mut sections)?;
+
+        let expected = json!([{
+            "name": "Preset_1",
+            "description": "Used by: Sneak, Sprint",
+            "conditions": [npcs],
+        }]);
+        assert_eq!(Value::Array(presets), expected);
+        let reference = json!({
+            "condition": "PRESET",
+            "requiredVersion": "2.3.0.0",
+            "Preset": "Preset_1",
+        });
+        assert_eq!(sections[0]["conditions"][0], reference);
+        assert_eq!(sections[1]["conditions"][0], reference);
+        assert_eq!(sections[0]["conditions"][1]["condition"], "IsSneaking");
+        assert_eq!(sections[2]["conditions"][0]["condition"], "IsInCombat");
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_hoist_unique_or_small_subtrees() -> Result<()> {
+        let small = "IsActorBase(\"Skyrim.esm\" | 0x7) OR\nIsFemale()";
+        let mut sections = [
+            section("A", NPCS)?,
+            section("B", small)?,
+            section("C", small)?,
+        ];
+        assert!(hoist_presets(&mut sections)?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_resolve_winner_of_converted_presets() -> Result<()> {
+        use crate::{CancellationToken, Closure, ConvertOptions, convert_dar_to_oar};
+        use oar_conditions::conditions::{MainConfig, Oar};
+        use oar_conditions::eval::{Namespace, Snapshot, resolve_winner};
+
+        let temp_dir = temp_dir::TempDir::new()?;
+        let animations = temp_dir
+            .path()
+            .join("TestMod/meshes/actors/character/animations");
+        for (priority, flag) in [("100", "IsSneaking()"), ("200", "IsSprinting()")] {
+            let dir = animations
+                .join("DynamicAnimationReplacer/_CustomConditions")
+                .join(priority);
+            fs::create_dir_all(&dir).await?;
+            fs::write(dir.join("_conditions.txt"), format!("{NPCS}\n{flag}")).await?;
+            fs::write(dir.join("idle.hkx"), "").await?;
+        }
+        let options = ConvertOptions {
+            dar_dir: animations
+                .join("DynamicAnimationReplacer")
+                .display()
+                .to_string(),
+            mod_name: Some("TestMod".into()),
+            extract_presets: true,
+            ..Default::default()
+        };
+        convert_dar_to_oar(options, Closure::default_event, CancellationToken::new()).await?;
+
+        let name_space = animations.join("OpenAnimationReplacer/TestMod");
+        let main_json = fs::read_to_string(name_space.join("config.json")).await?;
+        let mut section_jsons = vec![];
+        for section_dir in read_section_dirs(&name_space).await? {
+            section_jsons.push(fs::read_to_string(section_dir.join("config.json")).await?);
+        }
+        let namespace = Namespace {
+            config: serde_json::from_str::<MainConfig>(&main_json)?,
+            sections: (section_jsons.iter())
+                .map(|json| serde_json::from_str(json))
+                .collect::<core::result::Result<_, _>>()?,
+        };
+        assert_eq!(namespace.config.condition_presets.len(), 1);
+        assert!(matches!(
+            namespace.sections[0].conditions[0],
+            Oar::Preset(_)
+        ));
+
+        let snapshot: Snapshot = serde_json::from_str(
+            r#"{
+  "actorBase": { "plugin": "Skyrim.esm", "formID": "13BC0" },
+  "flags": { "sneaking": true }
+}"#,
+        )?;
+        let resolution = resolve_winner([&namespace], &snapshot, &mut |_, _| 0.0);
+        let winner = resolution.winner.map(|winner| winner.name);
+        assert_eq!(winner, Some("100"), "{:#?}", resolution.sections);
+        Ok(())
+    }
+}
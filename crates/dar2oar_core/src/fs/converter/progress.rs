@@ -0,0 +1,195 @@
+//! Progress events of the converter
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// What a DAR file was converted into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileKind {
+    /// `_conditions.txt` converted into `config.json`
+    Conditions,
+    /// Motion file(`.hkx`, etc.) copied into a section
+    Motion,
+    /// File in a non-numeric priority dir, copied as a memo
+    Memo,
+}
+
+/// Summary of a conversion.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertReport {
+    /// Number of `_conditions.txt` converted
+    pub conditions: usize,
+    /// Number of motion files copied
+    pub motions: usize,
+    /// Number of files copied as memos
+    pub memos: usize,
+    /// Number of warnings
+    pub warnings: usize,
+    /// Number of sections merged into animation variants of another section
+    pub variants: usize,
+    /// Number of sections referring to another section's motion files via `overrideAnimationsFolder`
+    pub deduplicated: usize,
+    /// Bytes of the motion files not copied thanks to the deduplication
+    pub bytes_saved: u64,
+}
+
+impl ConvertReport {
+    /// Number of converted files.
+    pub const fn files(&self) -> usize {
+        self.conditions + self.motions + self.memos
+    }
+
+    /// Count a converted file.
+    pub(super) const fn add(&mut self, kind: FileKind) {
+        match kind {
+            FileKind::Conditions => self.conditions += 1,
+            FileKind::Motion => self.motions += 1,
+            FileKind::Memo => self.memos += 1,
+        }
+    }
+}
+
+/// Event reported while converting.
+///
+/// Both the parallel and the sequential converters count only DAR files in [`ProgressEvent::Started`],
+/// so `total` matches the number of [`ProgressEvent::FileDone`] unless some files cannot be interpreted
+/// as DAR paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ProgressEvent {
+    /// Conversion started.
+    Started {
+        /// Number of DAR files to convert
+        total: usize,
+    },
+    /// Started converting a file.
+    FileStarted {
+        /// DAR file
+        path: PathBuf,
+    },
+    /// Finished converting a file.
+    FileDone {
+        /// DAR file
+        path: PathBuf,
+        /// What the file was converted into
+        kind: FileKind,
+    },
+    /// Converted, but something may need attention.
+    Warning {
+        /// DAR file
+        path: PathBuf,
+        /// Message
+        message: String,
+    },
+    /// All files were converted and the output was committed.
+    Finished {
+        /// Summary
+        report: ConvertReport,
+    },
+}
+
+/// Receiver of [`ProgressEvent`]s.
+///
+/// Implemented for every `FnMut(ProgressEvent)`.
+pub trait ProgressReporter {
+    /// Called for each event.
+    fn report(&mut self, event: ProgressEvent);
+}
+
+impl<F> ProgressReporter for F
+where
+    F: FnMut(ProgressEvent),
+{
+    #[inline]
+    fn report(&mut self, event: ProgressEvent) {
+        self(event);
+    }
+}
+
+/// The result of converting a single DAR file.
+#[derive(Debug)]
+pub(super) struct FileOutcome {
+    /// What the file was converted into
+    pub(super) kind: FileKind,
+    /// Warnings for the file
+    pub(super) warnings: Vec<String>,
+}
+
+impl FileOutcome {
+    /// Report [`ProgressEvent::Warning`]s and [`ProgressEvent::FileDone`], and add them to `report`.
+    pub(super) fn report_to(
+        self,
+        path: PathBuf,
+        report: &mut ConvertReport,
+        reporter: &mut impl ProgressReporter,
+    ) {
+        report.add(self.kind);
+        report.warnings += self.warnings.len();
+        for message in self.warnings {
+            reporter.report(ProgressEvent::Warning {
+                path: path.clone(),
+                message,
+            });
+        }
+        reporter.report(ProgressEvent::FileDone {
+            path,
+            kind: self.kind,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::fs::converter::{ConvertOptions, convert_dar_to_oar};
+    use temp_dir::TempDir;
+    use tokio::fs;
+    use tokio_util::sync::CancellationToken;
+
+    async fn convert(run_parallel: bool) -> Result<(Vec<ProgressEvent>, ConvertReport)> {
+        let temp_dir = TempDir::new()?;
+        let dar_dir = temp_dir.path().join("TestMod");
+        let section_dir = dar_dir.join(
+            "meshes/actors/character/animations/DynamicAnimationReplacer/_CustomConditions/100",
+        );
+        fs::create_dir_all(&section_dir).await?;
+        fs::write(section_dir.join("_conditions.txt"), "IsFemale()").await?;
+        fs::write(section_dir.join("mt_idle.hkx"), "").await?;
+
+        let mut events = vec![];
+        let options = ConvertOptions {
+            dar_dir: dar_dir.display().to_string(),
+            run_parallel,
+            ..Default::default()
+        };
+        let reporter = |event| events.push(event);
+        let report = convert_dar_to_oar(options, reporter, CancellationToken::new()).await?;
+        Ok((events, report))
+    }
+
+    #[tokio::test]
+    async fn should_report_same_events_in_both_converters() -> Result<()> {
+        for run_parallel in [false, true] {
+            let (events, report) = convert(run_parallel).await?;
+            let expected = ConvertReport {
+                conditions: 1,
+                motions: 1,
+                ..Default::default()
+            };
+            assert_eq!(report, expected);
+            assert_eq!(events.first(), Some(&ProgressEvent::Started { total: 2 }));
+            assert_eq!(
+                events.last(),
+                Some(&ProgressEvent::Finished { report: expected })
+            );
+            let done = events
+                .iter()
+                .filter(|event| matches!(event, ProgressEvent::FileDone { .. }))
+                .count();
+            assert_eq!(done, 2);
+        }
+        Ok(())
+    }
+}
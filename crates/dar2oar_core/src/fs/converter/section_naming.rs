@@ -0,0 +1,348 @@
+//! Derive readable section names from the DAR conditions.
+use crate::error::Result;
+use crate::fs::converter::ConvertOptions;
+use crate::fs::converter::common::is_dar_file;
+use crate::fs::converter::parallel::get_dar_files;
+use crate::fs::path_changer::parse_dar_path;
+use dar_syntax::ast::{ActorArgs, Dar, Expression, Function};
+use dar_syntax::parse_dar_syntax;
+use mapping_table::{MappingTable, plugin_key};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// How to name the OAR sections that have no entry in the mapping table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SectionNaming {
+    /// Use the raw priority(or base id). e.g. `8107000`
+    #[default]
+    Priority,
+    /// Derive a short name from the conditions. e.g. `Dagger_Sneaking`
+    ///
+    /// Names shared by several sections get a `_2`, `_3`... suffix.
+    Conditions,
+}
+
+/// Upper limit of the derived name length.
+const MAX_NAME_LEN: usize = 64;
+
+/// Fill `section_table` & `section_1person_table` with the names derived from the conditions.
+///
+/// Entries of the given tables take precedence over the derived names,
+/// and the derived names never collide with them.
+///
+/// # Errors
+/// Failed to read a `_conditions.txt`.
+pub(super) async fn fill_section_tables(options: &mut ConvertOptions) -> Result<()> {
+    let (table, table_1person) = derive_section_tables(
+        &options.dar_dir,
+        [
+            options.section_table.as_ref(),
+            options.section_1person_table.as_ref(),
+        ],
+    )
+    .await?;
+    for (section_table, derived) in [
+        (&mut options.section_table, table),
+        (&mut options.section_1person_table, table_1person),
+    ] {
+        let mut derived = derived;
+        if let Some(section_table) = section_table.take() {
+            derived.extend(section_table);
+        }
+        *section_table = Some(derived);
+    }
+    Ok(())
+}
+
+/// Returns derived `(table, table for _1st_person)`.
+///
+/// `user_tables`(`[table, table for _1st_person]`) are the given tables. Their sections are not named,
+/// and their names are not reused.
+async fn derive_section_tables(
+    dar_dir: impl AsRef<Path>,
+    user_tables: [Option<&MappingTable>; 2],
+) -> Result<(MappingTable, MappingTable)> {
+    let user_table = |is_1st_person: bool| user_tables[usize::from(is_1st_person)];
+
+    // (is_1st_person, priority, key) -> name. Sorted to make the collision suffixes stable.
+    let mut names = BTreeMap::new();
+    for entry in get_dar_files(dar_dir).into_iter().flatten() {
+        let path = entry.path();
+        if !is_dar_file(&path) {
+            continue;
+        }
+        let Ok(parsed) = parse_dar_path(&path) else {
+            continue;
+        };
+        let Ok(priority) = parsed.priority else {
+            continue;
+        };
+
+        let is_named = |key: &str| {
+            user_table(parsed.is_1st_person)
+                .and_then(|table| mapping_table::lookup(table, parsed.esp_dir.as_deref(), key))
+                .is_some()
+        };
+        let (key, name) = match (&parsed.esp_dir, &parsed.base_id) {
+            (Some(_), Some(base_id)) if is_named(base_id) => continue,
+            (Some(esp_dir), Some(base_id)) => (
+                plugin_key(esp_dir, base_id),
+                Some(format!("ActorBase_{}", short_form_id(base_id))),
+            ),
+            _ if path
+                .file_name()
+                .is_some_and(|name| name.eq_ignore_ascii_case("_conditions.txt")) =>
+            {
+                let key = priority.to_string();
+                if is_named(&key) {
+                    continue;
+                }
+                let content = tokio::fs::read_to_string(&path).await?;
+                let name = parse_dar_syntax(&content)
+                    .ok()
+                    .and_then(|dar| name_from_conditions(&dar));
+                (key, name)
+            }
+            _ => continue,
+        };
+        if let Some(name) = name {
+            names.insert((parsed.is_1st_person, priority, key), name);
+        }
+    }
+
+    let mut table = MappingTable::new();
+    let mut table_1person = MappingTable::new();
+    let mut used: HashSet<_> = [false, true]
+        .into_iter()
+        .flat_map(|is_1st_person| {
+            user_table(is_1st_person)
+                .into_iter()
+                .flat_map(MappingTable::values)
+                .map(move |name| (is_1st_person, name.clone()))
+        })
+        .collect();
+    for ((is_1st_person, _, key), name) in names {
+        let name = dedupe_name(&mut used, is_1st_person, name);
+        match is_1st_person {
+            true => table_1person.insert(key, name),
+            false => table.insert(key, name),
+        };
+    }
+    Ok((table, table_1person))
+}
+
+/// Append `_2`, `_3`... to `name` until it is unused in the same person table.
+fn dedupe_name(used: &mut HashSet<(bool, String)>, is_1st_person: bool, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut count = 1;
+    while used.contains(&(is_1st_person, candidate.clone())) {
+        count += 1;
+        candidate = format!("{name}_{count}");
+    }
+    used.insert((is_1st_person, candidate.clone()));
+    candidate
+}
+
+/// Derive a short section name from the DAR conditions.
+///
+/// e.g. `IsEquippedRightType(2) AND IsSneaking()` -> `Dagger_Sneaking`
+///
+/// Returns `None` if there is no condition.
+pub(crate) fn name_from_conditions(dar: &Dar) -> Option<String> {
+    let mut name = render(dar);
+    if name.len() > MAX_NAME_LEN {
+        name.truncate(MAX_NAME_LEN);
+        name.truncate(name.trim_end_matches('_').len());
+    }
+    (!name.is_empty()).then_some(name)
+}
+
+fn render(dar: &Dar) -> String {
+    let join = |conditions: &[Dar], separator| {
+        conditions
+            .iter()
+            .map(render)
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    };
+
+    match dar {
+        Dar::And(conditions) => join(conditions, "_"),
+        Dar::Or(conditions) => join(conditions, "_Or_"),
+        Dar::Exp(expression) => word(expression),
+    }
+}
+
+/// A word for a single condition. e.g. `IsActorBase("Skyrim.esm" | 0x13BBF)` -> `ActorBase_13BBF`
+fn word(expression: &Expression) -> String {
+    let Expression { function, negated } = expression;
+    let with_id = |kind: &str, form_id: &str| format!("{kind}_{}", short_form_id(form_id));
+    let actor = |args: &ActorArgs, kind: &str| format!("{}{kind}", args.actor_value().actor_value);
+
+    let word = match function {
+        Function::CurrentGameTimeLessThan { .. } => "GameTimeLessThan".into(),
+        Function::CurrentWeather { weather } => with_id("Weather", weather.form_id.as_str()),
+        Function::IsClass { class } => with_id("Class", class.form_id.as_str()),
+        Function::IsCombatStyle { combat_style } => {
+            with_id("CombatStyle", combat_style.form_id.as_str())
+        }
+        Function::IsActorValueEqualTo(args) => actor(args, "EqualTo"),
+        Function::IsActorValueLessThan(args) => actor(args, "LessThan"),
+        Function::IsActorValueBaseLessThan(args) => actor(args, "BaseLessThan"),
+        Function::IsActorValueMaxEqualTo(args) => actor(args, "MaxEqualTo"),
+        Function::IsActorValueMaxLessThan(args) => actor(args, "MaxLessThan"),
+        Function::IsActorValuePercentageEqualTo(args) => actor(args, "PercentageEqualTo"),
+        Function::IsActorValuePercentageLessThan(args) => actor(args, "PercentageLessThan"),
+        Function::IsActorBase { actor_base } => with_id("ActorBase", actor_base.form_id.as_str()),
+        Function::IsEquipped { form, is_left } => {
+            with_id(hand("Equipped", *is_left).as_str(), form.form_id.as_str())
+        }
+        Function::IsEquippedType { value, is_left } => match is_left {
+            true => format!("Left{value:?}"),
+            false => format!("{value:?}"),
+        },
+        Function::IsEquippedHasKeyword { keyword, is_left } => with_id(
+            hand("EquippedKeyword", *is_left).as_str(),
+            keyword.form_id.as_str(),
+        ),
+        Function::IsEquippedShout { shout } => with_id("Shout", shout.form_id.as_str()),
+        Function::IsInFaction { faction } => with_id("Faction", faction.form_id.as_str()),
+        Function::IsFactionRankEqualTo { faction, .. }
+        | Function::IsFactionRankLessThan { faction, .. } => {
+            with_id("FactionRank", faction.form_id.as_str())
+        }
+        Function::IsInLocation { location } => with_id("Location", location.form_id.as_str()),
+        Function::IsLevelLessThan { .. } => "LevelLessThan".into(),
+        Function::IsParentCell { cell } => with_id("Cell", cell.form_id.as_str()),
+        Function::IsMovementDirection { direction } => format!("Move{direction:?}"),
+        Function::IsRace { race } => with_id("Race", race.form_id.as_str()),
+        Function::IsVoiceType { voice_type } => with_id("VoiceType", voice_type.form_id.as_str()),
+        Function::IsWorldSpace { world_space } => {
+            with_id("WorldSpace", world_space.form_id.as_str())
+        }
+        Function::IsWorn { form } => with_id("Worn", form.form_id.as_str()),
+        Function::IsWornHasKeyword { keyword } => with_id("WornKeyword", keyword.form_id.as_str()),
+        Function::HasKeyword { keyword } => with_id("Keyword", keyword.form_id.as_str()),
+        Function::HasPerk { perk } => with_id("Perk", perk.form_id.as_str()),
+        Function::HasSpell { spell } => with_id("Spell", spell.form_id.as_str()),
+        Function::HasMagicEffect { magic_effect } => {
+            with_id("MagicEffect", magic_effect.form_id.as_str())
+        }
+        Function::HasMagicEffectWithKeyword { keyword } => {
+            with_id("MagicEffectKeyword", keyword.form_id.as_str())
+        }
+        Function::HasRefType { location_ref_type } => {
+            with_id("RefType", location_ref_type.form_id.as_str())
+        }
+        Function::Random { .. } => "Random".into(),
+        Function::ValueEqualTo { .. } => "ValueEqualTo".into(),
+        Function::ValueLessThan { .. } => "ValueLessThan".into(),
+        Function::IsFemale => "Female".into(),
+        Function::IsChild => "Child".into(),
+        Function::IsPlayerTeammate => "PlayerTeammate".into(),
+        Function::IsInInterior => "InInterior".into(),
+        Function::IsUnique => "Unique".into(),
+        Function::IsAttacking => "Attacking".into(),
+        Function::IsRunning => "Running".into(),
+        Function::IsSneaking => "Sneaking".into(),
+        Function::IsSprinting => "Sprinting".into(),
+        Function::IsInAir => "InAir".into(),
+        Function::IsInCombat => "InCombat".into(),
+        Function::IsWeaponDrawn => "WeaponDrawn".into(),
+    };
+
+    match negated {
+        true => format!("Not{word}"),
+        false => word,
+    }
+}
+
+/// `Equipped` -> `EquippedLeft`/`EquippedRight`
+fn hand(kind: &str, is_left: bool) -> String {
+    match is_left {
+        true => format!("{kind}Left"),
+        false => format!("{kind}Right"),
+    }
+}
+
+/// Upper hex without leading zeros. e.g. `00013bbf` -> `13BBF`
+fn short_form_id(form_id: &str) -> String {
+    let trimmed = form_id.trim_start_matches('0');
+    match trimmed.is_empty() {
+        true => "0".into(),
+        false => trimmed.to_ascii_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    fn name(input: &str) -> Option<String> {
+        name_from_conditions(&parse_dar_syntax(input).ok()?)
+    }
+
+    #[test]
+    fn should_derive_name_from_conditions() {
+        assert_eq!(
+            name("IsEquippedRightType(2) AND\nIsSneaking()").as_deref(),
+            Some("Dagger_Sneaking")
+        );
+        assert_eq!(
+            name(r#"IsActorBase("Skyrim.esm" | 0x00013BBF)"#).as_deref(),
+            Some("ActorBase_13BBF")
+        );
+        assert_eq!(
+            name("NOT IsInCombat() OR\nIsFemale()").as_deref(),
+            Some("NotInCombat_Or_Female")
+        );
+        assert_eq!(
+            name("IsActorValuePercentageLessThan(24, 0.3)").as_deref(),
+            Some("HealthPercentageLessThan")
+        );
+    }
+
+    #[test]
+    fn should_dedupe_names() {
+        let mut used = HashSet::new();
+        let names: Vec<_> = ["Sneaking", "Sneaking", "Sneaking"]
+            .into_iter()
+            .map(|name| dedupe_name(&mut used, false, name.into()))
+            .collect();
+        assert_eq!(names, ["Sneaking", "Sneaking_2", "Sneaking_3"]);
+        assert_eq!(dedupe_name(&mut used, true, "Sneaking".into()), "Sneaking");
+    }
+
+    #[tokio::test]
+    async fn should_not_rename_or_reuse_user_table_names() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dar_root = temp_dir
+            .path()
+            .join("Mod/meshes/actors/character/animations/DynamicAnimationReplacer");
+        for priority in ["100", "200"] {
+            let dir = dar_root.join("_CustomConditions").join(priority);
+            tokio::fs::create_dir_all(&dir).await?;
+            tokio::fs::write(dir.join("_conditions.txt"), "IsSneaking()").await?;
+        }
+        let actor_dir = dar_root.join("Skyrim.esm/00013BBF");
+        tokio::fs::create_dir_all(&actor_dir).await?;
+        tokio::fs::write(actor_dir.join("idle.hkx"), "").await?;
+
+        let user_table: MappingTable = [
+            ("100".into(), "Sneaking".into()),
+            ("00013BBF".into(), "Lydia".into()),
+        ]
+        .into();
+        let (table, table_1person) =
+            derive_section_tables(temp_dir.path(), [Some(&user_table), None]).await?;
+
+        let expected: MappingTable = [("200".into(), "Sneaking_2".into())].into();
+        assert_eq!(table, expected);
+        assert!(table_1person.is_empty());
+        Ok(())
+    }
+}
@@ -1,55 +1,135 @@
 //! Single thread converter
 use crate::error::{ConvertError, Result};
 use crate::fs::converter::ConvertOptions;
-use crate::fs::converter::common::{common_process, is_contain_dar};
+use crate::fs::converter::common::{
+    common_process, hide_dar_paths, is_dar_file, resolve_oar_namespace,
+};
+use crate::fs::converter::progress::{ConvertReport, ProgressEvent, ProgressReporter};
+use crate::fs::converter::staging::Staging;
+use crate::fs::manifest::Manifest;
 use crate::fs::path_changer::parse_dar_path;
 use async_walkdir::{Filtering, WalkDir};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tes_plugin::PluginIndex;
 use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
 
 /// Single thread converter
 ///
 /// # Parameters
 /// - `options`: Convert options
-/// - `progress_fn`: For progress callback(1st time: max contents count, 2nd~: index)
+/// - `reporter`: Receives [`ProgressEvent`]s.
+/// - `cancel_token`: Stops the conversion and discards the staged output.
 ///
 /// # Errors
-/// Failed to convert
+/// Failed to convert, or [`ConvertError::Cancelled`] if cancelled.
 pub async fn convert_dar_to_oar(
     options: ConvertOptions,
-    mut progress_fn: impl FnMut(usize),
-) -> Result<()> {
+    mut reporter: impl ProgressReporter,
+    cancel_token: CancellationToken,
+) -> Result<ConvertReport> {
     let dar_dir = options.dar_dir.as_str();
 
-    let walk_len = get_dar_file_count(dar_dir).await?;
+    let total = get_dar_file_count(dar_dir).await?;
     #[cfg(feature = "tracing")]
-    tracing::info!("Sequential Converter/DAR file counts: {}", walk_len);
-    progress_fn(walk_len);
+    tracing::info!("Sequential Converter/DAR file counts: {}", total);
+    reporter.report(ProgressEvent::Started { total });
+
+    let plugins =
+        (options.data_dir.as_deref()).map(|data_dir| Arc::new(PluginIndex::new(data_dir)));
+    let mut staging = Staging::default();
+    let mut dar_paths = vec![];
+    let mut report = ConvertReport::default();
+    let result = convert_to_staging(
+        &options,
+        plugins.as_ref(),
+        &mut staging,
+        &mut dar_paths,
+        &mut report,
+        &mut reporter,
+        &cancel_token,
+    )
+    .await;
+    let result = match result {
+        Ok(()) if options.extract_presets => {
+            staging
+                .extract_presets(options.target_oar_version, &mut report, &mut reporter)
+                .await
+        }
+        result => result,
+    };
+    let result = match result {
+        Ok(()) if options.collapse_variants => {
+            staging.collapse_variants(&mut report, &mut reporter).await
+        }
+        result => result,
+    };
+    let result = match result {
+        Ok(()) if options.dedupe_motions => staging.dedupe_motions(&mut report).await,
+        result => result,
+    };
+
+    if let Err(err) = result {
+        staging.rollback().await;
+        return Err(err);
+    }
+    if dar_paths.is_empty() {
+        return Err(ConvertError::NeverConverted {
+            path: options.dar_dir.as_str().into(),
+        });
+    }
+
+    staging.commit(&Manifest::new(&options.dar_dir)).await?;
+    if options.hide_dar {
+        hide_dar_paths(Path::new(&options.dar_dir), dar_paths).await?;
+    }
+    reporter.report(ProgressEvent::Finished {
+        report: report.clone(),
+    });
+    Ok(report)
+}
 
-    let mut entries = get_dar_files(dar_dir).await;
-    let mut idx = 0;
+/// Convert all DAR files into staging dirs, collecting the converted DAR file paths.
+async fn convert_to_staging(
+    options: &ConvertOptions,
+    plugins: Option<&Arc<PluginIndex>>,
+    staging: &mut Staging,
+    dar_paths: &mut Vec<PathBuf>,
+    report: &mut ConvertReport,
+    reporter: &mut impl ProgressReporter,
+    cancel_token: &CancellationToken,
+) -> Result<()> {
+    let mut entries = get_dar_files(&options.dar_dir).await;
     while let Some(entry) = entries.next().await {
+        if cancel_token.is_cancelled() {
+            return Err(ConvertError::Cancelled);
+        }
+
         let path = entry?.path();
-        let path = path.as_path();
-        if !path.is_file() {
+        if !is_dar_file(&path) {
             continue;
         }
-        let parsed_path = match parse_dar_path(path) {
+        let parsed_path = match parse_dar_path(&path) {
             Ok(p) => p,
             Err(_) => continue,
         };
+        let staging_dir = staging
+            .stage(resolve_oar_namespace(options, &parsed_path))
+            .await?;
 
         #[cfg(feature = "tracing")]
-        tracing::debug!("[Start {}th conversion]\n{:?}", idx, &parsed_path);
-        common_process(&options, path, &parsed_path).await?;
-        progress_fn(idx);
+        tracing::debug!("[Start conversion]\n{:?}", &parsed_path);
+        reporter.report(ProgressEvent::FileStarted { path: path.clone() });
+        let outcome = tokio::select! {
+            biased;
+            () = cancel_token.cancelled() => return Err(ConvertError::Cancelled),
+            result = common_process(options, plugins, &path, &parsed_path, &staging_dir) => result?,
+        };
+        outcome.report_to(path.clone(), report, reporter);
         #[cfg(feature = "tracing")]
-        tracing::debug!("[End {}th conversion]\n\n", idx);
-        idx += 1;
-    }
-
-    if idx == 0 {
-        return Err(ConvertError::NeverConverted);
+        tracing::debug!("[End conversion]\n\n");
+        dar_paths.push(path);
     }
 
     Ok(())
@@ -74,7 +154,7 @@ async fn get_dar_file_count(root: impl AsRef<Path>) -> Result<usize> {
         let path = entry?.path();
         #[cfg(feature = "tracing")]
         tracing::trace!("Calculate walk_len[{}]: {:?}", walk_len, &path);
-        if is_contain_dar(path).is_some() {
+        if is_dar_file(&path) {
             walk_len += 1;
         }
     }
@@ -0,0 +1,307 @@
+//! Staged output for the converters
+//!
+//! Each OAR namespace is first written to `<animations>/.dar2oar_staging/<mod name>` and is renamed
+//! into `OpenAnimationReplacer/<mod name>` only after the whole conversion has succeeded.
+//! A namespace that already existed is moved to `<animations>/.dar2oar_backup/<mod name>` first,
+//! so the last good state can be restored by hand.
+//!
+//! Both dirs are placed outside `OpenAnimationReplacer` so that OAR never loads them.
+use crate::error::{ConvertError, Result};
+use crate::fs::converter::progress::{ConvertReport, ProgressEvent, ProgressReporter};
+use crate::fs::manifest::Manifest;
+use oar_conditions::version::OarVersion;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Dir name holding the namespaces under conversion.
+const STAGING_DIR: &str = ".dar2oar_staging";
+/// Dir name holding the namespaces replaced by the last successful conversion.
+const BACKUP_DIR: &str = ".dar2oar_backup";
+
+/// A set of OAR namespaces written to staging dirs.
+#[derive(Debug, Default)]
+pub(super) struct Staging {
+    /// OAR namespace -> staging dir
+    name_spaces: HashMap<PathBuf, PathBuf>,
+}
+
+impl Staging {
+    /// Returns the staging dir to write `name_space` into.
+    ///
+    /// The first time a namespace is seen, the leftover of an interrupted run is removed.
+    ///
+    /// # Errors
+    /// - The namespace has no mod name part.
+    /// - Failed to remove the leftover.
+    pub(super) async fn stage(&mut self, name_space: PathBuf) -> Result<PathBuf> {
+        if let Some(staging) = self.name_spaces.get(&name_space) {
+            return Ok(staging.clone());
+        }
+
+        let staging = sibling_dir(&name_space, STAGING_DIR)?;
+        if staging.exists() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Remove leftover staging dir: {staging:?}");
+            fs::remove_dir_all(&staging).await?;
+        }
+        self.name_spaces.insert(name_space, staging.clone());
+        Ok(staging)
+    }
+
+    /// Hoist the repeated conditions of every staged namespace into condition presets.
+    ///
+    /// If `target` is older than the OAR supporting presets, nothing is extracted and it is reported instead.
+    ///
+    /// # Errors
+    /// Failed to read or write `config.json`s.
+    pub(super) async fn extract_presets(
+        &self,
+        target: Option<OarVersion>,
+        report: &mut ConvertReport,
+        reporter: &mut impl ProgressReporter,
+    ) -> Result<()> {
+        if let Some(target) = target.filter(|target| *target < OarVersion::V2_3) {
+            for name_space in self.name_spaces.keys() {
+                report.warnings += 1;
+                reporter.report(ProgressEvent::Warning {
+                    path: name_space.clone(),
+                    message: format!(
+                        "Condition presets require OAR {}, newer than the target {target}. They were not extracted.",
+                        OarVersion::V2_3
+                    ),
+                });
+            }
+            return Ok(());
+        }
+
+        for staging in self.name_spaces.values() {
+            super::presets::extract_presets(staging).await?;
+        }
+        Ok(())
+    }
+
+    /// Merge the `Random` variant sections of every staged namespace into animation variants,
+    /// adding the result to `report` and reporting the groups that could not be merged.
+    ///
+    /// # Errors
+    /// Failed to read, move or write the files.
+    pub(super) async fn collapse_variants(
+        &self,
+        report: &mut ConvertReport,
+        reporter: &mut impl ProgressReporter,
+    ) -> Result<()> {
+        for (name_space, staging) in &self.name_spaces {
+            let collapsed = super::variants::collapse_variants(staging).await?;
+            report.variants += collapsed.merged;
+            report.warnings += collapsed.warnings.len();
+            for (section, message) in collapsed.warnings {
+                reporter.report(ProgressEvent::Warning {
+                    path: name_space.join(section),
+                    message,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Deduplicate the motion files of every staged namespace, adding the result to `report`.
+    ///
+    /// # Errors
+    /// Failed to read, remove or write the files.
+    pub(super) async fn dedupe_motions(&self, report: &mut ConvertReport) -> Result<()> {
+        for staging in self.name_spaces.values() {
+            super::dedupe::dedupe_motions(staging, report).await?;
+        }
+        Ok(())
+    }
+
+    /// Write `manifest` into every staging dir and move them into place, backing up pre-existing namespaces.
+    ///
+    /// If any move fails, the namespaces already moved are reverted and the backups are restored.
+    ///
+    /// # Errors
+    /// Failed to move the dirs.
+    pub(super) async fn commit(self, manifest: &Manifest) -> Result<()> {
+        let mut committed = Vec::with_capacity(self.name_spaces.len());
+        let mut remaining = self.name_spaces.into_iter();
+
+        for (name_space, staging) in remaining.by_ref() {
+            if !staging.exists() {
+                continue; // Nothing was written. e.g. every entry failed to parse.
+            }
+
+            let result = match manifest.write(&staging).await {
+                Ok(()) => commit_one(&name_space, &staging).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(backup) => {
+                    remove_staging(&staging).await; // Only the empty parent is left.
+                    committed.push((name_space, backup));
+                }
+                Err(err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("Failed to commit {name_space:?}. Rolling back: {err}");
+                    for (name_space, backup) in committed {
+                        revert_one(&name_space, backup.as_deref()).await;
+                    }
+                    remove_staging(&staging).await;
+                    for (_, staging) in remaining {
+                        remove_staging(&staging).await;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove every staging dir, leaving the existing namespaces untouched.
+    pub(super) async fn rollback(self) {
+        for staging in self.name_spaces.into_values() {
+            remove_staging(&staging).await;
+        }
+    }
+}
+
+/// Returns `<animations>/<dir_name>/<mod name>` for `<animations>/OpenAnimationReplacer/<mod name>`.
+fn sibling_dir(name_space: &Path, dir_name: &str) -> Result<PathBuf> {
+    let mod_name = name_space
+        .file_name()
+        .ok_or_else(|| ConvertError::NotFoundFileName {
+            path: name_space.to_path_buf(),
+        })?;
+    let oar_dir = name_space.parent().unwrap_or_else(|| Path::new(""));
+    let animations_dir = oar_dir.parent().unwrap_or(oar_dir);
+    Ok(animations_dir.join(dir_name).join(mod_name))
+}
+
+/// Move `staging` to `name_space`. Returns the backup path if `name_space` existed.
+async fn commit_one(name_space: &Path, staging: &Path) -> Result<Option<PathBuf>> {
+    let backup = if name_space.exists() {
+        let backup = sibling_dir(name_space, BACKUP_DIR)?;
+        if backup.exists() {
+            fs::remove_dir_all(&backup).await?;
+        }
+        create_parent_dir(&backup).await?;
+        #[cfg(feature = "tracing")]
+        tracing::info!("Back up {name_space:?} to {backup:?}");
+        fs::rename(name_space, &backup).await?;
+        Some(backup)
+    } else {
+        None
+    };
+
+    let result = async {
+        create_parent_dir(name_space).await?;
+        fs::rename(staging, name_space).await
+    }
+    .await;
+
+    if let Err(err) = result {
+        if let Some(backup) = &backup {
+            fs::rename(backup, name_space).await?;
+        }
+        return Err(err.into());
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!("Committed: {name_space:?}");
+    Ok(backup)
+}
+
+/// Undo [`commit_one`] as far as possible. Failures are only logged.
+async fn revert_one(name_space: &Path, backup: Option<&Path>) {
+    let result = async {
+        fs::remove_dir_all(name_space).await?;
+        if let Some(backup) = backup {
+            fs::rename(backup, name_space).await?;
+        }
+        Ok::<_, std::io::Error>(())
+    }
+    .await;
+
+    #[cfg(feature = "tracing")]
+    if let Err(err) = result {
+        tracing::error!("Failed to revert {name_space:?}: {err}");
+    }
+    #[cfg(not(feature = "tracing"))]
+    drop(result);
+}
+
+/// Remove a staging dir and its parent if it became empty. Failures are only logged.
+async fn remove_staging(staging: &Path) {
+    if staging.exists()
+        && let Err(_err) = fs::remove_dir_all(staging).await
+    {
+        #[cfg(feature = "tracing")]
+        tracing::error!("Failed to remove staging dir {staging:?}: {_err}");
+    }
+    if let Some(parent) = staging.parent() {
+        let _ = fs::remove_dir(parent).await; // Fails unless empty, which is intended.
+    }
+}
+
+async fn create_parent_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) => fs::create_dir_all(parent).await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    const OAR_DIR: &str = "meshes/actors/character/animations/OpenAnimationReplacer";
+
+    #[tokio::test]
+    async fn should_commit_and_back_up_existing_namespace() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path().join(OAR_DIR).join("TestMod");
+        fs::create_dir_all(&name_space).await?;
+        fs::write(name_space.join("config.json"), "old").await?;
+
+        let mut staging = Staging::default();
+        let staging_dir = staging.stage(name_space.clone()).await?;
+        assert!(!staging_dir.starts_with(temp_dir.path().join(OAR_DIR)));
+        fs::create_dir_all(&staging_dir).await?;
+        fs::write(staging_dir.join("config.json"), "new").await?;
+        staging.commit(&Manifest::new("DAR")).await?;
+
+        assert_eq!(
+            fs::read_to_string(name_space.join("config.json")).await?,
+            "new"
+        );
+        let backup = sibling_dir(&name_space, BACKUP_DIR)?;
+        assert_eq!(fs::read_to_string(backup.join("config.json")).await?, "old");
+        assert!(!staging_dir.exists());
+        assert!(Manifest::read(&name_space).await.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_rollback_without_touching_existing_namespace() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path().join(OAR_DIR).join("TestMod");
+        fs::create_dir_all(&name_space).await?;
+        fs::write(name_space.join("config.json"), "old").await?;
+
+        let mut staging = Staging::default();
+        let staging_dir = staging.stage(name_space.clone()).await?;
+        fs::create_dir_all(&staging_dir).await?;
+        fs::write(staging_dir.join("config.json"), "half written").await?;
+        staging.rollback().await;
+
+        assert_eq!(
+            fs::read_to_string(name_space.join("config.json")).await?,
+            "old"
+        );
+        assert!(!staging_dir.exists());
+        assert!(!sibling_dir(&name_space, BACKUP_DIR)?.exists());
+        Ok(())
+    }
+}
@@ -1,32 +1,46 @@
 //! Auxiliary commands for smooth use of the converter
 use crate::error::{ConvertError, Result};
 use crate::fs::converter::parallel::{get_dar_files, get_oar, is_contain_oar};
-use std::ffi::{OsStr, OsString};
-use std::path::Path;
+use crate::fs::hide_journal::{self, HideJournal, UnhideReport};
+use crate::fs::manifest::Manifest;
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::fs;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 /// A parallel search will find the `DynamicAnimationReplacer` directory in the path passed as the argument
 /// and remove only the `mohidden` extension names from the files in that directory.
 ///
 /// # Errors
 /// - Failed to find the `DynamicAnimationReplacer` directory
+/// - [`ConvertError::Cancelled`] if `cancel_token` is cancelled
 pub async fn unhide_dar(
     dar_dir: impl AsRef<Path>,
     mut progress_fn: impl FnMut(usize),
+    cancel_token: CancellationToken,
 ) -> Result<()> {
-    let walk_len = get_dar_files(&dar_dir).into_iter().count();
+    let dar_dir = dar_dir.as_ref();
+    let walk_len = get_dar_files(dar_dir).into_iter().count();
     #[cfg(feature = "tracing")]
     tracing::debug!("Parallel unhide DAR dir & file counts: {}", walk_len);
     progress_fn(walk_len);
 
-    let mut task_handles: Vec<tokio::task::JoinHandle<Result<()>>> = Vec::new();
+    let mut task_handles = JoinSet::new();
     let rename_once = Arc::new(AtomicBool::new(false));
 
     let entires = get_dar_files(dar_dir).into_iter();
     for (idx, entry) in entires.enumerate() {
-        let path = Arc::new(entry.map_err(|_err| ConvertError::NotFoundEntry)?.path());
+        if cancel_token.is_cancelled() {
+            break;
+        }
+        let path = entry.map_err(|_err| ConvertError::NotFoundEntry {
+            path: dar_dir.to_path_buf(),
+        })?;
+        let path = Arc::new(path.path());
 
         if path.extension() != Some(OsStr::new("mohidden")) {
             continue;
@@ -34,7 +48,7 @@ pub async fn unhide_dar(
 
         #[cfg(feature = "tracing")]
         tracing::debug!("{:?}", &path);
-        task_handles.push(tokio::spawn({
+        task_handles.spawn({
             let rename_once = Arc::clone(&rename_once);
             let path = Arc::clone(&path);
             async move {
@@ -52,86 +66,170 @@ pub async fn unhide_dar(
                     rename_once.compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed);
                 Ok(())
             }
-        }));
+        });
         progress_fn(idx);
     }
 
-    for task_handle in task_handles {
-        task_handle.await??;
-    }
+    join_or_abort(task_handles, &cancel_token).await?;
 
     match rename_once.load(Ordering::Relaxed) {
         true => Ok(()),
-        false => Err(ConvertError::NotFoundUnhideTarget),
+        false => Err(ConvertError::NotFoundUnhideTarget {
+            path: dar_dir.to_path_buf(),
+        }),
     }
 }
 
-/// A parallel search will find and remove the `OpenAnimationReplacer` directory from the path passed as the argument.
+/// Reverse exactly the renames recorded by the converter's `hide_dar` in the journal of `dar_dir`.
+///
+/// Unlike [`unhide_dar`], files the user hid on purpose are left alone.
+/// Renames that cannot be reversed are reported as conflicts and kept in the journal.
+///
+/// # Errors
+/// - Not found the journal
+/// - Failed to rename a file
+/// - [`ConvertError::Cancelled`] if `cancel_token` is cancelled
+pub async fn unhide_dar_from_journal(
+    dar_dir: impl AsRef<Path>,
+    mut progress_fn: impl FnMut(usize),
+    cancel_token: CancellationToken,
+) -> Result<UnhideReport> {
+    let dar_dir = dar_dir.as_ref();
+    let journal_path = HideJournal::path(dar_dir);
+    if !journal_path.exists() {
+        return Err(ConvertError::NotFoundHideJournal { path: journal_path });
+    }
+
+    let mut journal = HideJournal::load(dar_dir).await?;
+    progress_fn(journal.renames.len());
+    let report = hide_journal::reverse(dar_dir, &mut journal, progress_fn, &cancel_token).await;
+    journal.save(dar_dir).await?;
+    report
+}
+
+/// Options for [`remove_oar`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoveOarOptions {
+    /// Remove whole `OpenAnimationReplacer` directories, including namespaces not generated by dar2oar.
+    pub force: bool,
+    /// Only list the paths that would be removed.
+    pub dry_run: bool,
+}
+
+/// A parallel search will find the `OpenAnimationReplacer` directory from the path passed as the argument
+/// and remove the namespaces in it that have a dar2oar [`Manifest`].
+///
+/// Returns the removed paths (or the paths that would be removed with `dry_run`).
 ///
 /// # Errors
 /// - Failed to find the `OpenAnimationReplacer` directory
+/// - [`ConvertError::Cancelled`] if `cancel_token` is cancelled
 pub async fn remove_oar(
     search_dir: impl AsRef<Path>,
+    options: RemoveOarOptions,
     mut progress_fn: impl FnMut(usize),
-) -> Result<()> {
-    let walk_len = get_oar(&search_dir).into_iter().count();
+    cancel_token: CancellationToken,
+) -> Result<Vec<PathBuf>> {
+    let search_dir = search_dir.as_ref();
+    let walk_len = get_oar(search_dir).into_iter().count();
     #[cfg(feature = "tracing")]
     tracing::debug!("Parallel remove OAR dir & file counts: {}", walk_len);
     progress_fn(walk_len);
 
-    let mut task_handles: Vec<tokio::task::JoinHandle<Result<()>>> = Vec::new();
-    let found_once = Arc::new(AtomicBool::new(false));
-    let mut prev_dir = OsString::new();
-
+    let mut oar_dirs = BTreeSet::new();
     for (idx, entry) in get_oar(search_dir).into_iter().enumerate() {
-        let path = Arc::new(entry.map_err(|_err| ConvertError::NotFoundEntry)?.path());
+        if cancel_token.is_cancelled() {
+            return Err(ConvertError::Cancelled);
+        }
+        let path = entry
+            .map_err(|_err| ConvertError::NotFoundEntry {
+                path: search_dir.to_path_buf(),
+            })?
+            .path();
         if path.is_dir()
-            && let Some(oar_start_idx) = is_contain_oar(path.as_ref())
+            && let Some(oar_start_idx) = is_contain_oar(&path)
         {
-            let paths: Vec<&OsStr> = path.iter().collect();
-
-            if let Some(oar_dir) = paths
-                .get(0..=oar_start_idx)
-                .map(|str_paths| str_paths.join(OsStr::new("/")))
-            {
-                if prev_dir == oar_dir {
-                    continue;
-                }
-                prev_dir.clone_from(&oar_dir);
-
-                task_handles.push(tokio::spawn({
-                    let found_once = Arc::clone(&found_once);
-
-                    async move {
-                        #[cfg(feature = "tracing")]
-                        tracing::debug!("Try to remove oar dir: {:?}\n", &oar_dir);
-                        fs::remove_dir_all(oar_dir).await?;
-                        // # Ordering validity:
-                        // Use `AcqRel` to `happened before relationship`(form a memory read/write order between threads) of cas(compare_and_swap),
-                        // so that other threads read after writing true to memory to prevent unnecessary file writing.
-                        // - In case of cas failure, use `Relaxed` because the order is unimportant.
-                        let _ = found_once.compare_exchange(
-                            false,
-                            true,
-                            Ordering::AcqRel,
-                            Ordering::Relaxed,
-                        );
-                        Ok(())
-                    }
-                }));
-            };
+            oar_dirs.insert(path.iter().take(oar_start_idx + 1).collect::<PathBuf>());
         };
         progress_fn(idx);
     }
 
-    for task_handle in task_handles {
-        task_handle.await??;
+    if oar_dirs.is_empty() {
+        return Err(ConvertError::NotFoundOarDir {
+            path: search_dir.to_path_buf(),
+        });
     }
 
-    match found_once.load(Ordering::Relaxed) {
-        true => Ok(()),
-        false => Err(ConvertError::NotFoundOarDir),
+    let targets = match options.force {
+        true => oar_dirs.into_iter().collect(),
+        false => find_generated_name_spaces(oar_dirs).await?,
+    };
+    if options.dry_run {
+        return Ok(targets);
+    }
+
+    let mut task_handles = JoinSet::new();
+    for target in &targets {
+        let target = target.clone();
+        task_handles.spawn(async move {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Try to remove: {:?}\n", &target);
+            fs::remove_dir_all(target).await?;
+            Ok(())
+        });
+    }
+    join_or_abort(task_handles, &cancel_token).await?;
+
+    Ok(targets)
+}
+
+/// Collect the namespaces having a dar2oar [`Manifest`] directly under the `OpenAnimationReplacer` dirs.
+async fn find_generated_name_spaces(oar_dirs: BTreeSet<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut name_spaces = vec![];
+    for oar_dir in oar_dirs {
+        let mut entries = fs::read_dir(&oar_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.is_dir() && Manifest::read(&path).await.is_some() {
+                name_spaces.push(path);
+            } else {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("Skip not generated by dar2oar: {:?}", &path);
+            }
+        }
+    }
+    name_spaces.sort();
+    Ok(name_spaces)
+}
+
+/// Wait for all tasks, aborting the remaining ones as soon as `cancel_token` is cancelled.
+///
+/// # Errors
+/// The first task error, or [`ConvertError::Cancelled`] if cancelled.
+async fn join_or_abort(
+    mut task_handles: JoinSet<Result<()>>,
+    cancel_token: &CancellationToken,
+) -> Result<()> {
+    loop {
+        tokio::select! {
+            biased;
+            () = cancel_token.cancelled() => {
+                task_handles.abort_all();
+                while task_handles.join_next().await.is_some() {}
+                return Err(ConvertError::Cancelled);
+            }
+            result = task_handles.join_next() => match result {
+                Some(result) => result??,
+                None => break,
+            },
+        }
+    }
+
+    // Scheduling may have stopped early without any task in flight.
+    if cancel_token.is_cancelled() {
+        return Err(ConvertError::Cancelled);
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -163,7 +261,42 @@ mod test {
         create_dir_all(test_dir.as_path()).await?;
         let _ = File::create(test_dir.join("_condition.txt.mohidden")).await?;
 
-        assert!(unhide_dar(temp_dir.path(), sender!()).await.is_ok());
+        assert!(
+            unhide_dar(temp_dir.path(), sender!(), CancellationToken::new())
+                .await
+                .is_ok()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_unhide_only_journaled_files() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let dar_dir = temp_dir.path();
+        let test_dir =
+            dar_dir.join("meshes/actors/character/animations/DynamicAnimationReplacer/100");
+        create_dir_all(&test_dir).await?;
+        let _ = File::create(test_dir.join("_conditions.txt.mohidden")).await?;
+        let _ = File::create(test_dir.join("conflict.hkx.mohidden")).await?;
+        let _ = File::create(test_dir.join("conflict.hkx")).await?;
+        let _ = File::create(test_dir.join("hidden_by_user.hkx.mohidden")).await?;
+
+        let mut journal = HideJournal::default();
+        for name in ["_conditions.txt", "conflict.hkx"] {
+            let from = test_dir.join(name);
+            journal.record(dar_dir, &from, &test_dir.join(format!("{name}.mohidden")));
+        }
+        journal.save(dar_dir).await?;
+
+        let report = unhide_dar_from_journal(dar_dir, sender!(), CancellationToken::new()).await?;
+        assert_eq!(report.restored, vec![test_dir.join("_conditions.txt")]);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(
+            report.conflicts[0].kind,
+            hide_journal::UnhideConflictKind::AlreadyExists
+        );
+        assert!(test_dir.join("hidden_by_user.hkx.mohidden").exists());
+        assert_eq!(HideJournal::load(dar_dir).await?.renames.len(), 1);
         Ok(())
     }
 
@@ -172,15 +305,72 @@ mod test {
         feature = "tracing",
         quick_tracing::try_init(file = "../logs/remove_oar.log", level = "ERROR")
     )]
-    async fn should_remove_oar_dir() -> Result<()> {
+    async fn should_remove_only_generated_oar_dir() -> Result<()> {
         let temp_dir = TempDir::new()?;
-        let test_dir = temp_dir
+        let oar_dir = temp_dir
+            .path()
+            .join("TestMod/meshes/actors/character/animations/OpenAnimationReplacer");
+        let generated = oar_dir.join("Generated");
+        let hand_made = oar_dir.join("HandMade/1000");
+        create_dir_all(&generated).await?;
+        create_dir_all(&hand_made).await?;
+        Manifest::new("DAR").write(&generated).await?;
+
+        let dry_run = RemoveOarOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let removed = remove_oar(
+            temp_dir.path(),
+            dry_run,
+            sender!(),
+            CancellationToken::new(),
+        );
+        assert_eq!(removed.await, Ok(vec![generated.clone()]));
+        assert!(generated.exists());
+
+        let options = RemoveOarOptions::default();
+        let removed = remove_oar(
+            temp_dir.path(),
+            options,
+            sender!(),
+            CancellationToken::new(),
+        );
+        assert_eq!(removed.await, Ok(vec![generated.clone()]));
+        assert!(!generated.exists());
+        assert!(hand_made.exists());
+
+        let force = RemoveOarOptions {
+            force: true,
+            ..Default::default()
+        };
+        let removed = remove_oar(temp_dir.path(), force, sender!(), CancellationToken::new());
+        assert_eq!(removed.await, Ok(vec![oar_dir.clone()]));
+        assert!(!oar_dir.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_remove_oar_dir_when_cancelled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let oar_dir_path = temp_dir
             .path()
             .join("TestMod/meshes/actors/character/animations/OpenAnimationReplacer/1000");
-        let oar_dir_path = temp_dir.path().join(test_dir);
         create_dir_all(&oar_dir_path).await?;
 
-        assert!(remove_oar(temp_dir.path(), sender!()).await.is_ok());
+        let cancel_token = CancellationToken::new();
+        cancel_token.cancel();
+        assert_eq!(
+            remove_oar(
+                temp_dir.path(),
+                RemoveOarOptions::default(),
+                sender!(),
+                cancel_token
+            )
+            .await,
+            Err(ConvertError::Cancelled)
+        );
+        assert!(oar_dir_path.exists());
         Ok(())
     }
 }
@@ -0,0 +1,392 @@
+//! Collapse the `Random()`-based DAR variant sections into OAR animation variants.
+//!
+//! A common DAR workaround to rotate between alternative animations is several priority dirs like
+//! ```txt
+//! 300: IsSneaking() AND Random(0.33)
+//! 200: IsSneaking() AND Random(0.5)
+//! 100: IsSneaking()
+//! ```
+//! If each section plays with the same probability and the rest of their configs are equal,
+//! they are merged into the highest priority one, with the motion files moved into
+//! `_variants_<animation name>` dirs(OAR picks one of them evenly).
+//!
+//! The written `config.json`s are handled as [`Value`], because reading them into `Oar` is lossy.
+use crate::error::Result;
+use crate::fs::converter::common::read_section_dirs;
+use crate::fs::section_writer::write_config_value;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Tolerance of the probabilities. e.g. `Random(0.33)` is regarded as 1/3.
+const TOLERANCE: f64 = 0.02;
+
+/// Result of [`collapse_variants`].
+#[derive(Debug, Default)]
+pub(super) struct Collapsed {
+    /// Number of the sections merged into another section
+    pub(super) merged: usize,
+    /// (section dir name, warning) for the groups that could not be merged
+    pub(super) warnings: Vec<(String, String)>,
+}
+
+/// A section of a namespace.
+struct Section {
+    dir: PathBuf,
+    config: Value,
+    priority: i64,
+    /// Conditions other than top-level `Random`
+    rest: Vec<Value>,
+    /// `config.json` without the name, the priority and the `Random`s. Sections are merged only if it's equal.
+    group_key: String,
+    /// Refers to the motion files of another section, so it can't be merged.
+    is_override: bool,
+    /// Probability that the `Random`s are all true, or the reason it is unknown
+    probability: core::result::Result<f64, String>,
+    has_random: bool,
+}
+
+/// Merge the sections of `name_space` that differ only by `Random` thresholds into animation variants.
+///
+/// # Errors
+/// Failed to read, move or write the files.
+pub(super) async fn collapse_variants(name_space: &Path) -> Result<Collapsed> {
+    let mut sections = vec![];
+    for dir in read_section_dirs(name_space).await? {
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("config.json")).await?)?;
+        sections.push(Section::new(dir, config));
+    }
+
+    // config without `Random`s -> sections
+    // All the sections are kept to check the priorities between a group.
+    let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, section) in sections.iter().enumerate() {
+        if !section.is_override {
+            groups.entry(&section.group_key).or_default().push(index);
+        }
+    }
+
+    let mut collapsed = Collapsed::default();
+    for mut group in groups.into_values() {
+        if group.len() < 2 || !group.iter().any(|&index| sections[index].has_random) {
+            continue;
+        }
+        group.sort_by_key(|&index| core::cmp::Reverse(sections[index].priority));
+        let group: Vec<_> = group.iter().map(|&index| &sections[index]).collect();
+
+        let files = match check_group(&group, &sections).await? {
+            Ok(files) => files,
+            Err(reason) => {
+                let names: Vec<_> = group.iter().map(|section| dir_name(&section.dir)).collect();
+                let warning = format!(
+                    "Sections {} differ only by Random, but were not merged into animation variants: {reason}",
+                    names.join(", ")
+                );
+                collapsed.warnings.push((names[0].clone(), warning));
+                continue;
+            }
+        };
+
+        merge(&group, &files).await?;
+        collapsed.merged += group.len() - 1;
+    }
+    Ok(collapsed)
+}
+
+impl Section {
+    fn new(dir: PathBuf, config: Value) -> Self {
+        let priority = config["priority"].as_i64().unwrap_or_default();
+        let (random, rest): (Vec<_>, Vec<_>) = config["conditions"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .cloned()
+            .partition(|condition| condition["condition"] == "Random");
+
+        let probability = random.iter().try_fold(1.0, |probability, random| {
+            Ok(probability * random_probability(random)?)
+        });
+        let mut behavior = config.clone();
+        if let Value::Object(fields) = &mut behavior {
+            fields.remove("name");
+            fields.remove("priority");
+            fields.insert("conditions".to_string(), Value::Array(rest.clone()));
+        }
+        Self {
+            dir,
+            priority,
+            rest,
+            group_key: behavior.to_string(),
+            is_override: config.get("overrideAnimationsFolder").is_some(),
+            config,
+            probability,
+            has_random: !random.is_empty(),
+        }
+    }
+}
+
+/// Probability that a `Random` condition is true.
+fn random_probability(random: &Value) -> core::result::Result<f64, String> {
+    let min = random["Random value"]["min"].as_f64().unwrap_or(0.0);
+    let max = random["Random value"]["max"].as_f64().unwrap_or(1.0);
+    let Some(value) = random["Numeric value"]["value"].as_f64() else {
+        return Err("Random is compared with a non-static value".to_string());
+    };
+    if max <= min {
+        return Err(format!("invalid Random range {min}..{max}"));
+    }
+
+    let less = ((value - min) / (max - min)).clamp(0.0, 1.0);
+    let probability = match random["Comparison"].as_str().unwrap_or("==") {
+        "<" | "<=" => less,
+        ">" | ">=" => 1.0 - less,
+        comparison => return Err(format!("Random is compared with `{comparison}`")),
+    };
+    Ok(match random["negated"].as_bool().unwrap_or(false) {
+        true => 1.0 - probability,
+        false => probability,
+    })
+}
+
+/// Check that `group`(by descending priority) is equivalent to even variants, and returns the motion files.
+async fn check_group(
+    group: &[&Section],
+    sections: &[Section],
+) -> Result<core::result::Result<Vec<PathBuf>, String>> {
+    // Probability that each section plays: it is reached and its `Random`s are true.
+    let mut remaining = 1.0;
+    let mut probabilities = vec![];
+    for section in group {
+        let probability = match &section.probability {
+            Ok(probability) => *probability,
+            Err(reason) => return Ok(Err(reason.clone())),
+        };
+        probabilities.push(remaining * probability);
+        remaining *= 1.0 - probability;
+    }
+    if remaining > TOLERANCE {
+        return Ok(Err(format!(
+            "none of them plays with the probability {remaining:.2}"
+        )));
+    }
+    let even = 1.0 / group.len() as f64;
+    if probabilities.iter().any(|p| (p - even).abs() > TOLERANCE) {
+        let probabilities: Vec<_> = probabilities.iter().map(|p| format!("{p:.2}")).collect();
+        return Ok(Err(format!(
+            "the probabilities {} are not even",
+            probabilities.join(", ")
+        )));
+    }
+
+    let (Some(highest), Some(lowest)) = (group.first(), group.last()) else {
+        return Ok(Err("empty group".to_string()));
+    };
+    if let Some(between) = sections.iter().find(|section| {
+        lowest.priority < section.priority
+            && section.priority < highest.priority
+            && !group.iter().any(|member| member.dir == section.dir)
+    }) {
+        return Ok(Err(format!(
+            "section {} has a priority between them",
+            dir_name(&between.dir)
+        )));
+    }
+
+    let files = motion_files(&highest.dir).await?;
+    if files.is_empty() {
+        return Ok(Err("no motion files".to_string()));
+    }
+    for section in &group[1..] {
+        if motion_files(&section.dir).await? != files {
+            return Ok(Err(format!(
+                "section {} has different motion files",
+                dir_name(&section.dir)
+            )));
+        }
+    }
+    Ok(Ok(files))
+}
+
+/// Move the motion files of `group` into `_variants_*` dirs of the first section, and remove the others.
+async fn merge(group: &[&Section], files: &[PathBuf]) -> Result<()> {
+    let Some(canonical) = group.first() else {
+        return Ok(());
+    };
+
+    for file in files {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        let variants_dir = canonical
+            .dir
+            .join(file.parent().unwrap_or_else(|| Path::new("")))
+            .join(format!("_variants_{stem}"));
+        fs::create_dir_all(&variants_dir).await?;
+
+        for section in group {
+            let mut variant = PathBuf::from(dir_name(&section.dir));
+            if let Some(extension) = file.extension() {
+                variant.set_extension(extension);
+            }
+            fs::rename(section.dir.join(file), variants_dir.join(variant)).await?;
+        }
+    }
+    for section in &group[1..] {
+        fs::remove_dir_all(&section.dir).await?;
+    }
+
+    let mut config = canonical.config.clone();
+    config["conditions"] = Value::Array(canonical.rest.clone());
+    write_config_value(&canonical.dir, &config).await
+}
+
+/// Motion files(all files except `config.json`) relative to `section_dir`, sorted.
+async fn motion_files(section_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    let mut dirs = vec![section_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(section_dir)
+                && relative != Path::new("config.json")
+            {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_dar2oar;
+    use oar_conditions::conditions::ConditionsConfig;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    async fn write_section(name_space: &Path, priority: i32, dar: &str) -> Result<()> {
+        let dir = name_space.join(priority.to_string());
+        fs::create_dir_all(dir.join("female")).await?;
+        let config = ConditionsConfig {
+            name: priority.to_string().into(),
+            priority,
+            conditions: parse_dar2oar("test", dar)?,
+            ..Default::default()
+        };
+        write_config_value(&dir, &serde_json::to_value(config)?).await?;
+        fs::write(dir.join("mt_idle.hkx"), priority.to_string()).await?;
+        fs::write(dir.join("female/mt_walk.hkx"), priority.to_string()).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_collapse_random_sections_into_variants() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path();
+        write_section(name_space, 300, "IsSneaking() AND\nRandom(0.33)").await?;
+        write_section(name_space, 200, "IsSneaking() AND\nRandom(0.5)").await?;
+        write_section(name_space, 100, "IsSneaking()").await?;
+
+        let collapsed = collapse_variants(name_space).await?;
+
+        assert_eq!(collapsed.merged, 2);
+        assert!(collapsed.warnings.is_empty(), "{:?}", collapsed.warnings);
+        assert!(!name_space.join("200").exists());
+        assert!(!name_space.join("100").exists());
+        for variant in ["100.hkx", "200.hkx", "300.hkx"] {
+            assert!(
+                name_space
+                    .join("300/_variants_mt_idle")
+                    .join(variant)
+                    .exists()
+            );
+            assert!(
+                name_space
+                    .join("300/female/_variants_mt_walk")
+                    .join(variant)
+                    .exists()
+            );
+        }
+        let config: Value =
+            serde_json::from_str(&fs::read_to_string(name_space.join("300/config.json")).await?)?;
+        assert_eq!(config["conditions"].as_array().map(Vec::len), Some(1));
+        assert_eq!(config["conditions"][0]["condition"], "IsSneaking");
+        Ok(())
+    }
+
+    /// Set `field` of the `config.json` of the section.
+    async fn set_field(name_space: &Path, priority: i32, field: &str, value: Value) -> Result<()> {
+        let dir = name_space.join(priority.to_string());
+        let mut config: Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("config.json")).await?)?;
+        config[field] = value;
+        write_config_value(&dir, &config).await
+    }
+
+    #[tokio::test]
+    async fn should_not_merge_over_override_sections() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path();
+        write_section(name_space, 300, "IsSneaking() AND\nRandom(0.5)").await?;
+        write_section(name_space, 200, "IsInCombat()").await?;
+        set_field(name_space, 200, "overrideAnimationsFolder", "../300".into()).await?;
+        write_section(name_space, 100, "IsSneaking()").await?;
+
+        let collapsed = collapse_variants(name_space).await?;
+
+        assert_eq!(collapsed.merged, 0);
+        assert_eq!(collapsed.warnings.len(), 1);
+        assert!(
+            collapsed.warnings[0]
+                .1
+                .contains("section 200 has a priority between them")
+        );
+        assert!(name_space.join("100/mt_idle.hkx").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_merge_sections_with_different_fields() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path();
+        write_section(name_space, 200, "IsSneaking() AND\nRandom(0.5)").await?;
+        write_section(name_space, 100, "IsSneaking()").await?;
+        set_field(name_space, 100, "interruptible", true.into()).await?;
+
+        let collapsed = collapse_variants(name_space).await?;
+
+        assert_eq!(collapsed.merged, 0);
+        assert!(collapsed.warnings.is_empty(), "{:?}", collapsed.warnings);
+        assert!(name_space.join("100/mt_idle.hkx").exists());
+        assert!(name_space.join("200/mt_idle.hkx").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_uneven_random_sections() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let name_space = temp_dir.path();
+        write_section(name_space, 200, "IsSneaking() AND\nRandom(0.8)").await?;
+        write_section(name_space, 100, "IsSneaking()").await?;
+
+        let collapsed = collapse_variants(name_space).await?;
+
+        assert_eq!(collapsed.merged, 0);
+        assert_eq!(collapsed.warnings.len(), 1);
+        assert!(collapsed.warnings[0].1.contains("not even"));
+        assert!(name_space.join("100/mt_idle.hkx").exists());
+        Ok(())
+    }
+}
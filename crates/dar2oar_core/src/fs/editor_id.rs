@@ -0,0 +1,32 @@
+//! Read the `EditorID` dictionary exported from xEdit.
+use crate::editor_id::EditorIdDictionary;
+use crate::error::{ConvertError, Result};
+use std::path::Path;
+use tokio::fs::read_to_string;
+
+/// Try to read the `EditorID` dictionary from path.
+///
+/// `.json` is parsed as JSON, others as CSV.
+///
+/// # Errors
+/// Path is not exist, or the contents are invalid.
+pub async fn read_editor_id_dictionary<P>(path: P) -> Result<EditorIdDictionary>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    if !path.exists() {
+        return Err(ConvertError::NonExistPath {
+            path: path.to_path_buf(),
+        });
+    };
+
+    let contents = read_to_string(path).await?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    match is_json {
+        true => EditorIdDictionary::parse_json(path, &contents),
+        false => EditorIdDictionary::parse_csv(path, &contents),
+    }
+}
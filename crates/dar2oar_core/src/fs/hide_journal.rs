@@ -0,0 +1,171 @@
+//! Journal of the renames made by `hide_dar`
+//!
+//! The converter records every `<file>` -> `<file>.mohidden` rename in
+//! `<DAR dir>/.dar2oar_hide_journal.json`, so that
+//! [`unhide_dar_from_journal`](crate::unhide_dar_from_journal) can reverse exactly those renames
+//! and leave the files the user hid on purpose alone.
+use crate::error::{ConvertError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio_util::sync::CancellationToken;
+
+/// File name of the journal placed in the DAR dir passed to the converter.
+pub const JOURNAL_FILE: &str = ".dar2oar_hide_journal.json";
+
+/// A single rename made by `hide_dar`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HideRename {
+    /// Original path (relative to the DAR dir)
+    pub from: PathBuf,
+    /// Hidden path (relative to the DAR dir)
+    pub to: PathBuf,
+}
+
+/// Renames made by `hide_dar`, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HideJournal {
+    /// Renames made by `hide_dar`
+    pub renames: Vec<HideRename>,
+}
+
+impl HideJournal {
+    /// Path of the journal for `dar_dir`.
+    pub fn path(dar_dir: impl AsRef<Path>) -> PathBuf {
+        dar_dir.as_ref().join(JOURNAL_FILE)
+    }
+
+    /// Read the journal in `dar_dir`. Returns an empty journal if there is none yet.
+    ///
+    /// # Errors
+    /// Failed to read or parse the journal.
+    pub async fn load(dar_dir: impl AsRef<Path>) -> Result<Self> {
+        let path = Self::path(dar_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(path).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Write the journal into `dar_dir`, or remove the file if the journal is empty.
+    ///
+    /// # Errors
+    /// Failed to write or remove the journal.
+    pub async fn save(&self, dar_dir: impl AsRef<Path>) -> Result<()> {
+        let path = Self::path(dar_dir);
+        if self.renames.is_empty() {
+            if path.exists() {
+                fs::remove_file(path).await?;
+            }
+            return Ok(());
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).await?;
+        Ok(())
+    }
+
+    /// Record a rename, storing the paths relative to `dar_dir` where possible.
+    pub(crate) fn record(&mut self, dar_dir: &Path, from: &Path, to: &Path) {
+        let relative = |path: &Path| path.strip_prefix(dar_dir).unwrap_or(path).to_path_buf();
+        self.renames.push(HideRename {
+            from: relative(from),
+            to: relative(to),
+        });
+    }
+}
+
+/// Why a journaled rename could not be reversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnhideConflictKind {
+    /// The unhidden path already exists.
+    AlreadyExists,
+    /// The hidden path no longer exists.
+    MissingHidden,
+}
+
+/// A journaled rename that was left as is.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnhideConflict {
+    /// Hidden path
+    pub hidden: PathBuf,
+    /// Path it would have been renamed to
+    pub unhidden: PathBuf,
+    /// Reason
+    pub kind: UnhideConflictKind,
+}
+
+/// Result of [`unhide_dar_from_journal`](crate::unhide_dar_from_journal).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UnhideReport {
+    /// Paths restored to their original names
+    pub restored: Vec<PathBuf>,
+    /// Renames that were not reversed. They stay in the journal.
+    pub conflicts: Vec<UnhideConflict>,
+}
+
+/// Reverse the renames in `journal`, keeping the conflicting ones in it.
+///
+/// # Errors
+/// - Failed to rename a file
+/// - [`ConvertError::Cancelled`] if `cancel_token` is cancelled. The journal then keeps the rest.
+pub(crate) async fn reverse(
+    dar_dir: &Path,
+    journal: &mut HideJournal,
+    mut progress_fn: impl FnMut(usize),
+    cancel_token: &CancellationToken,
+) -> Result<UnhideReport> {
+    let mut report = UnhideReport::default();
+    let mut kept = vec![];
+    let mut renames = core::mem::take(&mut journal.renames).into_iter();
+
+    for (idx, rename) in renames.by_ref().enumerate() {
+        if cancel_token.is_cancelled() {
+            kept.push(rename);
+            break;
+        }
+
+        let hidden = dar_dir.join(&rename.to);
+        let unhidden = dar_dir.join(&rename.from);
+        let conflict = if !hidden.exists() {
+            Some(UnhideConflictKind::MissingHidden)
+        } else if unhidden.exists() {
+            Some(UnhideConflictKind::AlreadyExists)
+        } else {
+            None
+        };
+
+        match conflict {
+            Some(kind) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Skip unhide {hidden:?}: {kind:?}");
+                report.conflicts.push(UnhideConflict {
+                    hidden,
+                    unhidden,
+                    kind,
+                });
+                kept.push(rename);
+            }
+            None => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("Rename {idx}th:\n- From: {hidden:?}\n-   To: {unhidden:?}\n");
+                if let Err(err) = fs::rename(&hidden, &unhidden).await {
+                    kept.push(rename);
+                    kept.extend(renames);
+                    journal.renames = kept;
+                    return Err(err.into());
+                }
+                report.restored.push(unhidden);
+            }
+        }
+        progress_fn(idx);
+    }
+
+    kept.extend(renames);
+    journal.renames = kept;
+
+    if cancel_token.is_cancelled() {
+        return Err(ConvertError::Cancelled);
+    }
+    Ok(report)
+}
@@ -0,0 +1,79 @@
+//! Manifest written into each OAR namespace generated by dar2oar
+//!
+//! It allows [`remove_oar`](crate::remove_oar) to tell generated namespaces apart from hand-made OAR mods.
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// File name of the manifest placed in an OAR namespace.
+pub const MANIFEST_FILE: &str = ".dar2oar.json";
+
+/// Value of [`Manifest::generator`] written by this crate.
+const GENERATOR: &str = "dar2oar";
+
+/// Records that an OAR namespace was generated by dar2oar.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Always `"dar2oar"`
+    pub generator: String,
+    /// Version of `dar2oar_core` used for the conversion
+    pub version: String,
+    /// DAR dir converted into this namespace
+    pub source: PathBuf,
+}
+
+impl Manifest {
+    /// Create a manifest for a namespace converted from `source`.
+    pub fn new(source: impl Into<PathBuf>) -> Self {
+        Self {
+            generator: GENERATOR.into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            source: source.into(),
+        }
+    }
+
+    /// Write the manifest into `oar_name_space`.
+    ///
+    /// # Errors
+    /// Failed to write the file.
+    pub async fn write(&self, oar_name_space: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(oar_name_space.as_ref().join(MANIFEST_FILE), json).await?;
+        Ok(())
+    }
+
+    /// Read the manifest in `oar_name_space`.
+    ///
+    /// Returns `None` if there is no manifest or it was not written by dar2oar.
+    pub async fn read(oar_name_space: impl AsRef<Path>) -> Option<Self> {
+        let json = fs::read_to_string(oar_name_space.as_ref().join(MANIFEST_FILE))
+            .await
+            .ok()?;
+        serde_json::from_str::<Self>(&json)
+            .ok()
+            .filter(|manifest| manifest.generator == GENERATOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    #[tokio::test]
+    async fn should_read_written_manifest() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let manifest = Manifest::new("path/to/DAR");
+        manifest.write(temp_dir.path()).await?;
+        assert_eq!(Manifest::read(temp_dir.path()).await, Some(manifest));
+
+        fs::write(
+            temp_dir.path().join(MANIFEST_FILE),
+            r#"{ "generator": "other", "version": "1.0.0", "source": "" }"#,
+        )
+        .await?;
+        assert_eq!(Manifest::read(temp_dir.path()).await, None);
+        Ok(())
+    }
+}
@@ -3,4 +3,7 @@ mod path_changer;
 mod section_writer;
 
 pub mod converter;
+pub mod editor_id;
+pub mod hide_journal;
+pub mod manifest;
 pub mod mapping_table;
@@ -79,7 +79,9 @@ pub fn parse_dar_path(path: impl AsRef<Path>) -> Result<ParsedPath> {
     let dar_pos = path
         .iter()
         .position(|os_str| os_str.eq_ignore_ascii_case(OsStr::new("DynamicAnimationReplacer")))
-        .ok_or(ConvertError::NotFoundDarDir)?;
+        .ok_or_else(|| ConvertError::NotFoundDarDir {
+            path: path.to_path_buf(),
+        })?;
 
     // ActorBase pattern only
     let esp_dir = paths.get(dar_pos + 1).and_then(|name| {
@@ -112,7 +114,9 @@ pub fn parse_dar_path(path: impl AsRef<Path>) -> Result<ParsedPath> {
             oar.push("OpenAnimationReplacer");
             (dar, oar)
         })
-        .ok_or(ConvertError::NotFoundDarDir)?;
+        .ok_or_else(|| ConvertError::NotFoundDarDir {
+            path: path.to_path_buf(),
+        })?;
 
     let mod_name = path
         .iter()
@@ -137,7 +141,9 @@ pub fn parse_dar_path(path: impl AsRef<Path>) -> Result<ParsedPath> {
         path.iter()
             .position(|os_str| os_str == OsStr::new("_CustomConditions"))
             .and_then(|idx| paths.get(idx + 1).and_then(|priority| priority.to_str()))
-            .ok_or(ConvertError::NotFoundPriorityDir)?
+            .ok_or_else(|| ConvertError::NotFoundPriorityDir {
+                path: path.to_path_buf(),
+            })?
     };
 
     let priority = priority.parse::<i32>().map_err(|_err| priority.into());
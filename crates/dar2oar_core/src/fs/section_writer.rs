@@ -47,10 +47,23 @@ where
     }
 
     let config_json = MainConfig {
-        name: mod_name,
-        author: author.unwrap_or_default(),
-        description: description.unwrap_or_default(),
+        name: mod_name.into(),
+        author: author.unwrap_or_default().into(),
+        description: description.unwrap_or_default().into(),
+        ..Default::default()
     };
 
     write_json_to(target_file, &config_json).await
 }
+
+/// Write a `config.json` read as [`serde_json::Value`] back to `dir`.
+///
+/// # Why not [`ConditionsConfig`]?
+/// [`Oar`](oar_conditions::conditions::Oar) is untagged, so reading an OAR json into it is lossy
+/// (e.g. `IsActorBase` would be read as a generic `Condition`).
+pub(crate) async fn write_config_value<P>(dir: P, config_json: &serde_json::Value) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    write_json_to(dir.as_ref().join("config.json"), config_json).await
+}
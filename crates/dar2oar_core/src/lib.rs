@@ -8,8 +8,7 @@
 //! ### Async with non Progress report.
 //!
 //! ```no_run
-//! use dar2oar_core::{convert_dar_to_oar, ConvertOptions, error::Result, get_mapping_table};
-//! use tracing::{level_filters::LevelFilter, subscriber::DefaultGuard};
+//! use dar2oar_core::{convert_dar_to_oar, CancellationToken, Closure, ConvertOptions, error::Result, read_mapping_table};
 //!
 //! const DAR_DIR: &str = "../test/data/UNDERDOG Animations";
 //! const TABLE_PATH: &str = "../test/settings/UnderDog Animations_v1.9.6_mapping_table.txt";
@@ -18,7 +17,7 @@
 //! async fn create_options() -> Result<ConvertOptions> {
 //!     Ok(ConvertOptions {
 //!         dar_dir: DAR_DIR.into(),
-//!         section_table: read_mapping_table(TABLE_PATH).await,
+//!         section_table: Some(read_mapping_table(TABLE_PATH).await?),
 //!         ..Default::default()
 //!     })
 //! }
@@ -26,7 +25,8 @@
 //! #[tokio::main]
 //! #[quick_tracing::try_init(file = "../convert.log", level = "DEBUG")]
 //! async fn main() -> Result<()> {
-//!     convert_dar_to_oar(create_options().await?, |_| {}).await?;
+//!     let report = convert_dar_to_oar(create_options().await?, Closure::default_event, CancellationToken::new()).await?;
+//!     println!("{report:?}");
 //!     Ok(())
 //! }
 //! ```
@@ -34,7 +34,7 @@
 //! ### Parallel Async with Progress report.
 //!
 //! ```no_run
-//! use dar2oar_core::{convert_dar_to_oar, ConvertOptions, error::Result, read_mapping_table};
+//! use dar2oar_core::{convert_dar_to_oar, CancellationToken, ConvertOptions, ProgressEvent, error::Result, read_mapping_table};
 //!
 //! const DAR_DIR: &str = "../test/data/UNDERDOG Animations";
 //! const TABLE_PATH: &str = "../test/settings/UnderDog Animations_v1.9.6_mapping_table.txt";
@@ -43,7 +43,7 @@
 //! async fn create_options() -> Result<ConvertOptions> {
 //!     Ok(ConvertOptions {
 //!         dar_dir: DAR_DIR.into(),
-//!         section_table: read_mapping_table(TABLE_PATH).await,
+//!         section_table: Some(read_mapping_table(TABLE_PATH).await?),
 //!         run_parallel: true,
 //!         ..Default::default()
 //!     })
@@ -55,38 +55,60 @@
 //!     let (tx, mut rx) = tokio::sync::mpsc::channel(500);
 //!
 //!     // Send function for progress reporting.
-//!     let sender = move |idx: usize| {
+//!     let sender = move |event: ProgressEvent| {
 //!         let tx = tx.clone();
 //!         tokio::spawn(async move {
-//!             tx.send(idx).await.unwrap_or_default();
+//!             tx.send(event).await.unwrap_or_default();
 //!         });
 //!     };
 //!
 //!     // Spawn conversion process with progress reporting.
-//!     tokio::spawn(convert_dar_to_oar(create_options().await?, sender));
+//!     // Call `cancel_token.cancel()` from elsewhere to stop the conversion.
+//!     let cancel_token = CancellationToken::new();
+//!     tokio::spawn(convert_dar_to_oar(create_options().await?, sender, cancel_token.clone()));
 //!
-//!     let mut walk_len = 0usize;
+//!     let mut total = 0usize;
+//!     let mut done = 0usize;
 //!     // Receive progress updates and print messages.
-//!     while let Some(idx) = rx.recv().await {
-//!          match walk_len == 0 {
-//!             true => walk_len = idx, // NOTE: 1st received index is length.
-//!             false => println!("[recv] Converted: {}/{}", idx + 1, walk_len),
-//!          }
+//!     while let Some(event) = rx.recv().await {
+//!         match event {
+//!             ProgressEvent::Started { total: len } => total = len,
+//!             ProgressEvent::FileDone { .. } => {
+//!                 done += 1;
+//!                 println!("[recv] Converted: {done}/{total}");
+//!             }
+//!             ProgressEvent::Warning { path, message } => println!("[warn] {path:?}: {message}"),
+//!             ProgressEvent::Finished { report } => println!("{report:?}"),
+//!             ProgressEvent::FileStarted { .. } => {}
+//!         }
 //!     }
 //!
 //!     Ok(())
 //! }
 //!
 //! ```
+mod forms;
 mod parser;
 
+pub mod editor_id;
 pub mod error;
 pub mod fs;
 
+pub use crate::editor_id::EditorIdDictionary;
+pub use crate::fs::converter::progress::{
+    ConvertReport, FileKind, ProgressEvent, ProgressReporter,
+};
+pub use crate::fs::converter::section_naming::SectionNaming;
 #[doc = include_str!("../readme.md")]
-pub use crate::fs::converter::support_cmd::{remove_oar, unhide_dar};
+pub use crate::fs::converter::support_cmd::{
+    RemoveOarOptions, remove_oar, unhide_dar, unhide_dar_from_journal,
+};
 pub use crate::fs::converter::{Closure, ConvertOptions, convert_dar_to_oar};
+pub use crate::fs::editor_id::read_editor_id_dictionary;
 pub use crate::fs::mapping_table::read_mapping_table;
+pub use oar_conditions::version::OarVersion;
+pub use oar_values::FormIdStyle;
+pub use tokio_util::sync::CancellationToken;
 
 #[cfg(test)]
 extern crate criterion as _; // Needed for cargo bench.
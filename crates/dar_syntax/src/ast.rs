@@ -252,6 +252,15 @@ pub enum ActorArgs<'i> {
 }
 
 impl<'i> ActorArgs<'i> {
+    /// The actor value side of the arguments.
+    #[inline]
+    pub const fn actor_value(&self) -> &ActorValue {
+        match self {
+            ActorArgs::ActorFirst { value_a, .. } => value_a,
+            ActorArgs::GlobalFirst { value_b, .. } => value_b,
+        }
+    }
+
     /// Cast to (value_a, value_b)
     #[inline]
     pub fn into_numeric_values(self) -> (NumericValue<'i>, NumericValue<'i>) {
@@ -0,0 +1,331 @@
+//! Owned rewriting of DAR syntax trees.
+//!
+//! Each method of [`Fold`] rebuilds the node from the folded children by default.
+//! To keep folding after overriding a method, call the free function of the same name.
+use crate::ast::{ActorArgs, Dar, Expression, Function, GlobalVariable};
+use oar_values::{ActorValue, Direction, FormID, PluginValue, StaticValue, WeaponType};
+
+/// Owned rewriter of DAR syntax trees and their values.
+pub trait Fold<'i> {
+    /// `AND`, `OR` or an expression.
+    fn fold_dar(&mut self, node: Dar<'i>) -> Dar<'i> {
+        fold_dar(self, node)
+    }
+
+    /// A line of a function call.
+    fn fold_expression(&mut self, node: Expression<'i>) -> Expression<'i> {
+        fold_expression(self, node)
+    }
+
+    /// A function and its arguments.
+    fn fold_function(&mut self, node: Function<'i>) -> Function<'i> {
+        fold_function(self, node)
+    }
+
+    /// Arguments of the `IsActorValue*` functions.
+    fn fold_actor_args(&mut self, node: ActorArgs<'i>) -> ActorArgs<'i> {
+        fold_actor_args(self, node)
+    }
+
+    /// A plugin value or a number.
+    fn fold_global_variable(&mut self, node: GlobalVariable<'i>) -> GlobalVariable<'i> {
+        fold_global_variable(self, node)
+    }
+
+    /// Actor value of [`ActorArgs`].
+    fn fold_actor_value(&mut self, node: ActorValue) -> ActorValue {
+        node
+    }
+
+    /// Argument of `IsMovementDirection`.
+    fn fold_direction(&mut self, node: Direction) -> Direction {
+        node
+    }
+
+    /// FormID of a plugin value.
+    fn fold_form_id(&mut self, node: FormID<'i>) -> FormID<'i> {
+        node
+    }
+
+    /// A pair of a plugin name and a FormID.
+    fn fold_plugin_value(&mut self, node: PluginValue<'i>) -> PluginValue<'i> {
+        fold_plugin_value(self, node)
+    }
+
+    /// A number.
+    fn fold_static_value(&mut self, node: StaticValue) -> StaticValue {
+        node
+    }
+
+    /// Argument of `IsEquipped*Type`.
+    fn fold_weapon_type(&mut self, node: WeaponType) -> WeaponType {
+        node
+    }
+}
+
+/// Fold the children of [`Dar`].
+pub fn fold_dar<'i, F>(f: &mut F, node: Dar<'i>) -> Dar<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    match node {
+        Dar::And(conditions) => Dar::And(conditions.into_iter().map(|c| f.fold_dar(c)).collect()),
+        Dar::Or(conditions) => Dar::Or(conditions.into_iter().map(|c| f.fold_dar(c)).collect()),
+        Dar::Exp(expression) => Dar::Exp(f.fold_expression(expression)),
+    }
+}
+
+/// Fold the function of [`Expression`].
+pub fn fold_expression<'i, F>(f: &mut F, node: Expression<'i>) -> Expression<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    Expression {
+        function: f.fold_function(node.function),
+        negated: node.negated,
+    }
+}
+
+/// Fold the arguments of [`Function`].
+pub fn fold_function<'i, F>(f: &mut F, node: Function<'i>) -> Function<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    match node {
+        Function::CurrentGameTimeLessThan { value } => Function::CurrentGameTimeLessThan {
+            value: f.fold_static_value(value),
+        },
+        Function::CurrentWeather { weather } => Function::CurrentWeather {
+            weather: f.fold_plugin_value(weather),
+        },
+        Function::IsClass { class } => Function::IsClass {
+            class: f.fold_plugin_value(class),
+        },
+        Function::IsCombatStyle { combat_style } => Function::IsCombatStyle {
+            combat_style: f.fold_plugin_value(combat_style),
+        },
+        Function::IsActorValueEqualTo(args) => {
+            Function::IsActorValueEqualTo(f.fold_actor_args(args))
+        }
+        Function::IsActorValueLessThan(args) => {
+            Function::IsActorValueLessThan(f.fold_actor_args(args))
+        }
+        Function::IsActorValueBaseLessThan(args) => {
+            Function::IsActorValueBaseLessThan(f.fold_actor_args(args))
+        }
+        Function::IsActorValueMaxEqualTo(args) => {
+            Function::IsActorValueMaxEqualTo(f.fold_actor_args(args))
+        }
+        Function::IsActorValueMaxLessThan(args) => {
+            Function::IsActorValueMaxLessThan(f.fold_actor_args(args))
+        }
+        Function::IsActorValuePercentageEqualTo(args) => {
+            Function::IsActorValuePercentageEqualTo(f.fold_actor_args(args))
+        }
+        Function::IsActorValuePercentageLessThan(args) => {
+            Function::IsActorValuePercentageLessThan(f.fold_actor_args(args))
+        }
+        Function::IsActorBase { actor_base } => Function::IsActorBase {
+            actor_base: f.fold_plugin_value(actor_base),
+        },
+        Function::IsEquipped { form, is_left } => Function::IsEquipped {
+            form: f.fold_plugin_value(form),
+            is_left,
+        },
+        Function::IsEquippedType { value, is_left } => Function::IsEquippedType {
+            value: f.fold_weapon_type(value),
+            is_left,
+        },
+        Function::IsEquippedHasKeyword { keyword, is_left } => Function::IsEquippedHasKeyword {
+            keyword: f.fold_plugin_value(keyword),
+            is_left,
+        },
+        Function::IsEquippedShout { shout } => Function::IsEquippedShout {
+            shout: f.fold_plugin_value(shout),
+        },
+        Function::IsInFaction { faction } => Function::IsInFaction {
+            faction: f.fold_plugin_value(faction),
+        },
+        Function::IsFactionRankEqualTo { rank, faction } => Function::IsFactionRankEqualTo {
+            rank: f.fold_global_variable(rank),
+            faction: f.fold_plugin_value(faction),
+        },
+        Function::IsFactionRankLessThan { rank, faction } => Function::IsFactionRankLessThan {
+            rank: f.fold_global_variable(rank),
+            faction: f.fold_plugin_value(faction),
+        },
+        Function::IsInLocation { location } => Function::IsInLocation {
+            location: f.fold_plugin_value(location),
+        },
+        Function::IsLevelLessThan { level } => Function::IsLevelLessThan {
+            level: f.fold_static_value(level),
+        },
+        Function::IsParentCell { cell } => Function::IsParentCell {
+            cell: f.fold_plugin_value(cell),
+        },
+        Function::IsMovementDirection { direction } => Function::IsMovementDirection {
+            direction: f.fold_direction(direction),
+        },
+        Function::IsRace { race } => Function::IsRace {
+            race: f.fold_plugin_value(race),
+        },
+        Function::IsVoiceType { voice_type } => Function::IsVoiceType {
+            voice_type: f.fold_plugin_value(voice_type),
+        },
+        Function::IsWorldSpace { world_space } => Function::IsWorldSpace {
+            world_space: f.fold_plugin_value(world_space),
+        },
+        Function::IsWorn { form } => Function::IsWorn {
+            form: f.fold_plugin_value(form),
+        },
+        Function::IsWornHasKeyword { keyword } => Function::IsWornHasKeyword {
+            keyword: f.fold_plugin_value(keyword),
+        },
+        Function::HasKeyword { keyword } => Function::HasKeyword {
+            keyword: f.fold_plugin_value(keyword),
+        },
+        Function::HasPerk { perk } => Function::HasPerk {
+            perk: f.fold_plugin_value(perk),
+        },
+        Function::HasSpell { spell } => Function::HasSpell {
+            spell: f.fold_plugin_value(spell),
+        },
+        Function::HasMagicEffect { magic_effect } => Function::HasMagicEffect {
+            magic_effect: f.fold_plugin_value(magic_effect),
+        },
+        Function::HasMagicEffectWithKeyword { keyword } => Function::HasMagicEffectWithKeyword {
+            keyword: f.fold_plugin_value(keyword),
+        },
+        Function::HasRefType { location_ref_type } => Function::HasRefType {
+            location_ref_type: f.fold_plugin_value(location_ref_type),
+        },
+        Function::Random { value } => Function::Random {
+            value: f.fold_static_value(value),
+        },
+        Function::ValueEqualTo { value_a, value_b } => Function::ValueEqualTo {
+            value_a: f.fold_global_variable(value_a),
+            value_b: f.fold_global_variable(value_b),
+        },
+        Function::ValueLessThan { value_a, value_b } => Function::ValueLessThan {
+            value_a: f.fold_global_variable(value_a),
+            value_b: f.fold_global_variable(value_b),
+        },
+        function @ (Function::IsFemale
+        | Function::IsChild
+        | Function::IsPlayerTeammate
+        | Function::IsInInterior
+        | Function::IsUnique
+        | Function::IsAttacking
+        | Function::IsRunning
+        | Function::IsSneaking
+        | Function::IsSprinting
+        | Function::IsInAir
+        | Function::IsInCombat
+        | Function::IsWeaponDrawn) => function,
+    }
+}
+
+/// Fold the arguments of [`ActorArgs`].
+pub fn fold_actor_args<'i, F>(f: &mut F, node: ActorArgs<'i>) -> ActorArgs<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    match node {
+        ActorArgs::ActorFirst { value_a, value_b } => ActorArgs::ActorFirst {
+            value_a: f.fold_actor_value(value_a),
+            value_b: f.fold_global_variable(value_b),
+        },
+        ActorArgs::GlobalFirst { value_a, value_b } => ActorArgs::GlobalFirst {
+            value_a: f.fold_global_variable(value_a),
+            value_b: f.fold_actor_value(value_b),
+        },
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn fold_global_variable<'i, F>(f: &mut F, node: GlobalVariable<'i>) -> GlobalVariable<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    match node {
+        GlobalVariable::Plugin(node) => GlobalVariable::Plugin(f.fold_plugin_value(node)),
+        GlobalVariable::StaticValue(node) => GlobalVariable::StaticValue(f.fold_static_value(node)),
+    }
+}
+
+/// Fold the FormID of [`PluginValue`].
+pub fn fold_plugin_value<'i, F>(f: &mut F, node: PluginValue<'i>) -> PluginValue<'i>
+where
+    F: Fold<'i> + ?Sized,
+{
+    PluginValue {
+        form_id: f.fold_form_id(node.form_id),
+        ..node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_dar_syntax;
+    use pretty_assertions::assert_eq;
+
+    /// Negate `IsFemale`, rename `Old.esp` and double the numbers.
+    struct Rewrite;
+
+    impl<'i> Fold<'i> for Rewrite {
+        fn fold_expression(&mut self, node: Expression<'i>) -> Expression<'i> {
+            let negated = node.negated ^ matches!(node.function, Function::IsFemale);
+            Expression {
+                negated,
+                ..fold_expression(self, node)
+            }
+        }
+
+        fn fold_plugin_value(&mut self, node: PluginValue<'i>) -> PluginValue<'i> {
+            let node = fold_plugin_value(self, node);
+            if node.plugin_name.eq_ignore_ascii_case("old.esp") {
+                return PluginValue {
+                    plugin_name: "New.esp".into(),
+                    ..node
+                };
+            }
+            node
+        }
+
+        fn fold_static_value(&mut self, node: StaticValue) -> StaticValue {
+            StaticValue {
+                value: node.value * 2.0,
+            }
+        }
+    }
+
+    #[test]
+    fn should_fold_nested_and_or() {
+        let input = r#"IsFemale AND
+IsLevelLessThan(10) OR
+NOT IsFemale OR
+IsInFaction("Old.esp" | 0x801) AND
+IsFactionRankEqualTo(1, "old.esp" | 0x802) OR
+IsWorn("Skyrim.esm" | 0x803)"#;
+        let expected = r#"NOT IsFemale AND
+IsLevelLessThan(20) OR
+IsFemale OR
+IsInFaction("New.esp" | 0x801) AND
+IsFactionRankEqualTo(2, "New.esp" | 0x802) OR
+IsWorn("Skyrim.esm" | 0x803)"#;
+
+        let (Ok(input), Ok(expected)) = (parse_dar_syntax(input), parse_dar_syntax(expected))
+        else {
+            panic!("invalid DAR syntax");
+        };
+        let Dar::And(conditions) = &input else {
+            panic!("expected AND: {input:?}");
+        };
+        assert!(
+            conditions.iter().any(|c| matches!(c, Dar::Or(_))),
+            "{input:?}"
+        );
+        assert_eq!(Rewrite.fold_dar(input), expected);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod ast;
+pub mod fold;
 mod parser;
+pub mod visit;
+pub mod visit_mut;
 
 pub use winnow_ext::ReadableError;
 
@@ -9,7 +9,7 @@ use super::{
     string::string,
 };
 
-use oar_values::{ActorValue, ActorValueType, PluginValue, WeaponType};
+use oar_values::{ActorValue, ActorValueIndex, ActorValueType, PluginValue, WeaponType};
 use winnow::{
     Parser,
     ascii::float,
@@ -74,8 +74,8 @@ where
         alt((
             seq! {
                 ActorArgs::ActorFirst {
-                    value_a: static_value.map(|v| ActorValue {
-                        actor_value: v.value as i64,
+                    value_a: actor_value_index.map(|actor_value| ActorValue {
+                        actor_value,
                         actor_value_type,
                     }),
                     _: delimited_multispace0(","),
@@ -86,8 +86,8 @@ where
                 ActorArgs::GlobalFirst {
                     value_a: global_variable,
                     _: delimited_multispace0(","),
-                    value_b: static_value.map(|v| ActorValue {
-                        actor_value: v.value as i64,
+                    value_b: actor_value_index.map(|actor_value| ActorValue {
+                        actor_value,
                         actor_value_type,
                     }),
                 }
@@ -101,6 +101,15 @@ where
     }
 }
 
+/// Parse an actor value index(e.g. `24`). Fractions and out of range values are rejected.
+fn actor_value_index(input: &mut &str) -> ModalResult<ActorValueIndex> {
+    float
+        .verify_map(|value: f64| ActorValueIndex::try_from(value).ok())
+        .context(Label("ActorValue"))
+        .context(Expected(Description("actor value index: integer 0..=163")))
+        .parse_next(input)
+}
+
 pub(crate) struct FactionArgs<'i> {
     pub(crate) faction: PluginValue<'i>,
     pub(crate) rank: GlobalVariable<'i>,
@@ -299,9 +299,12 @@ where
 
 #[cfg(test)]
 mod tests {
-    use oar_values::StaticValue;
+    use oar_values::{ActorValue, ActorValueIndex, ActorValueType, StaticValue};
 
-    use crate::{ast::GlobalVariable, parse_assert};
+    use crate::{
+        ast::{ActorArgs, GlobalVariable},
+        parse_assert,
+    };
 
     use super::*;
 
@@ -315,4 +318,28 @@ mod tests {
 
         parse_assert!(function(input), expected);
     }
+
+    #[test]
+    fn should_parse_actor_value_index() {
+        let input = r#"IsActorValueLessThan(24, 0.5)"#;
+        let expected = Function::IsActorValueLessThan(ActorArgs::ActorFirst {
+            value_a: ActorValue {
+                actor_value: ActorValueIndex::Health,
+                actor_value_type: ActorValueType::ActorValue,
+            },
+            value_b: GlobalVariable::StaticValue(StaticValue { value: 0.5 }),
+        });
+
+        parse_assert!(function(input), expected);
+    }
+
+    #[test]
+    fn should_reject_invalid_actor_value_index() {
+        for input in [
+            "IsActorValueLessThan(24.5, 0.5)",
+            "IsActorValueLessThan(164, 0.5)",
+        ] {
+            assert!(function.parse(input).is_err(), "{input}");
+        }
+    }
 }
@@ -0,0 +1,196 @@
+//! Read-only traversal of DAR syntax trees.
+//!
+//! Each method of [`Visit`] walks into the children by default.
+//! To keep walking after overriding a method, call the free function of the same name.
+//! ```
+//! use dar_syntax::visit::{self, Visit};
+//! use oar_values::FormID;
+//!
+//! struct FormIds<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visit<'ast> for FormIds<'ast> {
+//!     fn visit_form_id(&mut self, node: &'ast FormID<'_>) {
+//!         self.0.push(node.as_str());
+//!     }
+//! }
+//!
+//! let input = r#"IsActorBase("Skyrim.esm" | 0x7) OR IsRace("Skyrim.esm" | 0x13746)"#;
+//! let dar = dar_syntax::parse_dar_syntax(input)?;
+//! let mut form_ids = FormIds(vec![]);
+//! form_ids.visit_dar(&dar);
+//! assert_eq!(form_ids.0, ["7", "13746"]);
+//! # Ok::<(), dar_syntax::ReadableError>(())
+//! ```
+use crate::ast::{ActorArgs, Dar, Expression, Function, GlobalVariable};
+use oar_values::{ActorValue, Direction, FormID, PluginValue, StaticValue, WeaponType};
+
+/// Read-only visitor of DAR syntax trees and their values.
+pub trait Visit<'ast> {
+    /// `AND`, `OR` or an expression.
+    fn visit_dar(&mut self, node: &'ast Dar<'_>) {
+        visit_dar(self, node);
+    }
+
+    /// A line of a function call.
+    fn visit_expression(&mut self, node: &'ast Expression<'_>) {
+        visit_expression(self, node);
+    }
+
+    /// A function and its arguments.
+    fn visit_function(&mut self, node: &'ast Function<'_>) {
+        visit_function(self, node);
+    }
+
+    /// Arguments of the `IsActorValue*` functions.
+    fn visit_actor_args(&mut self, node: &'ast ActorArgs<'_>) {
+        visit_actor_args(self, node);
+    }
+
+    /// A plugin value or a number.
+    fn visit_global_variable(&mut self, node: &'ast GlobalVariable<'_>) {
+        visit_global_variable(self, node);
+    }
+
+    /// Actor value of [`ActorArgs`].
+    fn visit_actor_value(&mut self, _node: &'ast ActorValue) {}
+
+    /// Argument of `IsMovementDirection`.
+    fn visit_direction(&mut self, _node: &'ast Direction) {}
+
+    /// FormID of a plugin value.
+    fn visit_form_id(&mut self, _node: &'ast FormID<'_>) {}
+
+    /// A pair of a plugin name and a FormID.
+    fn visit_plugin_value(&mut self, node: &'ast PluginValue<'_>) {
+        visit_plugin_value(self, node);
+    }
+
+    /// A number.
+    fn visit_static_value(&mut self, _node: &'ast StaticValue) {}
+
+    /// Argument of `IsEquipped*Type`.
+    fn visit_weapon_type(&mut self, _node: &'ast WeaponType) {}
+}
+
+/// Visit the children of [`Dar`].
+pub fn visit_dar<'ast, V>(v: &mut V, node: &'ast Dar<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Dar::And(conditions) | Dar::Or(conditions) => {
+            for condition in conditions {
+                v.visit_dar(condition);
+            }
+        }
+        Dar::Exp(expression) => v.visit_expression(expression),
+    }
+}
+
+/// Visit the function of [`Expression`].
+pub fn visit_expression<'ast, V>(v: &mut V, node: &'ast Expression<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_function(&node.function);
+}
+
+/// Visit the arguments of [`Function`].
+pub fn visit_function<'ast, V>(v: &mut V, node: &'ast Function<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Function::CurrentGameTimeLessThan { value } | Function::Random { value } => {
+            v.visit_static_value(value);
+        }
+        Function::CurrentWeather { weather } => v.visit_plugin_value(weather),
+        Function::IsClass { class } => v.visit_plugin_value(class),
+        Function::IsCombatStyle { combat_style } => v.visit_plugin_value(combat_style),
+        Function::IsActorValueEqualTo(args)
+        | Function::IsActorValueLessThan(args)
+        | Function::IsActorValueBaseLessThan(args)
+        | Function::IsActorValueMaxEqualTo(args)
+        | Function::IsActorValueMaxLessThan(args)
+        | Function::IsActorValuePercentageEqualTo(args)
+        | Function::IsActorValuePercentageLessThan(args) => v.visit_actor_args(args),
+        Function::IsActorBase { actor_base } => v.visit_plugin_value(actor_base),
+        Function::IsEquipped { form, .. } | Function::IsWorn { form } => v.visit_plugin_value(form),
+        Function::IsEquippedType { value, .. } => v.visit_weapon_type(value),
+        Function::IsEquippedHasKeyword { keyword, .. }
+        | Function::IsWornHasKeyword { keyword }
+        | Function::HasKeyword { keyword }
+        | Function::HasMagicEffectWithKeyword { keyword } => v.visit_plugin_value(keyword),
+        Function::IsEquippedShout { shout } => v.visit_plugin_value(shout),
+        Function::IsInFaction { faction } => v.visit_plugin_value(faction),
+        Function::IsFactionRankEqualTo { rank, faction }
+        | Function::IsFactionRankLessThan { rank, faction } => {
+            v.visit_global_variable(rank);
+            v.visit_plugin_value(faction);
+        }
+        Function::IsInLocation { location } => v.visit_plugin_value(location),
+        Function::IsLevelLessThan { level } => v.visit_static_value(level),
+        Function::IsParentCell { cell } => v.visit_plugin_value(cell),
+        Function::IsMovementDirection { direction } => v.visit_direction(direction),
+        Function::IsRace { race } => v.visit_plugin_value(race),
+        Function::IsVoiceType { voice_type } => v.visit_plugin_value(voice_type),
+        Function::IsWorldSpace { world_space } => v.visit_plugin_value(world_space),
+        Function::HasPerk { perk } => v.visit_plugin_value(perk),
+        Function::HasSpell { spell } => v.visit_plugin_value(spell),
+        Function::HasMagicEffect { magic_effect } => v.visit_plugin_value(magic_effect),
+        Function::HasRefType { location_ref_type } => v.visit_plugin_value(location_ref_type),
+        Function::ValueEqualTo { value_a, value_b }
+        | Function::ValueLessThan { value_a, value_b } => {
+            v.visit_global_variable(value_a);
+            v.visit_global_variable(value_b);
+        }
+        Function::IsFemale
+        | Function::IsChild
+        | Function::IsPlayerTeammate
+        | Function::IsInInterior
+        | Function::IsUnique
+        | Function::IsAttacking
+        | Function::IsRunning
+        | Function::IsSneaking
+        | Function::IsSprinting
+        | Function::IsInAir
+        | Function::IsInCombat
+        | Function::IsWeaponDrawn => {}
+    }
+}
+
+/// Visit the arguments of [`ActorArgs`].
+pub fn visit_actor_args<'ast, V>(v: &mut V, node: &'ast ActorArgs<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        ActorArgs::ActorFirst { value_a, value_b } => {
+            v.visit_actor_value(value_a);
+            v.visit_global_variable(value_b);
+        }
+        ActorArgs::GlobalFirst { value_a, value_b } => {
+            v.visit_global_variable(value_a);
+            v.visit_actor_value(value_b);
+        }
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_global_variable<'ast, V>(v: &mut V, node: &'ast GlobalVariable<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        GlobalVariable::Plugin(node) => v.visit_plugin_value(node),
+        GlobalVariable::StaticValue(node) => v.visit_static_value(node),
+    }
+}
+
+/// Visit the FormID of [`PluginValue`].
+pub fn visit_plugin_value<'ast, V>(v: &mut V, node: &'ast PluginValue<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    v.visit_form_id(&node.form_id);
+}
@@ -0,0 +1,214 @@
+//! In-place traversal of DAR syntax trees.
+//!
+//! Each method of [`VisitMut`] walks into the children by default.
+//! To keep walking after overriding a method, call the free function of the same name.
+use crate::ast::{ActorArgs, Dar, Expression, Function, GlobalVariable};
+use oar_values::{ActorValue, Direction, FormID, PluginValue, StaticValue, WeaponType};
+
+/// In-place visitor of DAR syntax trees and their values.
+pub trait VisitMut {
+    /// `AND`, `OR` or an expression.
+    fn visit_dar_mut(&mut self, node: &mut Dar<'_>) {
+        visit_dar_mut(self, node);
+    }
+
+    /// A line of a function call.
+    fn visit_expression_mut(&mut self, node: &mut Expression<'_>) {
+        visit_expression_mut(self, node);
+    }
+
+    /// A function and its arguments.
+    fn visit_function_mut(&mut self, node: &mut Function<'_>) {
+        visit_function_mut(self, node);
+    }
+
+    /// Arguments of the `IsActorValue*` functions.
+    fn visit_actor_args_mut(&mut self, node: &mut ActorArgs<'_>) {
+        visit_actor_args_mut(self, node);
+    }
+
+    /// A plugin value or a number.
+    fn visit_global_variable_mut(&mut self, node: &mut GlobalVariable<'_>) {
+        visit_global_variable_mut(self, node);
+    }
+
+    /// Actor value of [`ActorArgs`].
+    fn visit_actor_value_mut(&mut self, _node: &mut ActorValue) {}
+
+    /// Argument of `IsMovementDirection`.
+    fn visit_direction_mut(&mut self, _node: &mut Direction) {}
+
+    /// FormID of a plugin value.
+    fn visit_form_id_mut(&mut self, _node: &mut FormID<'_>) {}
+
+    /// A pair of a plugin name and a FormID.
+    fn visit_plugin_value_mut(&mut self, node: &mut PluginValue<'_>) {
+        visit_plugin_value_mut(self, node);
+    }
+
+    /// A number.
+    fn visit_static_value_mut(&mut self, _node: &mut StaticValue) {}
+
+    /// Argument of `IsEquipped*Type`.
+    fn visit_weapon_type_mut(&mut self, _node: &mut WeaponType) {}
+}
+
+/// Visit the children of [`Dar`].
+pub fn visit_dar_mut<V>(v: &mut V, node: &mut Dar<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Dar::And(conditions) | Dar::Or(conditions) => {
+            for condition in conditions {
+                v.visit_dar_mut(condition);
+            }
+        }
+        Dar::Exp(expression) => v.visit_expression_mut(expression),
+    }
+}
+
+/// Visit the function of [`Expression`].
+pub fn visit_expression_mut<V>(v: &mut V, node: &mut Expression<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_function_mut(&mut node.function);
+}
+
+/// Visit the arguments of [`Function`].
+pub fn visit_function_mut<V>(v: &mut V, node: &mut Function<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Function::CurrentGameTimeLessThan { value } | Function::Random { value } => {
+            v.visit_static_value_mut(value);
+        }
+        Function::CurrentWeather { weather } => v.visit_plugin_value_mut(weather),
+        Function::IsClass { class } => v.visit_plugin_value_mut(class),
+        Function::IsCombatStyle { combat_style } => v.visit_plugin_value_mut(combat_style),
+        Function::IsActorValueEqualTo(args)
+        | Function::IsActorValueLessThan(args)
+        | Function::IsActorValueBaseLessThan(args)
+        | Function::IsActorValueMaxEqualTo(args)
+        | Function::IsActorValueMaxLessThan(args)
+        | Function::IsActorValuePercentageEqualTo(args)
+        | Function::IsActorValuePercentageLessThan(args) => v.visit_actor_args_mut(args),
+        Function::IsActorBase { actor_base } => v.visit_plugin_value_mut(actor_base),
+        Function::IsEquipped { form, .. } | Function::IsWorn { form } => {
+            v.visit_plugin_value_mut(form);
+        }
+        Function::IsEquippedType { value, .. } => v.visit_weapon_type_mut(value),
+        Function::IsEquippedHasKeyword { keyword, .. }
+        | Function::IsWornHasKeyword { keyword }
+        | Function::HasKeyword { keyword }
+        | Function::HasMagicEffectWithKeyword { keyword } => v.visit_plugin_value_mut(keyword),
+        Function::IsEquippedShout { shout } => v.visit_plugin_value_mut(shout),
+        Function::IsInFaction { faction } => v.visit_plugin_value_mut(faction),
+        Function::IsFactionRankEqualTo { rank, faction }
+        | Function::IsFactionRankLessThan { rank, faction } => {
+            v.visit_global_variable_mut(rank);
+            v.visit_plugin_value_mut(faction);
+        }
+        Function::IsInLocation { location } => v.visit_plugin_value_mut(location),
+        Function::IsLevelLessThan { level } => v.visit_static_value_mut(level),
+        Function::IsParentCell { cell } => v.visit_plugin_value_mut(cell),
+        Function::IsMovementDirection { direction } => v.visit_direction_mut(direction),
+        Function::IsRace { race } => v.visit_plugin_value_mut(race),
+        Function::IsVoiceType { voice_type } => v.visit_plugin_value_mut(voice_type),
+        Function::IsWorldSpace { world_space } => v.visit_plugin_value_mut(world_space),
+        Function::HasPerk { perk } => v.visit_plugin_value_mut(perk),
+        Function::HasSpell { spell } => v.visit_plugin_value_mut(spell),
+        Function::HasMagicEffect { magic_effect } => v.visit_plugin_value_mut(magic_effect),
+        Function::HasRefType { location_ref_type } => v.visit_plugin_value_mut(location_ref_type),
+        Function::ValueEqualTo { value_a, value_b }
+        | Function::ValueLessThan { value_a, value_b } => {
+            v.visit_global_variable_mut(value_a);
+            v.visit_global_variable_mut(value_b);
+        }
+        Function::IsFemale
+        | Function::IsChild
+        | Function::IsPlayerTeammate
+        | Function::IsInInterior
+        | Function::IsUnique
+        | Function::IsAttacking
+        | Function::IsRunning
+        | Function::IsSneaking
+        | Function::IsSprinting
+        | Function::IsInAir
+        | Function::IsInCombat
+        | Function::IsWeaponDrawn => {}
+    }
+}
+
+/// Visit the arguments of [`ActorArgs`].
+pub fn visit_actor_args_mut<V>(v: &mut V, node: &mut ActorArgs<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        ActorArgs::ActorFirst { value_a, value_b } => {
+            v.visit_actor_value_mut(value_a);
+            v.visit_global_variable_mut(value_b);
+        }
+        ActorArgs::GlobalFirst { value_a, value_b } => {
+            v.visit_global_variable_mut(value_a);
+            v.visit_actor_value_mut(value_b);
+        }
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_global_variable_mut<V>(v: &mut V, node: &mut GlobalVariable<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        GlobalVariable::Plugin(node) => v.visit_plugin_value_mut(node),
+        GlobalVariable::StaticValue(node) => v.visit_static_value_mut(node),
+    }
+}
+
+/// Visit the FormID of [`PluginValue`].
+pub fn visit_plugin_value_mut<V>(v: &mut V, node: &mut PluginValue<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    v.visit_form_id_mut(&mut node.form_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_dar_syntax;
+    use pretty_assertions::assert_eq;
+
+    struct RenamePlugin;
+
+    impl VisitMut for RenamePlugin {
+        fn visit_plugin_value_mut(&mut self, node: &mut PluginValue<'_>) {
+            if node.plugin_name.eq_ignore_ascii_case("old.esp") {
+                node.plugin_name = "New.esp".into();
+            }
+            visit_plugin_value_mut(self, node);
+        }
+    }
+
+    #[test]
+    fn should_rename_plugins() {
+        let input = r#"IsActorValueLessThan("Old.esp" | 0x801, 24) OR
+NOT IsFactionRankEqualTo(1, "old.esp" | 0x802) AND
+IsWorn("Skyrim.esm" | 0x803)"#;
+        let expected = r#"IsActorValueLessThan("New.esp" | 0x801, 24) OR
+NOT IsFactionRankEqualTo(1, "New.esp" | 0x802) AND
+IsWorn("Skyrim.esm" | 0x803)"#;
+
+        let (Ok(mut actual), Ok(expected)) = (parse_dar_syntax(input), parse_dar_syntax(expected))
+        else {
+            panic!("invalid DAR syntax");
+        };
+        RenamePlugin.visit_dar_mut(&mut actual);
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,125 +1,179 @@
-use crate::MappingTable;
+use crate::{MappingTable, is_plugin_name, plugin_key};
 
 use super::error::Error;
+use super::fomod::infer_from_fomod;
 use super::parser::{parse_dir_pattern, strip_numbers};
 use super::strategy::MappingStrategy;
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
+/// A generated table and where each name came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeneratedTable {
+    /// priority -> name. Priorities without a name have an empty name.
+    pub table: MappingTable,
+    /// priority -> strategy that produced the name
+    pub provenance: HashMap<String, MappingStrategy>,
+}
+
 /// Generate mapping table from a directory.
 ///
+/// With [`MappingStrategy::Composite`], the strategies are tried in order and
+/// the first name found for each priority is kept.
+///
 /// # Errors
 ///
-/// Returns an error if the path does not exist or if priority extraction fails.
+/// Returns an error if the path does not exist, if priority extraction fails
+/// or if a file needed by the strategy cannot be read.
 ///
 /// Root example:
 ///   .../meshes/actors/character/animations/DynamicAnimationReplacer
 pub fn generate_mapping_table(
     path: &Path,
-    strategy: MappingStrategy,
-) -> Result<MappingTable, Error> {
-    let root = path;
+    strategy: &MappingStrategy,
+) -> Result<GeneratedTable, Error> {
+    let mut generated = GeneratedTable {
+        table: collect_priorities(path)?
+            .into_iter()
+            .map(|priority| (priority, String::new()))
+            .collect(),
+        ..Default::default()
+    };
+
+    for strategy in strategy.leaves() {
+        for (priority, name) in infer(path, strategy)? {
+            let current = generated.table.entry(priority.clone()).or_default();
+            if name.is_empty() || !current.is_empty() {
+                continue;
+            }
+            *current = name;
+            generated.provenance.insert(priority, strategy.clone());
+        }
+    }
+
+    Ok(generated)
+}
 
-    if !root.exists() {
+/// Collect every priority(or FormID) dir under `DynamicAnimationReplacer` in `path`.
+///
+/// # Errors
+///
+/// Returns an error if the path does not exist.
+pub fn collect_priorities(path: &Path) -> Result<BTreeSet<String>, Error> {
+    if !path.exists() {
         return Err(Error::PathNotFound {
             path: path.to_path_buf(),
         });
     }
 
-    let mut map = MappingTable::new();
+    Ok(walk_dar(path)
+        .filter_map(|path| get_priority(&path))
+        .collect())
+}
 
-    for entry in jwalk::WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
-        let path = path.as_path();
+/// Infer `priority -> name` with a single (non-composite) strategy.
+fn infer(root: &Path, strategy: &MappingStrategy) -> Result<BTreeMap<String, String>, Error> {
+    let mut map = BTreeMap::new();
 
-        // ----------------------
-        // Skip everything until we are under DynamicAnimationReplacer
-        //
-        // Expected:
-        //   .../DynamicAnimationReplacer/...
-        //
-        if !is_under_dar(path) {
-            continue;
-        }
+    match strategy {
+        MappingStrategy::TxtStem | MappingStrategy::TxtStemStripped => {
+            for path in walk_dar(root).filter(|path| is_rename_source_txt(path)) {
+                let priority = get_priority(&path)
+                    .ok_or_else(|| Error::InvalidPriority { path: path.clone() })?;
+
+                let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                let name = match strategy {
+                    MappingStrategy::TxtStemStripped => strip_numbers(stem),
+                    _ => stem.to_string(),
+                };
 
-        // ----------------------
-        // Detect priority directory (DAR)
-        //
-        // Expected:
-        //   .../DynamicAnimationReplacer/<container>/<priority>/
-        //                                                ^ this directory
-        //
-        // <container>:
-        //   - plugin name (e.g. "Skyrim.esm")
-        //   - or "_CustomConditions"
-        //
-        if let Some(priority) = get_priority(path) {
-            map.entry(priority.to_string()).or_default();
+                map.insert(priority, name);
+            }
         }
 
-        match strategy {
-            MappingStrategy::TxtStem => {
-                if !is_rename_source_txt(path) {
+        MappingStrategy::DirPattern => {
+            for path in walk_dar(root).filter(|path| path.is_dir()) {
+                let Some(dir_name) = path.file_name().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                if dir_name.eq_ignore_ascii_case("DynamicAnimationReplacer")
+                    || dir_name.eq_ignore_ascii_case("_CustomConditions")
+                {
                     continue;
                 }
 
-                let priority = get_priority(path).ok_or_else(|| Error::InvalidPriority {
-                    path: path.to_path_buf(),
-                })?;
+                if let Some((priority, name)) = parse_dir_pattern(dir_name) {
+                    let container = path
+                        .parent()
+                        .and_then(Path::file_name)
+                        .and_then(|s| s.to_str());
+                    let Some(key) = priority_key(container.unwrap_or_default(), priority) else {
+                        continue;
+                    };
 
-                let name = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("")
-                    .to_string();
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(key, name, "parsed dir pattern");
 
-                map.insert(priority.to_string(), name);
+                    map.insert(key, name.to_string());
+                }
             }
+        }
 
-            MappingStrategy::TxtStemStripped => {
-                if !is_rename_source_txt(path) {
+        MappingStrategy::ReadmeTxt => {
+            for path in walk_dar(root).filter(|path| is_file_named(path, "readme.txt")) {
+                let Some(priority) = get_priority(&path) else {
                     continue;
+                };
+                let text = read_text(&path)?;
+                if let Some(line) = text.lines().map(str::trim).find(|line| !line.is_empty()) {
+                    map.insert(priority, line.to_string());
                 }
-
-                let priority = get_priority(path).ok_or_else(|| Error::InvalidPriority {
-                    path: path.to_path_buf(),
-                })?;
-
-                let name = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .map(strip_numbers)
-                    .unwrap_or_default();
-
-                map.insert(priority.to_string(), name);
             }
+        }
 
-            MappingStrategy::DirPattern => {
-                if !path.is_dir() {
+        MappingStrategy::ConditionsComment => {
+            for path in walk_dar(root).filter(|path| is_file_named(path, "_conditions.txt")) {
+                let Some(priority) = get_priority(&path) else {
                     continue;
+                };
+                let text = read_text(&path)?;
+                if let Some(comment) = header_comment(&text) {
+                    map.insert(priority, comment.to_string());
                 }
+            }
+        }
 
-                let Some(dir_name) = path.file_name().and_then(|s| s.to_str()) else {
+        MappingStrategy::HkxSubfolder => {
+            let mut subfolders = BTreeMap::<String, BTreeSet<String>>::new();
+            for path in walk_dar(root).filter(|path| has_extension(path, "hkx")) {
+                let Some(subfolder) = path.parent() else {
                     continue;
                 };
-                if dir_name.eq_ignore_ascii_case("DynamicAnimationReplacer")
-                    || dir_name.eq_ignore_ascii_case("_CustomConditions")
-                {
+                let (Some(priority), Some(name)) = (
+                    get_priority(subfolder),
+                    subfolder.file_name().and_then(|s| s.to_str()),
+                ) else {
                     continue;
-                }
-
-                if let Some((priority, name)) = parse_dir_pattern(dir_name) {
-                    if !is_valid_priority(priority) {
-                        continue;
-                    }
-
-                    #[cfg(feature = "tracing")]
-                    tracing::debug!(priority, name, "parsed dir pattern");
+                };
+                subfolders
+                    .entry(priority)
+                    .or_default()
+                    .insert(name.to_string());
+            }
 
-                    map.insert(priority.to_string(), name.to_string());
+            for (priority, names) in subfolders {
+                let mut names = names.into_iter();
+                if let (Some(name), None) = (names.next(), names.next()) {
+                    map.insert(priority, name);
                 }
             }
         }
+
+        MappingStrategy::Fomod => map = infer_from_fomod(root)?,
+
+        MappingStrategy::Composite(_) => {} // Expanded by `MappingStrategy::leaves`
     }
 
     Ok(map)
@@ -128,6 +182,15 @@ pub fn generate_mapping_table(
 // ----------------------
 // Helper functions
 // ----------------------
+/// Paths under `DynamicAnimationReplacer` in `root`.
+fn walk_dar(root: &Path) -> impl Iterator<Item = std::path::PathBuf> {
+    jwalk::WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_under_dar(path))
+}
+
 /// Check if path is under DynamicAnimationReplacer.
 ///
 /// Expected:
@@ -153,8 +216,8 @@ fn is_under_dar(path: &Path) -> bool {
 ///   .../DynamicAnimationReplacer/Skyrim.esm/666003/foo.txt
 ///   .../DynamicAnimationReplacer/_CustomConditions/666003/_conditions.txt
 ///
-/// returns "666003"
-fn get_priority(path: &Path) -> Option<&str> {
+/// returns "666003", or "Skyrim.esm|666003" for a plugin container. See [`priority_key`].
+pub(super) fn get_priority(path: &Path) -> Option<String> {
     let mut components = path.components().rev();
 
     // file or dir (skip)
@@ -163,8 +226,8 @@ fn get_priority(path: &Path) -> Option<&str> {
     // priority dir
     let priority = components.next()?.as_os_str().to_str()?;
 
-    // skip container (plugin or special dir)
-    let _ = components.next()?.as_os_str().to_str()?;
+    // container (plugin or special dir)
+    let container = components.next()?.as_os_str().to_str()?;
 
     // must be under DynamicAnimationReplacer
     let dar = components.next()?.as_os_str().to_str()?;
@@ -172,13 +235,28 @@ fn get_priority(path: &Path) -> Option<&str> {
         return None;
     }
 
-    if is_valid_priority(priority) {
-        Some(priority)
-    } else {
-        #[cfg(feature = "tracing")]
+    let key = priority_key(container, priority);
+    #[cfg(feature = "tracing")]
+    if key.is_none() {
         tracing::debug!(priority, container, "invalid priority detected");
-        None
     }
+    key
+}
+
+/// Returns the table key of `priority` in `container`.
+///
+/// - `_CustomConditions/666003` → `666003`
+/// - `Skyrim.esm/00013BBF` → `Skyrim.esm|00013BBF`(ActorBase, see [`plugin_key`])
+///
+/// `None` if `priority` is not a valid priority.
+pub(super) fn priority_key(container: &str, priority: &str) -> Option<String> {
+    if !is_valid_priority(priority) {
+        return None;
+    }
+    Some(match is_plugin_name(container) {
+        true => plugin_key(container, priority),
+        false => priority.to_string(),
+    })
 }
 
 /// Valid priority:
@@ -199,11 +277,118 @@ fn is_valid_priority(s: &str) -> bool {
 /// Excludes:
 ///   .../_conditions.txt
 fn is_rename_source_txt(path: &Path) -> bool {
-    path.extension()
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"))
-        && !path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .is_some_and(|s| s.eq_ignore_ascii_case("_conditions.txt"))
+    has_extension(path, "txt")
+        && !is_file_named(path, "_conditions.txt")
         && get_priority(path).is_some()
 }
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+fn is_file_named(path: &Path, name: &str) -> bool {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case(name))
+}
+
+/// Read a text file. Invalid UTF-8 is replaced, since these files are not always UTF-8.
+pub(super) fn read_text(path: &Path) -> Result<String, Error> {
+    let bytes = std::fs::read(path).map_err(|source| Error::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// The first non-empty `;` comment before any condition.
+///
+/// e.g. `; Sneak idle\nIsSneaking()` → `Sneak idle`
+fn header_comment(text: &str) -> Option<&str> {
+    text.trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map_while(|line| line.strip_prefix(';'))
+        .map(|comment| comment.trim_start_matches(';').trim())
+        .find(|comment| !comment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    const DAR_DIR: &str =
+        "meshes/actors/character/animations/DynamicAnimationReplacer/_CustomConditions";
+
+    fn write(root: &Path, path: &str, contents: &str) -> std::io::Result<()> {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+    }
+
+    #[test]
+    fn should_record_first_strategy_that_found_name() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        write(
+            root,
+            &format!("{DAR_DIR}/100/_conditions.txt"),
+            "; Sneak idle\nIsSneaking()",
+        )?;
+        write(
+            root,
+            &format!("{DAR_DIR}/100/readme.txt"),
+            "\nSneak from readme\n",
+        )?;
+        write(
+            root,
+            &format!("{DAR_DIR}/200/_conditions.txt"),
+            "IsFemale()",
+        )?;
+        write(root, &format!("{DAR_DIR}/200/Female Walk/mt_walk.hkx"), "")?;
+        write(
+            root,
+            &format!("{DAR_DIR}/300/_conditions.txt"),
+            "IsInCombat()",
+        )?;
+
+        let strategy = MappingStrategy::Composite(vec![
+            MappingStrategy::ConditionsComment,
+            MappingStrategy::ReadmeTxt,
+            MappingStrategy::HkxSubfolder,
+        ]);
+        let generated = generate_mapping_table(root, &strategy)?;
+
+        let table: MappingTable = [
+            ("100".to_string(), "Sneak idle".to_string()),
+            ("200".to_string(), "Female Walk".to_string()),
+            ("300".to_string(), String::new()),
+        ]
+        .into();
+        let provenance: HashMap<_, _> = [
+            ("100".to_string(), MappingStrategy::ConditionsComment),
+            ("200".to_string(), MappingStrategy::HkxSubfolder),
+        ]
+        .into();
+        assert_eq!(generated, GeneratedTable { table, provenance });
+        Ok(())
+    }
+
+    #[test]
+    fn should_qualify_actor_base_priorities() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        let actor_base = "meshes/actors/character/animations/DynamicAnimationReplacer/Skyrim.esm";
+        write(root, &format!("{actor_base}/00013BBF/mt_idle.hkx"), "")?;
+        write(root, &format!("{DAR_DIR}/100/mt_idle.hkx"), "")?;
+
+        let expected: BTreeSet<_> = ["100", "Skyrim.esm|00013BBF"].map(String::from).into();
+        assert_eq!(collect_priorities(root)?, expected);
+        Ok(())
+    }
+}
@@ -6,4 +6,16 @@ pub enum Error {
 
     #[snafu(display("Failed to parse priority from path: {}", path.display()))]
     InvalidPriority { path: std::path::PathBuf },
+
+    #[snafu(display("Failed to read {}: {source}", path.display()))]
+    ReadFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Invalid FOMOD config {}: {source}", path.display()))]
+    InvalidFomod {
+        path: std::path::PathBuf,
+        source: roxmltree::Error,
+    },
 }
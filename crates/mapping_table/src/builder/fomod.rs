@@ -0,0 +1,138 @@
+//! Infer names from a FOMOD installer(`fomod/ModuleConfig.xml`).
+//!
+//! Each option(`<plugin name="...">`) lists the folders/files it installs. If one of them points
+//! into a DAR priority dir, the option name is used as the section name of that priority.
+use super::build::priority_key;
+use super::error::Error;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Infer `priority -> option name` from every `fomod/ModuleConfig.xml` in `root`.
+///
+/// # Errors
+/// Failed to read or parse a `ModuleConfig.xml`.
+pub(super) fn infer_from_fomod(root: &Path) -> Result<BTreeMap<String, String>, Error> {
+    let mut map = BTreeMap::new();
+
+    let configs = jwalk::WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_module_config(path));
+
+    for path in configs {
+        let bytes = std::fs::read(&path).map_err(|source| Error::ReadFile {
+            path: path.clone(),
+            source,
+        })?;
+        let text = decode(&bytes);
+
+        let options = roxmltree::ParsingOptions {
+            allow_dtd: true,
+            ..Default::default()
+        };
+        let document =
+            roxmltree::Document::parse_with_options(&text, options).map_err(|source| {
+                Error::InvalidFomod {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+
+        for plugin in document
+            .descendants()
+            .filter(|node| node.has_tag_name("plugin"))
+        {
+            let Some(name) = plugin.attribute("name").map(str::trim) else {
+                continue;
+            };
+            let priorities = plugin
+                .descendants()
+                .filter(|node| node.has_tag_name("folder") || node.has_tag_name("file"))
+                .filter_map(|node| {
+                    ["source", "destination"]
+                        .into_iter()
+                        .find_map(|attr| node.attribute(attr).and_then(priority_in))
+                });
+            for priority in priorities {
+                map.insert(priority, name.to_string());
+            }
+        }
+    }
+
+    Ok(map)
+}
+
+/// `.../fomod/ModuleConfig.xml`
+fn is_module_config(path: &Path) -> bool {
+    let is_named = |path: Option<&Path>, name: &str| {
+        path.and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s.eq_ignore_ascii_case(name))
+    };
+    is_named(Some(path), "ModuleConfig.xml") && is_named(path.parent(), "fomod")
+}
+
+/// Table key of the priority dir in a FOMOD source/destination path.
+///
+/// e.g. `Option A\meshes\...\DynamicAnimationReplacer\_CustomConditions\100\mt_idle.hkx` → `100`
+fn priority_in(path: &str) -> Option<String> {
+    let components: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+    let dar_pos = components
+        .iter()
+        .position(|s| s.eq_ignore_ascii_case("DynamicAnimationReplacer"))?;
+    priority_key(components.get(dar_pos + 1)?, components.get(dar_pos + 2)?)
+}
+
+/// `ModuleConfig.xml` is often UTF-16, so decode by the BOM.
+fn decode(bytes: &[u8]) -> String {
+    let utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        bytes => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_dir::TempDir;
+
+    #[test]
+    fn should_infer_option_names() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let fomod_dir = temp_dir.path().join("fomod");
+        std::fs::create_dir_all(&fomod_dir)?;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-16"?>
+<config>
+  <plugins>
+    <plugin name="Sneak Idle">
+      <files>
+        <folder source="Sneak\meshes\actors\character\animations\DynamicAnimationReplacer\_CustomConditions\100" destination="" />
+      </files>
+    </plugin>
+    <plugin name="Whole mod">
+      <files><folder source="Core\meshes" destination="meshes" /></files>
+    </plugin>
+  </plugins>
+</config>"#;
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+        std::fs::write(fomod_dir.join("ModuleConfig.xml"), bytes)?;
+
+        let expected: BTreeMap<_, _> = [("100".to_string(), "Sneak Idle".to_string())].into();
+        assert_eq!(infer_from_fomod(temp_dir.path())?, expected);
+        Ok(())
+    }
+}
@@ -5,9 +5,10 @@
 
 mod build;
 mod error;
+mod fomod;
 mod parser;
 mod strategy;
 
-pub use build::generate_mapping_table;
+pub use build::{GeneratedTable, collect_priorities, generate_mapping_table};
 pub use error::Error;
 pub use strategy::MappingStrategy;
@@ -1,5 +1,5 @@
 /// Strategy used to infer mapping entries.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MappingStrategy {
@@ -13,4 +13,31 @@ pub enum MappingStrategy {
 
     /// Parse directory names like `123 - name`.
     DirPattern,
+
+    /// Use the first non-empty line of `readme.txt` in the priority dir.
+    ReadmeTxt,
+
+    /// Use the first `;` comment at the top of `_conditions.txt`.
+    /// e.g. `; Sneak idle` → "Sneak idle"
+    ConditionsComment,
+
+    /// Use the name of the only subfolder of the priority dir that contains `.hkx` files.
+    HkxSubfolder,
+
+    /// Use the FOMOD option(`<plugin name="...">` in `fomod/ModuleConfig.xml`)
+    /// that installs the priority dir.
+    Fomod,
+
+    /// Try each strategy in order. The first one that finds a name for a priority wins.
+    Composite(Vec<Self>),
+}
+
+impl MappingStrategy {
+    /// Non-composite strategies, in the order they are tried.
+    pub(super) fn leaves(&self) -> Vec<&Self> {
+        match self {
+            Self::Composite(strategies) => strategies.iter().flat_map(Self::leaves).collect(),
+            strategy => vec![strategy],
+        }
+    }
 }
@@ -0,0 +1,123 @@
+//! Validate a [`MappingTable`] against the priority dirs of a DAR tree.
+use crate::writer::compare_priority;
+use crate::{MappingTable, PLUGIN_KEY_SEPARATOR, lookup};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Problems found by [`check_mapping_table`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct CheckReport {
+    /// Priorities in the DAR tree that have no name in the table
+    pub unmapped: Vec<String>,
+    /// Priorities in the table that have no dir in the DAR tree
+    pub missing: Vec<String>,
+    /// Names used by more than one priority
+    pub duplicates: Vec<DuplicateName>,
+}
+
+/// A name shared by several priorities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DuplicateName {
+    /// Section name
+    pub name: String,
+    /// Priorities mapped to `name`
+    pub priorities: Vec<String>,
+}
+
+impl CheckReport {
+    /// Returns `true` if no problem was found.
+    pub const fn is_ok(&self) -> bool {
+        self.unmapped.is_empty() && self.missing.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+/// Compare `table` with the `priorities` found in the DAR tree.
+///
+/// `priorities` is usually collected by [`collect_priorities`](crate::builder::collect_priorities).
+/// A plugin-qualified priority(`Skyrim.esm|00013BBF`) is also mapped by its FormID only entry.
+pub fn check_mapping_table(table: &MappingTable, priorities: &BTreeSet<String>) -> CheckReport {
+    let is_named = |priority: &String| {
+        let (plugin, key) = match priority.split_once(PLUGIN_KEY_SEPARATOR) {
+            Some((plugin, form_id)) => (Some(plugin), form_id),
+            None => (None, priority.as_str()),
+        };
+        lookup(table, plugin, key).is_some_and(|name| !name.trim().is_empty())
+    };
+    let exists = |key: &String| {
+        priorities.contains(key)
+            || priorities.iter().any(|priority| {
+                priority
+                    .split_once(PLUGIN_KEY_SEPARATOR)
+                    .is_some_and(|(_, form_id)| form_id == key)
+            })
+    };
+
+    let mut unmapped: Vec<_> = priorities
+        .iter()
+        .filter(|p| !is_named(p))
+        .cloned()
+        .collect();
+    let mut missing: Vec<_> = table.keys().filter(|key| !exists(key)).cloned().collect();
+    unmapped.sort_by(|l, r| compare_priority(l, r));
+    missing.sort_by(|l, r| compare_priority(l, r));
+
+    let mut by_name = BTreeMap::<&str, Vec<String>>::new();
+    for (priority, name) in table {
+        let name = name.trim();
+        if !name.is_empty() {
+            by_name.entry(name).or_default().push(priority.clone());
+        }
+    }
+    let duplicates = by_name
+        .into_iter()
+        .filter(|(_, priorities)| priorities.len() > 1)
+        .map(|(name, mut priorities)| {
+            priorities.sort_by(|l, r| compare_priority(l, r));
+            DuplicateName {
+                name: name.to_string(),
+                priorities,
+            }
+        })
+        .collect();
+
+    CheckReport {
+        unmapped,
+        missing,
+        duplicates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_unmapped_missing_and_duplicates() {
+        let table: MappingTable = [
+            ("100".to_string(), "Walk".to_string()),
+            ("200".to_string(), "Walk".to_string()),
+            ("300".to_string(), "Run".to_string()),
+        ]
+        .into();
+        let priorities = ["100", "200", "400"].map(String::from).into();
+
+        let expected = CheckReport {
+            unmapped: vec!["400".into()],
+            missing: vec!["300".into()],
+            duplicates: vec![DuplicateName {
+                name: "Walk".into(),
+                priorities: vec!["100".into(), "200".into()],
+            }],
+        };
+        assert_eq!(check_mapping_table(&table, &priorities), expected);
+    }
+
+    #[test]
+    fn should_map_plugin_key_by_form_id() {
+        let table: MappingTable = [("00013BBF".to_string(), "Lydia".to_string())].into();
+        let priorities = ["Skyrim.esm|00013BBF".to_string()].into();
+        assert!(check_mapping_table(&table, &priorities).is_ok());
+    }
+}
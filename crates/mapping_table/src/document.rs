@@ -0,0 +1,308 @@
+//! Ordered document model of a mapping table.
+//!
+//! Unlike [`MappingTable`], a [`MappingDocument`] keeps comments, blank lines, line endings and
+//! entries with an omitted name, so that a table can be edited without mangling the user's file.
+//! Unchanged lines are written back byte for byte.
+use crate::reader::{ReadableError, parse_mapping_table};
+use crate::writer::compare_priority;
+use crate::{MappingTable, PLUGIN_KEY_SEPARATOR, is_plugin_name};
+use core::fmt;
+
+/// A mapping table file as a list of lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MappingDocument {
+    lines: Vec<DocLine>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DocLine {
+    line: Line,
+    ending: LineEnding,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineEnding {
+    Lf,
+    CrLf,
+    /// Last line without a line break
+    None,
+}
+
+impl LineEnding {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::None => "",
+        }
+    }
+}
+
+/// A line of a [`MappingDocument`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// Empty or whitespace only line
+    Blank(String),
+    /// `// comment` line
+    Comment(String),
+    /// `<priority> [name] [// comment]` line
+    Entry(Entry),
+}
+
+/// `<priority> [name] [// comment]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    priority: String,
+    name: Option<String>,
+    comment: Option<String>,
+    /// Original text. `None` once the entry has been edited.
+    raw: Option<String>,
+}
+
+impl Entry {
+    const fn new(priority: String, name: Option<String>) -> Self {
+        Self {
+            priority,
+            name,
+            comment: None,
+            raw: None,
+        }
+    }
+
+    /// Priority (or FormID)
+    pub fn priority(&self) -> &str {
+        &self.priority
+    }
+
+    /// Section name. `None` means `<previous name>_<N>`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Comment after the entry, without the leading `//`.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(raw) = &self.raw {
+            return f.write_str(raw);
+        }
+
+        f.write_str(&self.priority)?;
+        if let Some(name) = &self.name {
+            write!(f, " {name}")?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " //{comment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl MappingDocument {
+    /// Parse a mapping table, keeping everything needed to write it back unchanged.
+    ///
+    /// # Errors
+    /// Returns the same error as [`parse_mapping_table`] if the table is invalid.
+    pub fn parse(input: &str) -> Result<Self, ReadableError> {
+        parse_mapping_table(input)?;
+
+        let lines = input
+            .split_inclusive('\n')
+            .map(|line| {
+                let (content, ending) = match line.strip_suffix('\n') {
+                    Some(line) => match line.strip_suffix('\r') {
+                        Some(line) => (line, LineEnding::CrLf),
+                        None => (line, LineEnding::Lf),
+                    },
+                    None => (line, LineEnding::None),
+                };
+                DocLine {
+                    line: parse_line(content),
+                    ending,
+                }
+            })
+            .collect();
+        Ok(Self { lines })
+    }
+
+    /// Lines in the document order.
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.lines.iter().map(|line| &line.line)
+    }
+
+    /// Entries in the document order.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.lines().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            Line::Blank(_) | Line::Comment(_) => None,
+        })
+    }
+
+    /// Resolve the entries into a [`MappingTable`], as [`parse_mapping_table`] does.
+    ///
+    /// An omitted name with no named entry before it(only possible after [`Self::remove`]) is skipped.
+    pub fn to_table(&self) -> MappingTable {
+        let mut table = MappingTable::new();
+        let mut base = None;
+        let mut suffix = 0_usize;
+
+        for entry in self.entries() {
+            if let Some(name) = &entry.name {
+                suffix = 0;
+                base = Some(name.as_str());
+                table.insert(entry.priority.clone(), name.clone());
+            } else if let Some(base) = base {
+                suffix += 1;
+                table.insert(entry.priority.clone(), format!("{base}_{suffix}"));
+            }
+        }
+        table
+    }
+
+    /// Set the name of `priority`.
+    ///
+    /// The last entry of `priority` is rewritten in place, keeping its comment.
+    /// If there is none, a new entry is appended.
+    pub fn set(&mut self, priority: &str, name: &str) {
+        let name = Some(name.trim().to_string());
+        let entry = self
+            .lines
+            .iter_mut()
+            .rev()
+            .find_map(|line| match &mut line.line {
+                Line::Entry(entry) if entry.priority == priority => Some(entry),
+                Line::Entry(_) | Line::Blank(_) | Line::Comment(_) => None,
+            });
+
+        match entry {
+            Some(entry) if entry.name == name => {}
+            Some(entry) => {
+                entry.name = name;
+                entry.raw = None;
+            }
+            None => self.push(Line::Entry(Entry::new(priority.to_string(), name))),
+        }
+    }
+
+    /// Remove every entry of `priority`. Returns `true` if any was removed.
+    pub fn remove(&mut self, priority: &str) -> bool {
+        let len = self.lines.len();
+        self.lines
+            .retain(|line| !matches!(&line.line, Line::Entry(entry) if entry.priority == priority));
+        self.lines.len() != len
+    }
+
+    /// Append a line, using the line ending of the document.
+    fn push(&mut self, line: Line) {
+        let ending = self
+            .lines
+            .iter()
+            .map(|line| line.ending)
+            .find(|ending| *ending != LineEnding::None)
+            .unwrap_or(LineEnding::Lf);
+        if let Some(last) = self.lines.last_mut()
+            && last.ending == LineEnding::None
+        {
+            last.ending = ending;
+        }
+        self.lines.push(DocLine { line, ending });
+    }
+}
+
+/// Sorted by priority. Entries without a name are written as comments, so that the user can fill them in.
+impl From<&MappingTable> for MappingDocument {
+    fn from(table: &MappingTable) -> Self {
+        let mut entries: Vec<_> = table.iter().collect();
+        entries.sort_by(|(l, _), (r, _)| compare_priority(l, r));
+
+        let mut document = Self::default();
+        for (priority, name) in entries {
+            let name = name.trim();
+            document.push(match name.is_empty() {
+                true => Line::Comment(format!("// {priority}")),
+                false => Line::Entry(Entry::new(priority.clone(), Some(name.to_string()))),
+            });
+        }
+        document
+    }
+}
+
+impl fmt::Display for MappingDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for DocLine { line, ending } in &self.lines {
+            match line {
+                Line::Blank(raw) | Line::Comment(raw) => f.write_str(raw)?,
+                Line::Entry(entry) => write!(f, "{entry}")?,
+            }
+            f.write_str(ending.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a line(without the line ending) in the same way as the reader.
+fn parse_line(raw: &str) -> Line {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Line::Blank(raw.to_string());
+    }
+    if trimmed.starts_with("//") {
+        return Line::Comment(raw.to_string());
+    }
+
+    let (body, comment) = match trimmed.split_once("//") {
+        Some((body, comment)) => (body.trim(), Some(comment.to_string())),
+        None => (trimmed, None),
+    };
+    // Only the plugin part of `plugin|FormID` may contain spaces.
+    let key_start = match body.split_once(PLUGIN_KEY_SEPARATOR) {
+        Some((plugin, _)) if is_plugin_name(plugin) => plugin.len() + 1,
+        _ => 0,
+    };
+    let (priority, name) = match body[key_start..].find(' ') {
+        Some(end) => {
+            let (priority, name) = body.split_at(key_start + end);
+            (priority, Some(name.trim().to_string()))
+        }
+        None => (body, None),
+    };
+
+    Line::Entry(Entry {
+        priority: priority.to_string(),
+        name,
+        comment,
+        raw: Some(raw.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "// Header\r\n8000000  Combat\r\n8000001\r\n\r\n8001000  Unarmed // Comment after name\r\n8001010\r\nImmersive Armors.esp|00012345 Guard";
+
+    #[test]
+    fn should_write_unchanged_document_byte_identical() -> Result<(), ReadableError> {
+        let document = MappingDocument::parse(INPUT)?;
+        assert_eq!(document.to_string(), INPUT);
+        assert_eq!(document.to_table(), parse_mapping_table(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    fn should_edit_only_changed_lines() -> Result<(), ReadableError> {
+        let mut document = MappingDocument::parse(INPUT)?;
+        document.set("8001000", "Sword");
+        document.set("8002000", "Bow");
+        assert!(document.remove("8000001"));
+
+        let expected = "// Header\r\n8000000  Combat\r\n\r\n8001000 Sword // Comment after name\r\n8001010\r\nImmersive Armors.esp|00012345 Guard\r\n8002000 Bow\r\n";
+        assert_eq!(document.to_string(), expected);
+        assert_eq!(document.to_table(), parse_mapping_table(expected)?);
+        Ok(())
+    }
+}
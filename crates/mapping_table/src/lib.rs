@@ -4,6 +4,50 @@
 //! from directory structures and file names.
 
 pub mod builder;
+pub mod check;
+pub mod document;
 pub mod reader;
+pub mod writer;
 
+/// `priority -> section name`
+///
+/// For ActorBase dirs(`DynamicAnimationReplacer/Skyrim.esm/00013BBF/`), the key is the FormID,
+/// or the plugin-qualified [`plugin_key`](e.g. `Skyrim.esm|00013BBF`) which takes precedence.
 pub type MappingTable = std::collections::HashMap<String, String>;
+
+/// Separator of a plugin-qualified key. e.g. `Skyrim.esm|00013BBF`
+pub const PLUGIN_KEY_SEPARATOR: char = '|';
+
+/// Returns the plugin-qualified key of an ActorBase entry. e.g. `Skyrim.esm|00013BBF`
+pub fn plugin_key(plugin: &str, form_id: &str) -> String {
+    format!("{plugin}{PLUGIN_KEY_SEPARATOR}{form_id}")
+}
+
+/// Look up the section name of `key`, preferring `plugin|key` if `plugin` is given.
+///
+/// # Example
+/// ```
+/// use mapping_table::{MappingTable, lookup};
+///
+/// let table: MappingTable = [
+///     ("00013BBF".into(), "Lydia".into()),
+///     ("Dawnguard.esm|00013BBF".into(), "Serana".into()),
+/// ]
+/// .into();
+/// assert_eq!(lookup(&table, Some("Dawnguard.esm"), "00013BBF"), Some("Serana"));
+/// assert_eq!(lookup(&table, Some("Skyrim.esm"), "00013BBF"), Some("Lydia"));
+/// ```
+pub fn lookup<'a>(table: &'a MappingTable, plugin: Option<&str>, key: &str) -> Option<&'a str> {
+    plugin
+        .and_then(|plugin| table.get(&plugin_key(plugin, key)))
+        .or_else(|| table.get(key))
+        .map(String::as_str)
+}
+
+/// Returns `true` if `name` looks like a plugin file name(`.esp`, `.esm` or `.esl`).
+pub fn is_plugin_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    [".esp", ".esm", ".esl"]
+        .iter()
+        .any(|ext| name.len() > ext.len() && name.ends_with(ext))
+}
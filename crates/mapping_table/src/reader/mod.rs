@@ -4,7 +4,7 @@ use winnow::error::{StrContext, StrContextValue};
 use winnow::prelude::*;
 use winnow_ext::take_until_ext;
 
-use crate::MappingTable;
+use crate::{MappingTable, PLUGIN_KEY_SEPARATOR, is_plugin_name};
 
 pub use winnow_ext::ReadableError;
 
@@ -14,6 +14,9 @@ pub use winnow_ext::ReadableError;
 /// - `key name`
 /// - `key`
 ///
+/// `key` is a priority, a FormID, or a plugin-qualified FormID(e.g. `Skyrim.esm|00013BBF`).
+/// Only the plugin part may contain spaces.
+///
 /// Lines may contain trailing comments starting with `//`.
 /// Empty lines and comment-only lines are ignored.
 ///
@@ -84,15 +87,14 @@ fn parse_line<'a>(input: &mut &'a str) -> ModalResult<(&'a str, Option<&'a str>)
 
     alt((
         seq! {
-            take_until_ext(1.., alt((" ", "\n", "//"))).context(StrContext::Expected(StrContextValue::Description("priority: str"))),
+            key,
             _: space1,
             take_until_ext(1.., alt(("\n", "//")))
                 .context(StrContext::Expected(StrContextValue::Description("rename_to: &str"))),
         }
         .map(|(priority, name): (&str, &str)| (priority, Some(name.trim()))),
         //
-        take_until_ext(1.., alt((" ", "\n", "//"))).context(StrContext::Expected(StrContextValue::Description("priority: str")))
-        .map(|p| (p, None)),
+        key.map(|p| (p, None)),
     ))
     .context(StrContext::Label("mapping line"))
     .context(StrContext::Expected(StrContextValue::Description(
@@ -101,6 +103,19 @@ fn parse_line<'a>(input: &mut &'a str) -> ModalResult<(&'a str, Option<&'a str>)
     .parse_next(input)
 }
 
+/// `priority` or `plugin|FormID`
+fn key<'a>(input: &mut &'a str) -> ModalResult<&'a str> {
+    let token = || take_until_ext(1.., alt((" ", "\r", "\n", "//")));
+    let plugin =
+        take_until_ext(1.., alt(("|", "\r", "\n", "//"))).verify(|s: &str| is_plugin_name(s));
+
+    alt(((plugin, PLUGIN_KEY_SEPARATOR, token()).take(), token()))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "priority: str",
+        )))
+        .parse_next(input)
+}
+
 /// Comments starting with '//' until newline. 0 or more.
 fn line_comments0(input: &mut &str) -> ModalResult<()> {
     let _: () = repeat(0.., line_comment).parse_next(input)?;
@@ -158,4 +173,18 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn should_parse_plugin_key() {
+        let input = "Immersive Armors.esp|00012345 Guard\n00013BBF Lydia // FormID only\n";
+        let expected: MappingTable = [
+            (
+                "Immersive Armors.esp|00012345".to_string(),
+                "Guard".to_string(),
+            ),
+            ("00013BBF".to_string(), "Lydia".to_string()),
+        ]
+        .into();
+        assert_eq!(parse_mapping_table(input).ok(), Some(expected));
+    }
 }
@@ -0,0 +1,44 @@
+//! Write a [`MappingTable`] in the format [`parse_mapping_table`](crate::reader::parse_mapping_table) reads.
+use crate::MappingTable;
+use crate::document::MappingDocument;
+
+/// Write `table` as text, sorted by priority.
+///
+/// Entries without a name are written as comments, so that the user can fill them in.
+/// To edit an existing file without losing its comments, use [`MappingDocument`] instead.
+///
+/// # Example
+/// ```
+/// use mapping_table::{MappingTable, writer::write_mapping_table};
+///
+/// let table: MappingTable = [("200".into(), "Walk".into()), ("100".into(), String::new())].into();
+/// assert_eq!(write_mapping_table(&table), "// 100\n200 Walk\n");
+/// ```
+pub fn write_mapping_table(table: &MappingTable) -> String {
+    MappingDocument::from(table).to_string()
+}
+
+/// Order priorities numerically. (Works for both decimal and hex of the same width.)
+pub(crate) fn compare_priority(l: &str, r: &str) -> core::cmp::Ordering {
+    l.len().cmp(&r.len()).then_with(|| l.cmp(r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_mapping_table;
+
+    #[test]
+    fn should_read_written_table() {
+        let table: MappingTable = [
+            ("8000000".to_string(), "Combat".to_string()),
+            ("900".to_string(), "Sword+Shield".to_string()),
+            ("1000".to_string(), "Unarmed".to_string()),
+        ]
+        .into();
+
+        let text = write_mapping_table(&table);
+        assert_eq!(text, "900 Sword+Shield\n1000 Unarmed\n8000000 Combat\n");
+        assert_eq!(parse_mapping_table(&text).ok(), Some(table));
+    }
+}
@@ -0,0 +1,377 @@
+//! Builders of [`Oar`] conditions for generating OAR configs in Rust.
+//!
+//! `&` makes `AND`, `|` makes `OR` and `!`(or [`Not::not`]) negates. Like DAR, `&` binds tighter than `|`.
+//! ```
+//! use oar_conditions::cond::{self, Not as _};
+//! use oar_values::WeaponType;
+//!
+//! let conditions = cond::equipped_type(WeaponType::Dagger).left().not() & cond::is_sneaking()
+//!     | cond::random(0.3);
+//! assert_eq!(conditions.condition_name(), "OR");
+//! ```
+use crate::conditions::{
+    And, CompareValues, Condition, CurrentGameTime, CurrentWeather, FactionRank, HasKeyword,
+    HasMagicEffect, HasMagicEffectWithKeyword, HasPerk, HasRefType, HasSpell, IsActorBase, IsClass,
+    IsCombatStyle, IsEquipped, IsEquippedHasKeyword, IsEquippedShout, IsEquippedType, IsInFaction,
+    IsInLocation, IsMovementDirection, IsParentCell, IsRace, IsVoiceType, IsWorldSpace, IsWorn,
+    IsWornHasKeyword, Level, Oar, Or, PresetCondition, RandomCondition,
+};
+use oar_values::{
+    Cmp, Direction, DirectionValue, FormID, Keyword, NumericValue, PluginValue, StaticValue,
+    WeaponType,
+};
+use std::borrow::Cow;
+use std::ops::{BitAnd, BitOr};
+
+pub use std::ops::Not;
+
+/// A form of a plugin. e.g. `cond::form("Skyrim.esm", 0x13BBF)`
+pub fn form(plugin_name: &str, form_id: u32) -> PluginValue<'_> {
+    PluginValue {
+        plugin_name: plugin_name.into(),
+        form_id: FormID::from(format!("{form_id:X}")),
+    }
+}
+
+/// A static numeric value.
+pub fn value(value: f64) -> NumericValue<'static> {
+    StaticValue { value }.into()
+}
+
+/// A condition with no arguments. e.g. `cond::condition("IsBleedingOut")`
+pub fn condition(name: &str) -> Oar<'_> {
+    Oar::Condition(Condition::new(name))
+}
+
+/// Generate the builders of the conditions with no arguments.
+macro_rules! flag_conditions {
+    ($($fn_name:ident => $name:literal),+ $(,)?) => {
+        $(
+            #[doc = concat!("`", $name, "`")]
+            pub fn $fn_name() -> Oar<'static> {
+                condition($name)
+            }
+        )+
+    };
+}
+
+flag_conditions!(
+    is_attacking => "IsAttacking",
+    is_child => "IsChild",
+    is_female => "IsFemale",
+    is_in_air => "IsInAir",
+    is_in_combat => "IsInCombat",
+    is_in_interior => "IsInInterior",
+    is_player_teammate => "IsPlayerTeammate",
+    is_running => "IsRunning",
+    is_sneaking => "IsSneaking",
+    is_sprinting => "IsSprinting",
+    is_unique => "IsUnique",
+    is_weapon_drawn => "IsWeaponDrawn",
+);
+
+/// Generate the builders of the conditions with only a form.
+macro_rules! form_conditions {
+    ($($fn_name:ident => $variant:ident($field:ident)),+ $(,)?) => {
+        $(
+            #[doc = concat!("`", stringify!($variant), "`")]
+            pub fn $fn_name(form: PluginValue<'_>) -> Oar<'_> {
+                Oar::$variant($variant {
+                    $field: form,
+                    ..Default::default()
+                })
+            }
+        )+
+    };
+}
+
+form_conditions!(
+    current_weather => CurrentWeather(weather),
+    has_magic_effect => HasMagicEffect(magic_effect),
+    has_perk => HasPerk(perk),
+    has_spell => HasSpell(spell),
+    is_actor_base => IsActorBase(actor_base),
+    is_class => IsClass(class),
+    is_combat_style => IsCombatStyle(combat_style),
+    is_equipped => IsEquipped(form),
+    is_equipped_shout => IsEquippedShout(shout),
+    is_in_faction => IsInFaction(faction),
+    is_in_location => IsInLocation(location),
+    is_parent_cell => IsParentCell(cell),
+    is_race => IsRace(race),
+    is_voice_type => IsVoiceType(voice_type),
+    is_world_space => IsWorldSpace(world_space),
+    is_worn => IsWorn(form),
+);
+
+/// Generate the builders of the conditions with only a keyword.
+macro_rules! keyword_conditions {
+    ($($fn_name:ident => $variant:ident($field:ident)),+ $(,)?) => {
+        $(
+            #[doc = concat!("`", stringify!($variant), "`. The keyword is a form or an `editorID`.")]
+            pub fn $fn_name<'a>(keyword: impl Into<Keyword<'a>>) -> Oar<'a> {
+                Oar::$variant($variant {
+                    $field: keyword.into(),
+                    ..Default::default()
+                })
+            }
+        )+
+    };
+}
+
+keyword_conditions!(
+    has_keyword => HasKeyword(keyword),
+    has_magic_effect_with_keyword => HasMagicEffectWithKeyword(keyword),
+    has_ref_type => HasRefType(location_ref_type),
+    is_equipped_has_keyword => IsEquippedHasKeyword(keyword),
+    is_worn_has_keyword => IsWornHasKeyword(keyword),
+);
+
+/// `IsEquippedType`(right hand unless [`Oar::left`])
+pub fn equipped_type(weapon_type: WeaponType) -> Oar<'static> {
+    Oar::IsEquippedType(IsEquippedType {
+        type_value: weapon_type.into(),
+        ..Default::default()
+    })
+}
+
+/// `IsMovementDirection`
+pub fn movement_direction(direction: Direction) -> Oar<'static> {
+    Oar::IsDirectionMovement(IsMovementDirection {
+        direction: DirectionValue { value: direction },
+        ..Default::default()
+    })
+}
+
+/// `Random`: true with the `probability`(`0.0`-`1.0`).
+pub fn random(probability: f64) -> Oar<'static> {
+    Oar::RandomCondition(RandomCondition {
+        comparison: Cmp::Le,
+        numeric_value: value(probability),
+        ..Default::default()
+    })
+}
+
+/// `Level`: `actor level <comparison> level`
+pub fn level(comparison: Cmp, level: f64) -> Oar<'static> {
+    Oar::Level(Level {
+        comparison,
+        numeric_value: value(level),
+        ..Default::default()
+    })
+}
+
+/// `CurrentGameTime`: `game time <comparison> hours`
+pub fn current_game_time(comparison: Cmp, hours: f64) -> Oar<'static> {
+    Oar::CurrentGameTime(CurrentGameTime {
+        comparison,
+        numeric_value: value(hours),
+        ..Default::default()
+    })
+}
+
+/// `FactionRank`: `rank in faction <comparison> rank`
+pub fn faction_rank(faction: PluginValue<'_>, comparison: Cmp, rank: f64) -> Oar<'_> {
+    Oar::FactionRank(FactionRank {
+        faction,
+        comparison,
+        numeric_value: value(rank),
+        ..Default::default()
+    })
+}
+
+/// `CompareValues`: `a <comparison> b`
+pub fn compare_values<'a>(
+    a: impl Into<NumericValue<'a>>,
+    comparison: Cmp,
+    b: impl Into<NumericValue<'a>>,
+) -> Oar<'a> {
+    Oar::CompareValues(CompareValues {
+        value_a: a.into(),
+        comparison,
+        value_b: b.into(),
+        ..Default::default()
+    })
+}
+
+/// `PRESET`: refers to a condition preset of the namespace.
+pub fn preset(name: &str) -> Oar<'_> {
+    Oar::Preset(PresetCondition {
+        preset: name.into(),
+        ..Default::default()
+    })
+}
+
+/// `AND` of `conditions`
+pub fn and<'a>(conditions: impl IntoIterator<Item = Oar<'a>>) -> Oar<'a> {
+    Oar::And(And {
+        conditions: conditions.into_iter().collect(),
+        ..Default::default()
+    })
+}
+
+/// `OR` of `conditions`
+pub fn or<'a>(conditions: impl IntoIterator<Item = Oar<'a>>) -> Oar<'a> {
+    Oar::Or(Or {
+        conditions: conditions.into_iter().collect(),
+        ..Default::default()
+    })
+}
+
+impl Oar<'_> {
+    /// Check the left hand(`IsEquipped`, `IsEquippedType` and `IsEquippedHasKeyword`).
+    ///
+    /// Other conditions are returned as is.
+    #[must_use]
+    pub const fn left(mut self) -> Self {
+        match &mut self {
+            Self::IsEquipped(c) => c.left_hand = true,
+            Self::IsEquippedType(c) => c.left_hand = true,
+            Self::IsEquippedHasKeyword(c) => c.left_hand = true,
+            _ => {}
+        }
+        self
+    }
+
+    /// Set `requiredVersion`. e.g. `"1.1.0.0"`
+    #[must_use]
+    pub fn required_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        *self.required_version_mut() = version.into();
+        self
+    }
+}
+
+impl Not for Oar<'_> {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        let negated = self.negated_mut();
+        *negated = !*negated;
+        self
+    }
+}
+
+impl<'a> BitAnd for Oar<'a> {
+    type Output = Self;
+
+    /// `AND`. A non-negated `AND` operand is flattened. e.g. `a & b & c` -> `AND(a, b, c)`
+    fn bitand(self, rhs: Self) -> Self::Output {
+        let mut conditions = vec![];
+        for condition in [self, rhs] {
+            match condition {
+                Oar::And(and) if !and.negated => conditions.extend(and.conditions),
+                condition => conditions.push(condition),
+            }
+        }
+        and(conditions)
+    }
+}
+
+impl<'a> BitOr for Oar<'a> {
+    type Output = Self;
+
+    /// `OR`. A non-negated `OR` operand is flattened. e.g. `a | b | c` -> `OR(a, b, c)`
+    fn bitor(self, rhs: Self) -> Self::Output {
+        let mut conditions = vec![];
+        for condition in [self, rhs] {
+            match condition {
+                Oar::Or(or) if !or.negated => conditions.extend(or.conditions),
+                condition => conditions.push(condition),
+            }
+        }
+        or(conditions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conditions::ConditionsConfig;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_build_oar_tree() {
+        let actual = equipped_type(WeaponType::Dagger).left().not() & is_sneaking() | random(0.3);
+
+        let expected = Oar::Or(Or {
+            conditions: vec![
+                Oar::And(And {
+                    conditions: vec![
+                        Oar::IsEquippedType(IsEquippedType {
+                            negated: true,
+                            type_value: WeaponType::Dagger.into(),
+                            left_hand: true,
+                            ..Default::default()
+                        }),
+                        Oar::Condition(Condition::new("IsSneaking")),
+                    ],
+                    ..Default::default()
+                }),
+                Oar::RandomCondition(RandomCondition {
+                    comparison: Cmp::Le,
+                    numeric_value: StaticValue { value: 0.3 }.into(),
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_flatten_junctions() {
+        let actual = is_sneaking() & is_running() & !(is_in_combat() & is_female())
+            | is_child()
+            | is_unique();
+
+        let expected = or([
+            and([
+                is_sneaking(),
+                is_running(),
+                !and([is_in_combat(), is_female()]),
+            ]),
+            is_child(),
+            is_unique(),
+        ]);
+        assert_eq!(actual, expected);
+        assert_eq!(!!is_sneaking(), is_sneaking());
+    }
+
+    #[test]
+    fn should_build_conditions_config() {
+        let config = ConditionsConfig::builder("Sneak dagger")
+            .priority(100)
+            .condition(is_actor_base(form("Skyrim.esm", 0x7)) & is_sneaking())
+            .build();
+
+        let expected = r#"{
+  "name": "Sneak dagger",
+  "description": "",
+  "priority": 100,
+  "conditions": [
+    {
+      "condition": "AND",
+      "requiredVersion": "1.0.0.0",
+      "Conditions": [
+        {
+          "condition": "IsActorBase",
+          "requiredVersion": "1.0.0.0",
+          "Actor base": {
+            "pluginName": "Skyrim.esm",
+            "formID": "7"
+          }
+        },
+        {
+          "condition": "IsSneaking",
+          "requiredVersion": "1.0.0.0"
+        }
+      ]
+    }
+  ]
+}"#;
+        assert_eq!(
+            serde_json::to_string_pretty(&config).unwrap_or_default(),
+            expected
+        );
+    }
+}
@@ -13,6 +13,7 @@ use std::borrow::Cow;
 /// In DAR, AND is pushed up to the root conditions.
 /// The non-conditions definitions exist in anticipation of future OAR parsing.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct And<'a> {
     /// The name of the condition, which is "AND".
     pub condition: Cow<'a, str>,
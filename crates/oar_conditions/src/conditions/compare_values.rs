@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Structure comparing A and B
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CompareValues<'a> {
     /// Condition name "`CompareValues`"
     pub condition: Cow<'a, str>,
@@ -50,8 +51,8 @@ mod tests {
     use super::*;
     use crate::error::Result;
     use oar_values::{
-        ActorValue, ActorValueType, FormID, GraphValue, GraphVariableType, NumericValue,
-        PluginValue, StaticValue,
+        ActorValue, ActorValueIndex, ActorValueType, FormID, GraphValue, GraphVariableType,
+        NumericValue, PluginValue, StaticValue,
     };
     use pretty_assertions::assert_eq;
 
@@ -84,11 +85,11 @@ mod tests {
     fn should_stringify_compare_values_with_actor_value() -> Result<()> {
         let compare_values = CompareValues {
             value_a: NumericValue::ActorValue(ActorValue {
-                actor_value: 123,
+                actor_value: ActorValueIndex::Health,
                 actor_value_type: ActorValueType::Base,
             }),
             value_b: NumericValue::ActorValue(ActorValue {
-                actor_value: 456,
+                actor_value: ActorValueIndex::Stamina,
                 actor_value_type: ActorValueType::Max,
             }),
             comparison: Cmp::Ge,
@@ -100,12 +101,12 @@ mod tests {
   "condition": "CompareValues",
   "requiredVersion": "1.0.0.0",
   "Value A": {
-    "actorValue": 123,
+    "actorValue": 24,
     "actorValueType": "Base"
   },
   "Comparison": ">=",
   "Value B": {
-    "actorValue": 456,
+    "actorValue": 26,
     "actorValueType": "Max"
   }
 }"#;
@@ -14,6 +14,7 @@ pub const fn default_required_version() -> Cow<'static, str> {
 
 /// Represents a generic condition.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Condition<'a> {
     /// The name of the condition (e.g., "`IsWornHasKeyword`").
     #[serde(default)]
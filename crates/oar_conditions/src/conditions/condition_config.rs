@@ -5,6 +5,7 @@ use std::borrow::Cow;
 
 /// Represents the configuration for each animation root specified in a `config.json` file.
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ConditionsConfig<'a> {
     /// An arbitrary name given by the user (value in the mapping table).
     ///
@@ -26,3 +27,55 @@ pub struct ConditionsConfig<'a> {
     #[serde(default, borrow)]
     pub conditions: Vec<Oar<'a>>,
 }
+
+impl<'a> ConditionsConfig<'a> {
+    /// Start building a config of the animation root named `name`.
+    pub fn builder(name: impl Into<Cow<'a, str>>) -> ConditionsConfigBuilder<'a> {
+        ConditionsConfigBuilder(Self {
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Builder of [`ConditionsConfig`]. See [`ConditionsConfig::builder`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ConditionsConfigBuilder<'a>(ConditionsConfig<'a>);
+
+impl<'a> ConditionsConfigBuilder<'a> {
+    /// Set the description.
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.0.description = description.into();
+        self
+    }
+
+    /// Set the priority.
+    pub const fn priority(mut self, priority: i32) -> Self {
+        self.0.priority = priority;
+        self
+    }
+
+    /// Set `overrideAnimationsFolder`.
+    pub fn override_animations_folder(mut self, folder: impl Into<Cow<'a, str>>) -> Self {
+        self.0.override_animations_folder = Some(folder.into());
+        self
+    }
+
+    /// Push a condition. The root conditions are ANDed by OAR.
+    pub fn condition(mut self, condition: Oar<'a>) -> Self {
+        self.0.conditions.push(condition);
+        self
+    }
+
+    /// Push conditions.
+    pub fn conditions(mut self, conditions: impl IntoIterator<Item = Oar<'a>>) -> Self {
+        self.0.conditions.extend(conditions);
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> ConditionsConfig<'a> {
+        self.0
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 /// Represents a condition to check if the current weather matches a specified weather.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CurrentWeather<'a> {
     /// The name of the condition, which is "`CurrentWeather`".
     pub condition: Cow<'a, str>,
@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to test the reference's faction rank against a specified rank.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FactionRank<'a> {
     /// The name of the condition, which is "`FactionRank`".
     pub condition: Cow<'a, str>,
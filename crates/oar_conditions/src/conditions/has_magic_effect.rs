@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to check if an entity has a specific magic effect.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HasMagicEffect<'a> {
     /// The name of the condition, which is "`HasMagicEffect`".
     pub condition: Cow<'a, str>,
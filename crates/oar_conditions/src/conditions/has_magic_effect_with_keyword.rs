@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to check if an entity has a magic effect with a specific keyword.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HasMagicEffectWithKeyword<'a> {
     /// The name of the condition, which is "`HasMagicEffectWithKeyword`".
     ///
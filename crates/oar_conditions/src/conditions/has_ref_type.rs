@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to check if a reference has a specific type.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HasRefType<'a> {
     /// The name of the condition, which is "`HasRefType`".
     pub condition: Cow<'a, str>,
@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition based on whether an entity is equipped with a specific form.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IsEquipped<'a> {
     /// The name of the condition, which is "`IsEquipped`".
     pub condition: Cow<'a, str>,
@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to check if an equipped item has a specific keyword.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IsEquippedHasKeyword<'a> {
     /// The name of the condition, which is "`IsEquippedHasKeyword`".
     pub condition: Cow<'a, str>,
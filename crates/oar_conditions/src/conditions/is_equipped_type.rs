@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition to check if a specific type is equipped.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IsEquippedType<'a> {
     /// The name of the condition, which is "`IsEquippedType`".
     pub condition: Cow<'a, str>,
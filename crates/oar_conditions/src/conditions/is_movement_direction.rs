@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition based on the movement direction of an entity.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IsMovementDirection<'a> {
     /// The name of the condition, which is "`IsMovementDirection`".
     pub condition: Cow<'a, str>,
@@ -6,6 +6,7 @@ use std::borrow::Cow;
 
 /// Represents a condition based on whether an entity is worn and has a specific keyword.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IsWornHasKeyword<'a> {
     /// The name of the condition, which is "`IsWornHasKeyword`".
     pub condition: Cow<'a, str>,
@@ -17,22 +17,35 @@ mod is_movement_direction;
 mod is_worn_has_keyword;
 mod namespace_config;
 mod or;
+mod preset;
 mod random;
 
 pub use self::{
-    and::And, compare_values::CompareValues, condition::Condition,
-    condition_config::ConditionsConfig, current_weather::CurrentWeather, faction_rank::FactionRank,
-    has_keyword::HasKeyword, has_magic_effect::HasMagicEffect,
-    has_magic_effect_with_keyword::HasMagicEffectWithKeyword, has_perk::HasPerk,
-    has_ref_type::HasRefType, is_equipped::IsEquipped,
-    is_equipped_has_keyword::IsEquippedHasKeyword, is_equipped_type::IsEquippedType,
-    is_movement_direction::IsMovementDirection, is_worn_has_keyword::IsWornHasKeyword,
-    namespace_config::MainConfig, or::Or, random::RandomCondition,
+    and::And,
+    compare_values::CompareValues,
+    condition::Condition,
+    condition_config::{ConditionsConfig, ConditionsConfigBuilder},
+    current_weather::CurrentWeather,
+    faction_rank::FactionRank,
+    has_keyword::HasKeyword,
+    has_magic_effect::HasMagicEffect,
+    has_magic_effect_with_keyword::HasMagicEffectWithKeyword,
+    has_perk::HasPerk,
+    has_ref_type::HasRefType,
+    is_equipped::IsEquipped,
+    is_equipped_has_keyword::IsEquippedHasKeyword,
+    is_equipped_type::IsEquippedType,
+    is_movement_direction::IsMovementDirection,
+    is_worn_has_keyword::IsWornHasKeyword,
+    namespace_config::MainConfig,
+    or::Or,
+    preset::{ConditionPreset, PRESET_REQUIRED_VERSION, PresetCondition},
+    random::RandomCondition,
 };
 
 use self::condition::default_required_version;
 use oar_values::{Cmp, NumericValue, PluginValue};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error as _};
 use std::borrow::Cow;
 
 /// Returns `true` if the provided boolean value is `false`, otherwise `false`.
@@ -50,6 +63,7 @@ macro_rules! gen_cmp_num_struct {
       $(
         $(#[$attr])*
         #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
         pub struct $name<'a> {
             pub condition: Cow<'a, str>,
             #[serde(default = "default_required_version")]
@@ -98,6 +112,7 @@ macro_rules! gen_one_plugin_struct {
         $(
         $(#[$attr])*
         #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
         pub struct $name<'a> {
             pub condition: Cow<'a, str>,
             #[serde(default = "default_required_version")]
@@ -142,13 +157,20 @@ gen_one_plugin_struct!(
 );
 
 /// Represents a set of conditions that can be serialized to the OAR of functions present in the DAR.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// # NOTE
+/// Deserialization is keyed on `condition`(the condition name) instead of the untagged variant order.
+/// Unknown names fall back to [`Oar::Condition`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Oar<'a> {
     /// Represents a logical AND operation between conditions.
     #[serde(borrow)]
     And(And<'a>),
 
+    /// Refers to a condition preset of the namespace.
+    Preset(PresetCondition<'a>),
+
     /// Represents a single condition.
     Condition(Condition<'a>),
 
@@ -243,7 +265,131 @@ pub enum Oar<'a> {
     RandomCondition(RandomCondition<'a>),
 }
 
+/// Invoke `$callback` with the condition names and the [`Oar`] variants(and their types) dispatched by them.
+///
+/// The other names are [`Oar::Condition`].
+macro_rules! with_named_conditions {
+    ($callback:ident) => {
+        $callback!(
+            "AND" => And(And),
+            "OR" => Or(Or),
+            "PRESET" => Preset(PresetCondition),
+            "CompareValues" => CompareValues(CompareValues),
+            "CurrentGameTime" => CurrentGameTime(CurrentGameTime),
+            "CurrentWeather" => CurrentWeather(CurrentWeather),
+            "FactionRank" => FactionRank(FactionRank),
+            "HasKeyword" => HasKeyword(HasKeyword),
+            "HasMagicEffect" => HasMagicEffect(HasMagicEffect),
+            "HasMagicEffectWithKeyword" => HasMagicEffectWithKeyword(HasMagicEffectWithKeyword),
+            "HasPerk" => HasPerk(HasPerk),
+            "HasRefType" => HasRefType(HasRefType),
+            "HasSpell" => HasSpell(HasSpell),
+            "IsActorBase" => IsActorBase(IsActorBase),
+            "IsClass" => IsClass(IsClass),
+            "IsCombatStyle" => IsCombatStyle(IsCombatStyle),
+            "IsEquipped" => IsEquipped(IsEquipped),
+            "IsEquippedHasKeyword" => IsEquippedHasKeyword(IsEquippedHasKeyword),
+            "IsEquippedShout" => IsEquippedShout(IsEquippedShout),
+            "IsEquippedType" => IsEquippedType(IsEquippedType),
+            "IsInFaction" => IsInFaction(IsInFaction),
+            "IsInLocation" => IsInLocation(IsInLocation),
+            "IsMovementDirection" => IsDirectionMovement(IsMovementDirection),
+            "IsParentCell" => IsParentCell(IsParentCell),
+            "IsRace" => IsRace(IsRace),
+            "IsVoiceType" => IsVoiceType(IsVoiceType),
+            "IsWorldSpace" => IsWorldSpace(IsWorldSpace),
+            "IsWorn" => IsWorn(IsWorn),
+            "IsWornHasKeyword" => IsWornHasKeyword(IsWornHasKeyword),
+            "Level" => Level(Level),
+            "Random" => RandomCondition(RandomCondition),
+        )
+    };
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Oar<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let name = match value.get("condition") {
+            Some(serde_json::Value::String(name)) => name.clone(),
+            Some(_) => return Err(D::Error::custom("`condition` must be a string")),
+            None => return Err(D::Error::missing_field("condition")),
+        };
+
+        /// Deserialize the variant of the condition name.
+        macro_rules! dispatch {
+            ($($name:literal => $variant:ident($type:ident)),+ $(,)?) => {
+                match name.as_str() {
+                    $($name => Self::deserialize_variant(value, Self::$variant),)+
+                    _ => Self::deserialize_variant(value, Self::Condition),
+                }
+            };
+        }
+
+        with_named_conditions!(dispatch)
+            .map_err(|err| D::Error::custom(format_args!("`{name}`: {err}")))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Oar<'_> {
+    fn schema_name() -> Cow<'static, str> {
+        "Oar".into()
+    }
+
+    /// One of the conditions, selected by the `condition` name like the deserialization.
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        /// Restrict `condition` of the schema of `T`.
+        fn variant<T: schemars::JsonSchema>(
+            generator: &mut schemars::SchemaGenerator,
+            condition: serde_json::Value,
+        ) -> schemars::Schema {
+            schemars::json_schema!({
+                "allOf": [
+                    generator.subschema_for::<T>(),
+                    {
+                        "properties": { "condition": condition },
+                        "required": ["condition"],
+                    },
+                ],
+            })
+        }
+
+        macro_rules! variants {
+            ($($name:literal => $variant:ident($type:ident)),+ $(,)?) => {
+                vec![
+                    $(variant::<$type<'_>>(generator, serde_json::json!({ "const": $name })),)+
+                    // Any other condition, e.g. `IsSneaking` or `IsBleedingOut`
+                    variant::<Condition<'_>>(
+                        generator,
+                        serde_json::json!({ "type": "string", "not": { "enum": [$($name),+] } }),
+                    ),
+                ]
+            };
+        }
+
+        let variants: Vec<_> = with_named_conditions!(variants);
+        schemars::json_schema!({
+            "description": "A condition. `condition` selects the kind of the rest of the fields.",
+            "oneOf": variants,
+        })
+    }
+}
+
 impl<'a> Oar<'a> {
+    /// Deserialize `value` as the inner condition of `variant`.
+    fn deserialize_variant<T>(
+        value: serde_json::Value,
+        variant: fn(T) -> Self,
+    ) -> Result<Self, serde_json::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        T::deserialize(value).map(variant)
+    }
+
     /// Into inner [`Vec`]
     ///
     /// # Errors
@@ -256,4 +402,118 @@ impl<'a> Oar<'a> {
             _ => return Err(crate::error::Error::CastError),
         })
     }
+
+    /// Condition name. e.g. `IsActorBase`
+    pub fn condition_name(&self) -> &str {
+        match self {
+            Self::And(c) => &c.condition,
+            Self::Preset(c) => &c.condition,
+            Self::Condition(c) => &c.condition,
+            Self::CompareValues(c) => &c.condition,
+            Self::CurrentGameTime(c) => &c.condition,
+            Self::CurrentWeather(c) => &c.condition,
+            Self::FactionRank(c) => &c.condition,
+            Self::HasKeyword(c) => &c.condition,
+            Self::HasMagicEffect(c) => &c.condition,
+            Self::HasMagicEffectWithKeyword(c) => &c.condition,
+            Self::HasPerk(c) => &c.condition,
+            Self::HasRefType(c) => &c.condition,
+            Self::HasSpell(c) => &c.condition,
+            Self::IsActorBase(c) => &c.condition,
+            Self::IsClass(c) => &c.condition,
+            Self::IsCombatStyle(c) => &c.condition,
+            Self::IsEquipped(c) => &c.condition,
+            Self::IsEquippedHasKeyword(c) => &c.condition,
+            Self::IsEquippedShout(c) => &c.condition,
+            Self::IsEquippedType(c) => &c.condition,
+            Self::IsInFaction(c) => &c.condition,
+            Self::IsInLocation(c) => &c.condition,
+            Self::IsParentCell(c) => &c.condition,
+            Self::IsRace(c) => &c.condition,
+            Self::IsVoiceType(c) => &c.condition,
+            Self::IsWorldSpace(c) => &c.condition,
+            Self::IsWorn(c) => &c.condition,
+            Self::IsWornHasKeyword(c) => &c.condition,
+            Self::IsDirectionMovement(c) => &c.condition,
+            Self::Level(c) => &c.condition,
+            Self::Or(c) => &c.condition,
+            Self::RandomCondition(c) => &c.condition,
+        }
+    }
+
+    /// `requiredVersion` of the condition.
+    pub const fn required_version_mut(&mut self) -> &mut Cow<'a, str> {
+        match self {
+            Self::And(c) => &mut c.required_version,
+            Self::Preset(c) => &mut c.required_version,
+            Self::Condition(c) => &mut c.required_version,
+            Self::CompareValues(c) => &mut c.required_version,
+            Self::CurrentGameTime(c) => &mut c.required_version,
+            Self::CurrentWeather(c) => &mut c.required_version,
+            Self::FactionRank(c) => &mut c.required_version,
+            Self::HasKeyword(c) => &mut c.required_version,
+            Self::HasMagicEffect(c) => &mut c.required_version,
+            Self::HasMagicEffectWithKeyword(c) => &mut c.required_version,
+            Self::HasPerk(c) => &mut c.required_version,
+            Self::HasRefType(c) => &mut c.required_version,
+            Self::HasSpell(c) => &mut c.required_version,
+            Self::IsActorBase(c) => &mut c.required_version,
+            Self::IsClass(c) => &mut c.required_version,
+            Self::IsCombatStyle(c) => &mut c.required_version,
+            Self::IsEquipped(c) => &mut c.required_version,
+            Self::IsEquippedHasKeyword(c) => &mut c.required_version,
+            Self::IsEquippedShout(c) => &mut c.required_version,
+            Self::IsEquippedType(c) => &mut c.required_version,
+            Self::IsInFaction(c) => &mut c.required_version,
+            Self::IsInLocation(c) => &mut c.required_version,
+            Self::IsParentCell(c) => &mut c.required_version,
+            Self::IsRace(c) => &mut c.required_version,
+            Self::IsVoiceType(c) => &mut c.required_version,
+            Self::IsWorldSpace(c) => &mut c.required_version,
+            Self::IsWorn(c) => &mut c.required_version,
+            Self::IsWornHasKeyword(c) => &mut c.required_version,
+            Self::IsDirectionMovement(c) => &mut c.required_version,
+            Self::Level(c) => &mut c.required_version,
+            Self::Or(c) => &mut c.required_version,
+            Self::RandomCondition(c) => &mut c.required_version,
+        }
+    }
+
+    /// `negated` of the condition.
+    pub const fn negated_mut(&mut self) -> &mut bool {
+        match self {
+            Self::And(c) => &mut c.negated,
+            Self::Preset(c) => &mut c.negated,
+            Self::Condition(c) => &mut c.negated,
+            Self::CompareValues(c) => &mut c.negated,
+            Self::CurrentGameTime(c) => &mut c.negated,
+            Self::CurrentWeather(c) => &mut c.negated,
+            Self::FactionRank(c) => &mut c.negated,
+            Self::HasKeyword(c) => &mut c.negated,
+            Self::HasMagicEffect(c) => &mut c.negated,
+            Self::HasMagicEffectWithKeyword(c) => &mut c.negated,
+            Self::HasPerk(c) => &mut c.negated,
+            Self::HasRefType(c) => &mut c.negated,
+            Self::HasSpell(c) => &mut c.negated,
+            Self::IsActorBase(c) => &mut c.negated,
+            Self::IsClass(c) => &mut c.negated,
+            Self::IsCombatStyle(c) => &mut c.negated,
+            Self::IsEquipped(c) => &mut c.negated,
+            Self::IsEquippedHasKeyword(c) => &mut c.negated,
+            Self::IsEquippedShout(c) => &mut c.negated,
+            Self::IsEquippedType(c) => &mut c.negated,
+            Self::IsInFaction(c) => &mut c.negated,
+            Self::IsInLocation(c) => &mut c.negated,
+            Self::IsParentCell(c) => &mut c.negated,
+            Self::IsRace(c) => &mut c.negated,
+            Self::IsVoiceType(c) => &mut c.negated,
+            Self::IsWorldSpace(c) => &mut c.negated,
+            Self::IsWorn(c) => &mut c.negated,
+            Self::IsWornHasKeyword(c) => &mut c.negated,
+            Self::IsDirectionMovement(c) => &mut c.negated,
+            Self::Level(c) => &mut c.negated,
+            Self::Or(c) => &mut c.negated,
+            Self::RandomCondition(c) => &mut c.negated,
+        }
+    }
 }
@@ -1,18 +1,27 @@
 //! Specifically for the 'config.json' namespace.
+use super::ConditionPreset;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// Represents the configuration structure for the 'config.json' namespace.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MainConfig<'config> {
     /// The name associated with the configuration.
     #[serde(default)]
-    pub name: &'config str,
+    pub name: Cow<'config, str>,
 
     /// The description associated with the configuration.
     #[serde(default)]
-    pub description: &'config str,
+    pub description: Cow<'config, str>,
 
     /// The author associated with the configuration.
     #[serde(default)]
-    pub author: &'config str,
+    pub author: Cow<'config, str>,
+
+    /// Condition presets shared by the sections of the namespace.
+    #[serde(default, borrow)]
+    #[serde(rename = "conditionPresets")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub condition_presets: Vec<ConditionPreset<'config>>,
 }
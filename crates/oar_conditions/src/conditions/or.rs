@@ -8,6 +8,7 @@ use std::borrow::Cow;
 /// - OAR: OR
 /// - DAR: `fn_name() OR`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Or<'a> {
     /// The name of the condition, which is "OR".
     pub condition: Cow<'a, str>,
@@ -0,0 +1,89 @@
+//! Condition presets defined in the namespace `config.json` and the condition referring to them.
+use super::{Oar, is_false};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// OAR version that introduced condition presets.
+pub const PRESET_REQUIRED_VERSION: &str = "2.3.0.0";
+
+const fn default_preset_required_version() -> Cow<'static, str> {
+    Cow::Borrowed(PRESET_REQUIRED_VERSION)
+}
+
+/// A named set of conditions shared by the sections of a namespace.
+///
+/// - OAR: an entry of `conditionPresets` in the namespace `config.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ConditionPreset<'a> {
+    /// Preset name referred to by [`PresetCondition::preset`]
+    #[serde(default)]
+    pub name: Cow<'a, str>,
+    /// The description of the preset.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub description: Cow<'a, str>,
+    /// Conditions of the preset(all of them must be true).
+    #[serde(default, borrow)]
+    pub conditions: Vec<Oar<'a>>,
+}
+
+/// Refers to a [`ConditionPreset`] of the namespace.
+///
+/// - OAR: PRESET
+/// - DAR: None
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PresetCondition<'a> {
+    /// The name of the condition, which is "PRESET".
+    pub condition: Cow<'a, str>,
+    /// The required version for this condition.
+    #[serde(default = "default_preset_required_version")]
+    #[serde(rename = "requiredVersion")]
+    pub required_version: Cow<'a, str>,
+    /// Indicates whether the condition is negated or not.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub negated: bool,
+
+    /// Name of the preset
+    #[serde(rename = "Preset")]
+    pub preset: Cow<'a, str>,
+}
+
+impl Default for PresetCondition<'_> {
+    fn default() -> Self {
+        Self {
+            condition: "PRESET".into(),
+            required_version: default_preset_required_version(),
+            negated: Default::default(),
+            preset: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conditions::Condition;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_deserialize_preset_before_generic_condition() -> Result<(), serde_json::Error> {
+        let json = r#"[
+  { "condition": "PRESET", "requiredVersion": "2.3.0.0", "Preset": "Guards" },
+  { "condition": "IsSneaking", "requiredVersion": "1.0.0.0" }
+]"#;
+        let actual: Vec<Oar> = serde_json::from_str(json)?;
+
+        let expected = vec![
+            Oar::Preset(PresetCondition {
+                preset: "Guards".into(),
+                ..Default::default()
+            }),
+            Oar::Condition(Condition::new("IsSneaking")),
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use std::borrow::Cow;
 ///
 /// - OAR (Object Arithmetic Representation): Random
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RandomCondition<'a> {
     /// The name of the condition, which is "Random".
     pub condition: Cow<'a, str>,
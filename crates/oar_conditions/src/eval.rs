@@ -0,0 +1,778 @@
+//! Evaluate OAR conditions against a mock actor/world [`Snapshot`], without launching the game.
+//!
+//! This is an approximation of OAR for regression tests of condition edits:
+//! only the state described in the snapshot is known, and missing numbers are `0`.
+use crate::conditions::{ConditionPreset, ConditionsConfig, MainConfig, Oar};
+use oar_values::{
+    ActorValueIndex, ActorValueType, Cmp, Direction, FormValue, Keyword, NumericValue, PluginValue,
+    WeaponType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Actor & world state to evaluate the conditions against. Usually deserialized from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Snapshot {
+    /// `IsActorBase`
+    pub actor_base: Option<Form>,
+    /// `IsRace`
+    pub race: Option<Form>,
+    /// `IsClass`
+    pub class: Option<Form>,
+    /// `IsCombatStyle`
+    pub combat_style: Option<Form>,
+    /// `IsVoiceType`
+    pub voice_type: Option<Form>,
+    /// `HasKeyword`
+    pub keywords: Vec<Form>,
+    /// `IsEquipped*`
+    pub equipped: Equipped,
+    /// `IsWorn`, `IsWornHasKeyword`
+    pub worn: Vec<Item>,
+    /// `HasPerk`
+    pub perks: Vec<Form>,
+    /// `HasSpell`
+    pub spells: Vec<Form>,
+    /// `HasMagicEffect`, `HasMagicEffectWithKeyword`
+    pub magic_effects: Vec<Item>,
+    /// `IsInFaction`, `FactionRank`
+    pub factions: Vec<Faction>,
+    /// Actor values used by `CompareValues`
+    pub actor_values: Vec<ActorValueState>,
+    /// Global variables used by numeric values
+    pub globals: Vec<Global>,
+    /// Graph variables used by numeric values. name -> value
+    pub graph_variables: HashMap<String, f64>,
+    /// `Level`
+    pub level: f64,
+    /// `CurrentWeather`
+    pub weather: Option<Form>,
+    /// `IsInLocation`
+    pub location: Option<Form>,
+    /// `HasRefType`
+    pub location_ref_types: Vec<Form>,
+    /// `IsParentCell`
+    pub cell: Option<Form>,
+    /// `IsWorldSpace`
+    pub world_space: Option<Form>,
+    /// `CurrentGameTime` in hours. e.g. `13.5`
+    pub game_time: f64,
+    /// `IsMovementDirection`: 0 = none, 1 = forward, 2 = right, 3 = back, 4 = left
+    pub movement_direction: f64,
+    /// Conditions without arguments. e.g. `IsSneaking`
+    pub flags: Flags,
+}
+
+/// A form. Matched by `plugin` & `formID`, or by `editorID` for the keywords written as EditorID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Form {
+    /// e.g. `Skyrim.esm`
+    pub plugin: String,
+    /// Hexadecimal without prefix. e.g. `13BBF`
+    #[serde(rename = "formID")]
+    pub form_id: String,
+    /// e.g. `WeapTypeDagger`
+    #[serde(rename = "editorID")]
+    pub editor_id: Option<String>,
+}
+
+/// Equipped items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Equipped {
+    /// Right hand
+    pub right: Option<Item>,
+    /// Left hand
+    pub left: Option<Item>,
+    /// `IsEquippedShout`
+    pub shout: Option<Form>,
+}
+
+/// An item(or magic effect) with its keywords.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Item {
+    /// The item itself
+    pub form: Form,
+    /// Keywords of the item
+    pub keywords: Vec<Form>,
+    /// `IsEquippedType`. e.g. `2.0`(Dagger). See [`WeaponType`].
+    pub weapon_type: f32,
+}
+
+/// A faction the actor is in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Faction {
+    /// The faction
+    pub form: Form,
+    /// Rank in the faction
+    pub rank: f64,
+}
+
+/// Values of an actor value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActorValueState {
+    /// Actor value index. e.g. `24`(Health)
+    pub index: ActorValueIndex,
+    /// Current value
+    pub value: f64,
+    /// Base value
+    pub base: f64,
+    /// Max value
+    pub max: f64,
+}
+
+/// A global variable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Global {
+    /// The `GLOB` form
+    pub form: Form,
+    /// Value
+    pub value: f64,
+}
+
+/// State for the conditions without arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Flags {
+    /// `IsFemale`
+    pub female: bool,
+    /// `IsChild`
+    pub child: bool,
+    /// `IsPlayerTeammate`
+    pub player_teammate: bool,
+    /// `IsInInterior`
+    pub in_interior: bool,
+    /// `IsUnique`
+    pub unique: bool,
+    /// `IsAttacking`
+    pub attacking: bool,
+    /// `IsRunning`
+    pub running: bool,
+    /// `IsSneaking`
+    pub sneaking: bool,
+    /// `IsSprinting`
+    pub sprinting: bool,
+    /// `IsInAir`
+    pub in_air: bool,
+    /// `IsInCombat`
+    pub in_combat: bool,
+    /// `IsWeaponDrawn`
+    pub weapon_drawn: bool,
+}
+
+/// Evaluation of a condition node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    /// Condition name. e.g. `IsRace`
+    pub condition: String,
+    /// Whether the result was negated
+    pub negated: bool,
+    /// Result after the negation
+    pub result: bool,
+    /// Why it failed, if the state was not in the snapshot. e.g. `unknown condition`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Traces of `AND`/`OR` children
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Self>,
+}
+
+/// Source of the random number for `RandomCondition`. Returns a value in `min..=max`.
+pub trait RandomSource {
+    /// Next random number in `min..=max`.
+    fn next(&mut self, min: f32, max: f32) -> f32;
+}
+
+impl<F> RandomSource for F
+where
+    F: FnMut(f32, f32) -> f32,
+{
+    fn next(&mut self, min: f32, max: f32) -> f32 {
+        self(min, max)
+    }
+}
+
+/// Evaluate `conditions`(all of them must be true, as in `config.json`) against `snapshot`.
+///
+/// `PRESET`s are resolved with `presets`(`conditionPresets` of the namespace).
+///
+/// Returns the result and the trace of each top-level condition.
+pub fn evaluate(
+    conditions: &[Oar<'_>],
+    presets: &[ConditionPreset<'_>],
+    snapshot: &Snapshot,
+    random: &mut impl RandomSource,
+) -> (bool, Vec<Trace>) {
+    let mut evaluator = Evaluator {
+        snapshot,
+        presets,
+        resolving: vec![],
+        random,
+    };
+    let traces: Vec<_> = conditions
+        .iter()
+        .map(|condition| evaluator.eval(condition))
+        .collect();
+    (traces.iter().all(|trace| trace.result), traces)
+}
+
+/// A namespace(mod): its `config.json` and the `config.json`s of its sections.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Namespace<'c> {
+    /// Namespace `config.json`, holding the condition presets
+    pub config: MainConfig<'c>,
+    /// Sections of the namespace
+    pub sections: Vec<ConditionsConfig<'c>>,
+}
+
+/// A section and its evaluation, in the order OAR checks them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionResult<'c> {
+    /// Namespace name
+    pub namespace: &'c str,
+    /// Section name
+    pub name: &'c str,
+    /// Section priority
+    pub priority: i32,
+    /// Whether the conditions are true
+    pub result: bool,
+    /// Trace of the conditions
+    pub trace: Vec<Trace>,
+}
+
+/// Result of [`resolve_winner`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Resolution<'c> {
+    /// The section that would play, or `None` for the vanilla(or DAR) animation.
+    pub winner: Option<SectionResult<'c>>,
+    /// All sections by descending priority
+    pub sections: Vec<SectionResult<'c>>,
+}
+
+/// Find the section that would play: the highest priority one whose conditions are true.
+///
+/// `namespaces` are all the installed mods. Sections of the same priority keep the given order.
+/// `PRESET`s are resolved with the presets of the section's namespace.
+pub fn resolve_winner<'c>(
+    namespaces: impl IntoIterator<Item = &'c Namespace<'c>>,
+    snapshot: &Snapshot,
+    random: &mut impl RandomSource,
+) -> Resolution<'c> {
+    let mut sections: Vec<_> = namespaces
+        .into_iter()
+        .flat_map(|namespace| {
+            (namespace.sections.iter()).map(move |section| (&namespace.config, section))
+        })
+        .collect();
+    sections.sort_by_key(|(_, section)| core::cmp::Reverse(section.priority));
+
+    let sections: Vec<_> = sections
+        .into_iter()
+        .map(|(config, section)| {
+            let (result, trace) = evaluate(
+                &section.conditions,
+                &config.condition_presets,
+                snapshot,
+                random,
+            );
+            SectionResult {
+                namespace: config.name.as_ref(),
+                name: section.name.as_ref(),
+                priority: section.priority,
+                result,
+                trace,
+            }
+        })
+        .collect();
+    Resolution {
+        winner: sections.iter().find(|section| section.result).cloned(),
+        sections,
+    }
+}
+
+struct Evaluator<'s, 'p, R> {
+    snapshot: &'s Snapshot,
+    presets: &'s [ConditionPreset<'p>],
+    /// Names of the presets being evaluated, to stop at a recursive reference.
+    resolving: Vec<String>,
+    random: &'s mut R,
+}
+
+impl<R: RandomSource> Evaluator<'_, '_, R> {
+    fn eval(&mut self, condition: &Oar<'_>) -> Trace {
+        let s = self.snapshot;
+        let mut note = None;
+        let mut children = vec![];
+
+        let (name, negated, result) = match condition {
+            Oar::And(and) => {
+                children = and.conditions.iter().map(|c| self.eval(c)).collect();
+                let result = children.iter().all(|trace| trace.result);
+                (and.condition.as_ref(), and.negated, result)
+            }
+            Oar::Or(or) => {
+                children = or.conditions.iter().map(|c| self.eval(c)).collect();
+                let result = children.iter().any(|trace| trace.result);
+                (or.condition.as_ref(), or.negated, result)
+            }
+            Oar::Preset(c) => {
+                let presets = self.presets;
+                let preset = presets.iter().find(|preset| preset.name == c.preset);
+                let result = match preset {
+                    None => {
+                        note = Some(format!("preset `{}` is not found", c.preset));
+                        false
+                    }
+                    Some(_) if self.resolving.iter().any(|name| *name == c.preset) => {
+                        note = Some(format!("preset `{}` refers to itself", c.preset));
+                        false
+                    }
+                    Some(preset) => {
+                        self.resolving.push(c.preset.to_string());
+                        children = preset.conditions.iter().map(|c| self.eval(c)).collect();
+                        self.resolving.pop();
+                        children.iter().all(|trace| trace.result)
+                    }
+                };
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::Condition(c) => {
+                let result = flag(&s.flags, &c.condition);
+                if result.is_none() {
+                    note = Some("unknown condition".to_string());
+                }
+                (c.condition.as_ref(), c.negated, result.unwrap_or(false))
+            }
+            Oar::CompareValues(c) => {
+                let (a, b) = (self.number(&c.value_a), self.number(&c.value_b));
+                let actor_values: Vec<_> = [(&c.value_a, a), (&c.value_b, b)]
+                    .into_iter()
+                    .filter_map(|(value, number)| match value {
+                        NumericValue::ActorValue(value) => Some(format!(
+                            "{}({:?}) = {number}",
+                            value.actor_value, value.actor_value_type
+                        )),
+                        _ => None,
+                    })
+                    .collect();
+                if !actor_values.is_empty() {
+                    note = Some(actor_values.join(", "));
+                }
+                (
+                    c.condition.as_ref(),
+                    c.negated,
+                    compare(a, &c.comparison, b),
+                )
+            }
+            Oar::CurrentGameTime(c) => {
+                let value = self.number(&c.numeric_value);
+                (
+                    c.condition.as_ref(),
+                    c.negated,
+                    compare(s.game_time, &c.comparison, value),
+                )
+            }
+            Oar::Level(c) => {
+                let value = self.number(&c.numeric_value);
+                (
+                    c.condition.as_ref(),
+                    c.negated,
+                    compare(s.level, &c.comparison, value),
+                )
+            }
+            Oar::RandomCondition(c) => {
+                let random = self.random.next(c.random_value.min, c.random_value.max);
+                let value = self.number(&c.numeric_value);
+                let result = compare(f64::from(random), &c.comparison, value);
+                note = Some(format!("random: {random}"));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::FactionRank(c) => {
+                let value = self.number(&c.numeric_value);
+                let result = s
+                    .factions
+                    .iter()
+                    .find(|faction| faction.form.is(&c.faction))
+                    .is_some_and(|faction| compare(faction.rank, &c.comparison, value));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::CurrentWeather(c) => (c.condition.as_ref(), c.negated, is(&s.weather, &c.weather)),
+            Oar::HasKeyword(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                has(&s.keywords, &c.keyword),
+            ),
+            Oar::HasMagicEffect(c) => {
+                let result = s
+                    .magic_effects
+                    .iter()
+                    .any(|effect| effect.form.is(&c.magic_effect));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::HasMagicEffectWithKeyword(c) => {
+                let result = s
+                    .magic_effects
+                    .iter()
+                    .any(|effect| has(&effect.keywords, &c.keyword));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::HasPerk(c) => (c.condition.as_ref(), c.negated, any(&s.perks, &c.perk)),
+            Oar::HasRefType(c) => {
+                let result = has(&s.location_ref_types, &c.location_ref_type);
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::HasSpell(c) => (c.condition.as_ref(), c.negated, any(&s.spells, &c.spell)),
+            Oar::IsActorBase(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                is(&s.actor_base, &c.actor_base),
+            ),
+            Oar::IsClass(c) => (c.condition.as_ref(), c.negated, is(&s.class, &c.class)),
+            Oar::IsCombatStyle(c) => {
+                let result = is(&s.combat_style, &c.combat_style);
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsEquipped(c) => {
+                let result =
+                    hand(&s.equipped, c.left_hand).is_some_and(|item| item.form.is(&c.form));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsEquippedHasKeyword(c) => {
+                let result = hand(&s.equipped, c.left_hand)
+                    .is_some_and(|item| has(&item.keywords, &c.keyword));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsEquippedShout(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                is(&s.equipped.shout, &c.shout),
+            ),
+            Oar::IsEquippedType(c) => {
+                // An empty hand is unarmed.
+                let weapon_type =
+                    hand(&s.equipped, c.left_hand).map_or(0.0, |item| item.weapon_type);
+                let result =
+                    WeaponType::try_from(weapon_type).ok() == Some(c.type_value.value.clone());
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsInFaction(c) => {
+                let result = s.factions.iter().any(|faction| faction.form.is(&c.faction));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsInLocation(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                is(&s.location, &c.location),
+            ),
+            Oar::IsParentCell(c) => (c.condition.as_ref(), c.negated, is(&s.cell, &c.cell)),
+            Oar::IsRace(c) => (c.condition.as_ref(), c.negated, is(&s.race, &c.race)),
+            Oar::IsVoiceType(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                is(&s.voice_type, &c.voice_type),
+            ),
+            Oar::IsWorldSpace(c) => (
+                c.condition.as_ref(),
+                c.negated,
+                is(&s.world_space, &c.world_space),
+            ),
+            Oar::IsWorn(c) => {
+                let result = s.worn.iter().any(|item| item.form.is(&c.form));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsWornHasKeyword(c) => {
+                let result = s.worn.iter().any(|item| has(&item.keywords, &c.keyword));
+                (c.condition.as_ref(), c.negated, result)
+            }
+            Oar::IsDirectionMovement(c) => {
+                let direction = Direction::try_from(s.movement_direction).ok();
+                let result = direction == Some(c.direction.value.clone());
+                (c.condition.as_ref(), c.negated, result)
+            }
+        };
+
+        Trace {
+            condition: name.to_string(),
+            negated,
+            result: result != negated,
+            note,
+            children,
+        }
+    }
+
+    /// Resolve a numeric value. Values not in the snapshot are `0`.
+    fn number(&self, value: &NumericValue<'_>) -> f64 {
+        let s = self.snapshot;
+        match value {
+            NumericValue::StaticValue(value) => value.value,
+            NumericValue::GlobalVariable(FormValue { form }) => s
+                .globals
+                .iter()
+                .find(|global| global.form.is(form))
+                .map_or(0.0, |global| global.value),
+            NumericValue::ActorValue(value) => s
+                .actor_values
+                .iter()
+                .find(|state| state.index == value.actor_value)
+                .map_or(0.0, |state| match value.actor_value_type {
+                    ActorValueType::ActorValue => state.value,
+                    ActorValueType::Base => state.base,
+                    ActorValueType::Max => state.max,
+                    ActorValueType::Percentage if state.max == 0.0 => 0.0,
+                    ActorValueType::Percentage => state.value / state.max,
+                }),
+            NumericValue::GraphVariable(value) => s
+                .graph_variables
+                .get(value.graph_variable.as_ref())
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Form {
+    /// Is this `form`? The plugin name is case insensitive, and the FormIDs are compared as numbers.
+    pub fn is(&self, form: &PluginValue<'_>) -> bool {
+        let parse = |id: &str| u32::from_str_radix(id.trim_start_matches("0x"), 16).ok();
+        self.plugin.eq_ignore_ascii_case(&form.plugin_name)
+            && parse(&self.form_id).is_some_and(|id| Some(id) == parse(form.form_id.as_str()))
+    }
+
+    /// Is this `keyword`? The keyword may be written as form or EditorID.
+    pub fn is_keyword(&self, keyword: &Keyword<'_>) -> bool {
+        match keyword {
+            Keyword::Form(FormValue { form }) => self.is(form),
+            Keyword::Literal(literal) => self
+                .editor_id
+                .as_deref()
+                .is_some_and(|editor_id| editor_id.eq_ignore_ascii_case(&literal.editor_id)),
+        }
+    }
+}
+
+fn is(form: &Option<Form>, expected: &PluginValue<'_>) -> bool {
+    form.as_ref().is_some_and(|form| form.is(expected))
+}
+
+fn any(forms: &[Form], expected: &PluginValue<'_>) -> bool {
+    forms.iter().any(|form| form.is(expected))
+}
+
+fn has(forms: &[Form], keyword: &Keyword<'_>) -> bool {
+    forms.iter().any(|form| form.is_keyword(keyword))
+}
+
+const fn hand(equipped: &Equipped, left_hand: bool) -> Option<&Item> {
+    match left_hand {
+        true => equipped.left.as_ref(),
+        false => equipped.right.as_ref(),
+    }
+}
+
+fn compare(a: f64, comparison: &Cmp, b: f64) -> bool {
+    match comparison {
+        Cmp::Eq => (a - b).abs() < f64::EPSILON,
+        Cmp::Ne => (a - b).abs() >= f64::EPSILON,
+        Cmp::Gt => a > b,
+        Cmp::Lt => a < b,
+        Cmp::Ge => a >= b,
+        Cmp::Le => a <= b,
+    }
+}
+
+/// State of the conditions without arguments, or `None` if unknown.
+fn flag(flags: &Flags, condition: &str) -> Option<bool> {
+    Some(match condition {
+        "IsFemale" => flags.female,
+        "IsChild" => flags.child,
+        "IsPlayerTeammate" => flags.player_teammate,
+        "IsInInterior" => flags.in_interior,
+        "IsUnique" => flags.unique,
+        "IsAttacking" => flags.attacking,
+        "IsRunning" => flags.running,
+        "IsSneaking" => flags.sneaking,
+        "IsSprinting" => flags.sprinting,
+        "IsInAir" => flags.in_air,
+        "IsInCombat" => flags.in_combat,
+        "IsWeaponDrawn" => flags.weapon_drawn,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(dar: &str) -> Vec<Oar<'_>> {
+        match dar_syntax::parse_dar_syntax(dar) {
+            Ok(dar) => Oar::from(dar).into_vec().unwrap_or_default(),
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn snapshot() -> Result<Snapshot, serde_json::Error> {
+        serde_json::from_str(
+            r#"{
+  "race": { "plugin": "Skyrim.esm", "formID": "13746" },
+  "equipped": {
+    "right": {
+      "form": { "plugin": "Skyrim.esm", "formID": "1397E" },
+      "keywords": [{ "plugin": "Skyrim.esm", "formID": "1E713", "editorID": "WeapTypeDagger" }],
+      "weaponType": 2.0
+    }
+  },
+  "actorValues": [{ "index": 24, "value": 50.0, "max": 100.0 }],
+  "flags": { "sneaking": true }
+}"#,
+        )
+    }
+
+    #[test]
+    fn should_evaluate_conditions_with_trace() -> Result<(), serde_json::Error> {
+        let conditions = parse(
+            r#"IsEquippedRightType(2) AND
+IsRace("Skyrim.esm" | 0x00013746) AND
+IsActorValueLessThan(24, 60) AND
+NOT IsInCombat() AND
+IsFemale() OR
+IsSneaking()"#,
+        );
+        let (result, traces) = evaluate(&conditions, &[], &snapshot()?, &mut |_, _| 0.0);
+
+        assert!(result, "{traces:#?}");
+        let results: Vec<_> = traces.iter().map(|trace| trace.result).collect();
+        assert_eq!(results, [true, true, true, true, true]);
+        assert_eq!(traces[2].note.as_deref(), Some("Health(ActorValue) = 50"));
+        let or = &traces[4];
+        assert_eq!(
+            or.children.iter().map(|t| t.result).collect::<Vec<_>>(),
+            [false, true]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_resolve_winner_by_priority() -> Result<(), serde_json::Error> {
+        let section = |name: &'static str, priority, dar| ConditionsConfig {
+            name: name.into(),
+            priority,
+            conditions: parse(dar),
+            ..Default::default()
+        };
+        let namespaces = [
+            Namespace {
+                sections: vec![
+                    section("Sneak", 100, "IsSneaking()"),
+                    section("Combat", 300, "IsInCombat()"),
+                ],
+                ..Default::default()
+            },
+            Namespace {
+                sections: vec![section("Random", 200, "Random(0.5)")],
+                ..Default::default()
+            },
+        ];
+
+        let resolution = resolve_winner(&namespaces, &snapshot()?, &mut |_, _| 0.9);
+        let order: Vec<_> = resolution.sections.iter().map(|s| s.name).collect();
+        assert_eq!(order, ["Combat", "Random", "Sneak"]);
+        assert_eq!(resolution.winner.map(|winner| winner.name), Some("Sneak"));
+
+        let resolution = resolve_winner(&namespaces, &snapshot()?, &mut |_, _| 0.1);
+        assert_eq!(resolution.winner.map(|winner| winner.name), Some("Random"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_evaluate_config_json() -> Result<(), serde_json::Error> {
+        let config: ConditionsConfig = serde_json::from_str(
+            r#"{
+  "name": "Sneak",
+  "priority": 100,
+  "conditions": [
+    {
+      "condition": "IsRace",
+      "requiredVersion": "1.0.0.0",
+      "Race": { "pluginName": "Skyrim.esm", "formID": "13746" }
+    },
+    {
+      "condition": "OR",
+      "requiredVersion": "1.0.0.0",
+      "Conditions": [
+        { "condition": "IsSneaking", "requiredVersion": "1.0.0.0" },
+        { "condition": "IsFemale", "requiredVersion": "1.0.0.0" }
+      ]
+    }
+  ]
+}"#,
+        )?;
+        assert!(matches!(config.conditions[0], Oar::IsRace(_)));
+        assert!(matches!(config.conditions[1], Oar::Or(_)));
+
+        let (result, traces) = evaluate(&config.conditions, &[], &snapshot()?, &mut |_, _| 0.0);
+        assert!(result, "{traces:#?}");
+
+        let namespace = Namespace {
+            sections: vec![config],
+            ..Default::default()
+        };
+        let resolution = resolve_winner([&namespace], &snapshot()?, &mut |_, _| 0.0);
+        assert_eq!(resolution.winner.map(|winner| winner.name), Some("Sneak"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_resolve_presets() -> Result<(), serde_json::Error> {
+        let config: MainConfig = serde_json::from_str(
+            r#"{
+  "name": "Mod",
+  "conditionPresets": [
+    { "name": "Dagger", "conditions": [{ "condition": "IsEquippedType", "Type": { "value": 2.0 } }] },
+    { "name": "Loop", "conditions": [{ "condition": "PRESET", "Preset": "Loop" }] }
+  ]
+}"#,
+        )?;
+        let section = |name: &'static str, priority, preset: &'static str| ConditionsConfig {
+            name: name.into(),
+            priority,
+            conditions: vec![
+                crate::cond::preset(preset),
+                Oar::Condition(crate::conditions::Condition::new("IsSneaking")),
+            ],
+            ..Default::default()
+        };
+        let namespace = Namespace {
+            config,
+            sections: vec![
+                section("Loop", 300, "Loop"),
+                section("Missing", 200, "Missing"),
+                section("Dagger", 100, "Dagger"),
+            ],
+        };
+
+        let resolution = resolve_winner([&namespace], &snapshot()?, &mut |_, _| 0.0);
+        let winner = resolution.winner.as_ref().map(|winner| winner.name);
+        assert_eq!(winner, Some("Dagger"), "{:#?}", resolution.sections);
+        let notes: Vec<_> = (resolution.sections.iter())
+            .map(|section| section.trace[0].note.as_deref())
+            .collect();
+        assert_eq!(notes, [None, Some("preset `Missing` is not found"), None,]);
+        let looped = &resolution.sections[0].trace[0].children[0];
+        assert_eq!(
+            looped.note.as_deref(),
+            Some("preset `Loop` refers to itself")
+        );
+        assert_eq!(resolution.sections[0].namespace, "Mod");
+        Ok(())
+    }
+}
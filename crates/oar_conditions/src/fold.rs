@@ -0,0 +1,511 @@
+//! Owned rewriting of OAR conditions.
+//!
+//! Each method of [`Fold`] rebuilds the node from the folded children by default.
+//! To keep folding after overriding a method, call the free function of the same name.
+//! ```
+//! use oar_conditions::cond::{self, Not as _};
+//! use oar_conditions::conditions::Oar;
+//! use oar_conditions::fold::{self, Fold};
+//!
+//! /// Drop all `NOT`s.
+//! struct Affirm;
+//!
+//! impl<'a> Fold<'a> for Affirm {
+//!     fn fold_oar(&mut self, mut node: Oar<'a>) -> Oar<'a> {
+//!         *node.negated_mut() = false;
+//!         fold::fold_oar(self, node)
+//!     }
+//! }
+//!
+//! let conditions = (cond::is_sneaking().not() | cond::is_female()).not();
+//! assert_eq!(Affirm.fold_oar(conditions), cond::is_sneaking() | cond::is_female());
+//! ```
+use crate::conditions::{
+    And, CompareValues, Condition, ConditionPreset, ConditionsConfig, CurrentGameTime,
+    CurrentWeather, FactionRank, HasKeyword, HasMagicEffect, HasMagicEffectWithKeyword, HasPerk,
+    HasRefType, HasSpell, IsActorBase, IsClass, IsCombatStyle, IsEquipped, IsEquippedHasKeyword,
+    IsEquippedShout, IsEquippedType, IsInFaction, IsInLocation, IsMovementDirection, IsParentCell,
+    IsRace, IsVoiceType, IsWorldSpace, IsWorn, IsWornHasKeyword, Level, MainConfig, Oar, Or,
+    PresetCondition, RandomCondition,
+};
+use oar_values::{
+    ActorValue, Cmp, DirectionValue, FormID, FormValue, GraphValue, Keyword, LiteralValue,
+    NumericValue, PluginValue, RandomValue, StaticValue, TypeValue,
+};
+
+/// Owned rewriter of OAR conditions and their values.
+pub trait Fold<'a> {
+    /// `config.json` of an animation root(section).
+    fn fold_conditions_config(&mut self, node: ConditionsConfig<'a>) -> ConditionsConfig<'a> {
+        fold_conditions_config(self, node)
+    }
+
+    /// `config.json` of a namespace.
+    fn fold_main_config(&mut self, node: MainConfig<'a>) -> MainConfig<'a> {
+        fold_main_config(self, node)
+    }
+
+    /// A condition preset of a namespace.
+    fn fold_condition_preset(&mut self, node: ConditionPreset<'a>) -> ConditionPreset<'a> {
+        fold_condition_preset(self, node)
+    }
+
+    /// Any condition. Dispatches to the variant's method.
+    fn fold_oar(&mut self, node: Oar<'a>) -> Oar<'a> {
+        fold_oar(self, node)
+    }
+
+    /// `AND`
+    fn fold_and(&mut self, node: And<'a>) -> And<'a> {
+        fold_and(self, node)
+    }
+
+    /// `OR`
+    fn fold_or(&mut self, node: Or<'a>) -> Or<'a> {
+        fold_or(self, node)
+    }
+
+    /// `PRESET`
+    fn fold_preset_condition(&mut self, node: PresetCondition<'a>) -> PresetCondition<'a> {
+        node
+    }
+
+    /// A condition with no arguments. e.g. `IsFemale`
+    fn fold_condition(&mut self, node: Condition<'a>) -> Condition<'a> {
+        node
+    }
+
+    /// `CompareValues`
+    fn fold_compare_values(&mut self, node: CompareValues<'a>) -> CompareValues<'a> {
+        fold_compare_values(self, node)
+    }
+
+    /// `CurrentGameTime`
+    fn fold_current_game_time(&mut self, node: CurrentGameTime<'a>) -> CurrentGameTime<'a> {
+        fold_current_game_time(self, node)
+    }
+
+    /// `CurrentWeather`
+    fn fold_current_weather(&mut self, node: CurrentWeather<'a>) -> CurrentWeather<'a> {
+        fold_current_weather(self, node)
+    }
+
+    /// `FactionRank`
+    fn fold_faction_rank(&mut self, node: FactionRank<'a>) -> FactionRank<'a> {
+        fold_faction_rank(self, node)
+    }
+
+    /// `HasKeyword`
+    fn fold_has_keyword(&mut self, node: HasKeyword<'a>) -> HasKeyword<'a> {
+        fold_has_keyword(self, node)
+    }
+
+    /// `HasMagicEffect`
+    fn fold_has_magic_effect(&mut self, node: HasMagicEffect<'a>) -> HasMagicEffect<'a> {
+        fold_has_magic_effect(self, node)
+    }
+
+    /// `HasMagicEffectWithKeyword`
+    fn fold_has_magic_effect_with_keyword(
+        &mut self,
+        node: HasMagicEffectWithKeyword<'a>,
+    ) -> HasMagicEffectWithKeyword<'a> {
+        fold_has_magic_effect_with_keyword(self, node)
+    }
+
+    /// `HasPerk`
+    fn fold_has_perk(&mut self, node: HasPerk<'a>) -> HasPerk<'a> {
+        fold_has_perk(self, node)
+    }
+
+    /// `HasRefType`
+    fn fold_has_ref_type(&mut self, node: HasRefType<'a>) -> HasRefType<'a> {
+        fold_has_ref_type(self, node)
+    }
+
+    /// `HasSpell`
+    fn fold_has_spell(&mut self, node: HasSpell<'a>) -> HasSpell<'a> {
+        fold_has_spell(self, node)
+    }
+
+    /// `IsActorBase`
+    fn fold_is_actor_base(&mut self, node: IsActorBase<'a>) -> IsActorBase<'a> {
+        fold_is_actor_base(self, node)
+    }
+
+    /// `IsClass`
+    fn fold_is_class(&mut self, node: IsClass<'a>) -> IsClass<'a> {
+        fold_is_class(self, node)
+    }
+
+    /// `IsCombatStyle`
+    fn fold_is_combat_style(&mut self, node: IsCombatStyle<'a>) -> IsCombatStyle<'a> {
+        fold_is_combat_style(self, node)
+    }
+
+    /// `IsEquipped`
+    fn fold_is_equipped(&mut self, node: IsEquipped<'a>) -> IsEquipped<'a> {
+        fold_is_equipped(self, node)
+    }
+
+    /// `IsEquippedHasKeyword`
+    fn fold_is_equipped_has_keyword(
+        &mut self,
+        node: IsEquippedHasKeyword<'a>,
+    ) -> IsEquippedHasKeyword<'a> {
+        fold_is_equipped_has_keyword(self, node)
+    }
+
+    /// `IsEquippedShout`
+    fn fold_is_equipped_shout(&mut self, node: IsEquippedShout<'a>) -> IsEquippedShout<'a> {
+        fold_is_equipped_shout(self, node)
+    }
+
+    /// `IsEquippedType`
+    fn fold_is_equipped_type(&mut self, node: IsEquippedType<'a>) -> IsEquippedType<'a> {
+        fold_is_equipped_type(self, node)
+    }
+
+    /// `IsInFaction`
+    fn fold_is_in_faction(&mut self, node: IsInFaction<'a>) -> IsInFaction<'a> {
+        fold_is_in_faction(self, node)
+    }
+
+    /// `IsInLocation`
+    fn fold_is_in_location(&mut self, node: IsInLocation<'a>) -> IsInLocation<'a> {
+        fold_is_in_location(self, node)
+    }
+
+    /// `IsMovementDirection`
+    fn fold_is_movement_direction(
+        &mut self,
+        node: IsMovementDirection<'a>,
+    ) -> IsMovementDirection<'a> {
+        fold_is_movement_direction(self, node)
+    }
+
+    /// `IsParentCell`
+    fn fold_is_parent_cell(&mut self, node: IsParentCell<'a>) -> IsParentCell<'a> {
+        fold_is_parent_cell(self, node)
+    }
+
+    /// `IsRace`
+    fn fold_is_race(&mut self, node: IsRace<'a>) -> IsRace<'a> {
+        fold_is_race(self, node)
+    }
+
+    /// `IsVoiceType`
+    fn fold_is_voice_type(&mut self, node: IsVoiceType<'a>) -> IsVoiceType<'a> {
+        fold_is_voice_type(self, node)
+    }
+
+    /// `IsWorldSpace`
+    fn fold_is_world_space(&mut self, node: IsWorldSpace<'a>) -> IsWorldSpace<'a> {
+        fold_is_world_space(self, node)
+    }
+
+    /// `IsWorn`
+    fn fold_is_worn(&mut self, node: IsWorn<'a>) -> IsWorn<'a> {
+        fold_is_worn(self, node)
+    }
+
+    /// `IsWornHasKeyword`
+    fn fold_is_worn_has_keyword(&mut self, node: IsWornHasKeyword<'a>) -> IsWornHasKeyword<'a> {
+        fold_is_worn_has_keyword(self, node)
+    }
+
+    /// `Level`
+    fn fold_level(&mut self, node: Level<'a>) -> Level<'a> {
+        fold_level(self, node)
+    }
+
+    /// `Random`
+    fn fold_random_condition(&mut self, node: RandomCondition<'a>) -> RandomCondition<'a> {
+        fold_random_condition(self, node)
+    }
+
+    /// Actor value of a numeric value.
+    fn fold_actor_value(&mut self, node: ActorValue) -> ActorValue {
+        node
+    }
+
+    /// Comparison operator.
+    fn fold_cmp(&mut self, node: Cmp) -> Cmp {
+        node
+    }
+
+    /// Movement direction.
+    fn fold_direction_value(&mut self, node: DirectionValue) -> DirectionValue {
+        node
+    }
+
+    /// FormID of a plugin value.
+    fn fold_form_id(&mut self, node: FormID<'a>) -> FormID<'a> {
+        node
+    }
+
+    /// A form wrapping a plugin value.
+    fn fold_form_value(&mut self, node: FormValue<'a>) -> FormValue<'a> {
+        fold_form_value(self, node)
+    }
+
+    /// Graph variable of a numeric value.
+    fn fold_graph_value(&mut self, node: GraphValue<'a>) -> GraphValue<'a> {
+        node
+    }
+
+    /// A form or an `editorID`.
+    fn fold_keyword(&mut self, node: Keyword<'a>) -> Keyword<'a> {
+        fold_keyword(self, node)
+    }
+
+    /// `editorID` of a keyword.
+    fn fold_literal_value(&mut self, node: LiteralValue<'a>) -> LiteralValue<'a> {
+        node
+    }
+
+    /// Static, global, actor or graph value.
+    fn fold_numeric_value(&mut self, node: NumericValue<'a>) -> NumericValue<'a> {
+        fold_numeric_value(self, node)
+    }
+
+    /// A pair of a plugin name and a FormID.
+    fn fold_plugin_value(&mut self, node: PluginValue<'a>) -> PluginValue<'a> {
+        fold_plugin_value(self, node)
+    }
+
+    /// Range of `Random`.
+    fn fold_random_value(&mut self, node: RandomValue) -> RandomValue {
+        node
+    }
+
+    /// A number.
+    fn fold_static_value(&mut self, node: StaticValue) -> StaticValue {
+        node
+    }
+
+    /// Weapon type of `IsEquippedType`.
+    fn fold_type_value(&mut self, node: TypeValue) -> TypeValue {
+        node
+    }
+}
+
+/// Fold the conditions of [`ConditionsConfig`].
+pub fn fold_conditions_config<'a, F>(f: &mut F, node: ConditionsConfig<'a>) -> ConditionsConfig<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    ConditionsConfig {
+        conditions: node.conditions.into_iter().map(|c| f.fold_oar(c)).collect(),
+        ..node
+    }
+}
+
+/// Fold the condition presets of [`MainConfig`].
+pub fn fold_main_config<'a, F>(f: &mut F, node: MainConfig<'a>) -> MainConfig<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    MainConfig {
+        condition_presets: (node.condition_presets.into_iter())
+            .map(|preset| f.fold_condition_preset(preset))
+            .collect(),
+        ..node
+    }
+}
+
+/// Fold the conditions of [`ConditionPreset`].
+pub fn fold_condition_preset<'a, F>(f: &mut F, node: ConditionPreset<'a>) -> ConditionPreset<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    ConditionPreset {
+        conditions: node.conditions.into_iter().map(|c| f.fold_oar(c)).collect(),
+        ..node
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn fold_oar<'a, F>(f: &mut F, node: Oar<'a>) -> Oar<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    match node {
+        Oar::And(node) => Oar::And(f.fold_and(node)),
+        Oar::Preset(node) => Oar::Preset(f.fold_preset_condition(node)),
+        Oar::Condition(node) => Oar::Condition(f.fold_condition(node)),
+        Oar::CompareValues(node) => Oar::CompareValues(f.fold_compare_values(node)),
+        Oar::CurrentGameTime(node) => Oar::CurrentGameTime(f.fold_current_game_time(node)),
+        Oar::CurrentWeather(node) => Oar::CurrentWeather(f.fold_current_weather(node)),
+        Oar::FactionRank(node) => Oar::FactionRank(f.fold_faction_rank(node)),
+        Oar::HasKeyword(node) => Oar::HasKeyword(f.fold_has_keyword(node)),
+        Oar::HasMagicEffect(node) => Oar::HasMagicEffect(f.fold_has_magic_effect(node)),
+        Oar::HasMagicEffectWithKeyword(node) => {
+            Oar::HasMagicEffectWithKeyword(f.fold_has_magic_effect_with_keyword(node))
+        }
+        Oar::HasPerk(node) => Oar::HasPerk(f.fold_has_perk(node)),
+        Oar::HasRefType(node) => Oar::HasRefType(f.fold_has_ref_type(node)),
+        Oar::HasSpell(node) => Oar::HasSpell(f.fold_has_spell(node)),
+        Oar::IsActorBase(node) => Oar::IsActorBase(f.fold_is_actor_base(node)),
+        Oar::IsClass(node) => Oar::IsClass(f.fold_is_class(node)),
+        Oar::IsCombatStyle(node) => Oar::IsCombatStyle(f.fold_is_combat_style(node)),
+        Oar::IsEquipped(node) => Oar::IsEquipped(f.fold_is_equipped(node)),
+        Oar::IsEquippedHasKeyword(node) => {
+            Oar::IsEquippedHasKeyword(f.fold_is_equipped_has_keyword(node))
+        }
+        Oar::IsEquippedShout(node) => Oar::IsEquippedShout(f.fold_is_equipped_shout(node)),
+        Oar::IsEquippedType(node) => Oar::IsEquippedType(f.fold_is_equipped_type(node)),
+        Oar::IsInFaction(node) => Oar::IsInFaction(f.fold_is_in_faction(node)),
+        Oar::IsInLocation(node) => Oar::IsInLocation(f.fold_is_in_location(node)),
+        Oar::IsParentCell(node) => Oar::IsParentCell(f.fold_is_parent_cell(node)),
+        Oar::IsRace(node) => Oar::IsRace(f.fold_is_race(node)),
+        Oar::IsVoiceType(node) => Oar::IsVoiceType(f.fold_is_voice_type(node)),
+        Oar::IsWorldSpace(node) => Oar::IsWorldSpace(f.fold_is_world_space(node)),
+        Oar::IsWorn(node) => Oar::IsWorn(f.fold_is_worn(node)),
+        Oar::IsWornHasKeyword(node) => Oar::IsWornHasKeyword(f.fold_is_worn_has_keyword(node)),
+        Oar::IsDirectionMovement(node) => {
+            Oar::IsDirectionMovement(f.fold_is_movement_direction(node))
+        }
+        Oar::Level(node) => Oar::Level(f.fold_level(node)),
+        Oar::Or(node) => Oar::Or(f.fold_or(node)),
+        Oar::RandomCondition(node) => Oar::RandomCondition(f.fold_random_condition(node)),
+    }
+}
+
+/// Fold the conditions of [`And`].
+pub fn fold_and<'a, F>(f: &mut F, node: And<'a>) -> And<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    And {
+        conditions: node.conditions.into_iter().map(|c| f.fold_oar(c)).collect(),
+        ..node
+    }
+}
+
+/// Fold the conditions of [`Or`].
+pub fn fold_or<'a, F>(f: &mut F, node: Or<'a>) -> Or<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    Or {
+        conditions: node.conditions.into_iter().map(|c| f.fold_oar(c)).collect(),
+        ..node
+    }
+}
+
+/// Fold the form of [`FormValue`].
+pub fn fold_form_value<'a, F>(f: &mut F, node: FormValue<'a>) -> FormValue<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    FormValue {
+        form: f.fold_plugin_value(node.form),
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn fold_keyword<'a, F>(f: &mut F, node: Keyword<'a>) -> Keyword<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    match node {
+        Keyword::Form(node) => Keyword::Form(f.fold_form_value(node)),
+        Keyword::Literal(node) => Keyword::Literal(f.fold_literal_value(node)),
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn fold_numeric_value<'a, F>(f: &mut F, node: NumericValue<'a>) -> NumericValue<'a>
+where
+    F: Fold<'a> + ?Sized,
+{
+    match node {
+        NumericValue::StaticValue(node) => NumericValue::StaticValue(f.fold_static_value(node)),
+        NumericValue::GlobalVariable(node) => NumericValue::GlobalVariable(f.fold_form_value(node)),
+        NumericValue::ActorValue(node) => NumericValue::ActorValue(f.fold_actor_value(node)),
+        NumericValue::GraphVariable(node) => NumericValue::GraphVariable(f.fold_graph_value(node)),
+    }
+}
+
+/// Generate functions folding the fields of a node.
+macro_rules! fold_fields {
+    ($($fn_name:ident($ty:ident { $($field:ident => $fold:ident),+ })),+ $(,)?) => {
+        $(
+            #[doc = concat!("Fold the fields of [`", stringify!($ty), "`].")]
+            pub fn $fn_name<'a, F>(f: &mut F, node: $ty<'a>) -> $ty<'a>
+            where
+                F: Fold<'a> + ?Sized,
+            {
+                $ty {
+                    $($field: f.$fold(node.$field),)+
+                    ..node
+                }
+            }
+        )+
+    };
+}
+
+fold_fields!(
+    fold_compare_values(CompareValues { value_a => fold_numeric_value, comparison => fold_cmp, value_b => fold_numeric_value }),
+    fold_current_game_time(CurrentGameTime { comparison => fold_cmp, numeric_value => fold_numeric_value }),
+    fold_current_weather(CurrentWeather { weather => fold_plugin_value }),
+    fold_faction_rank(FactionRank { faction => fold_plugin_value, comparison => fold_cmp, numeric_value => fold_numeric_value }),
+    fold_has_keyword(HasKeyword { keyword => fold_keyword }),
+    fold_has_magic_effect(HasMagicEffect { magic_effect => fold_plugin_value }),
+    fold_has_magic_effect_with_keyword(HasMagicEffectWithKeyword { keyword => fold_keyword }),
+    fold_has_perk(HasPerk { perk => fold_plugin_value }),
+    fold_has_ref_type(HasRefType { location_ref_type => fold_keyword }),
+    fold_has_spell(HasSpell { spell => fold_plugin_value }),
+    fold_is_actor_base(IsActorBase { actor_base => fold_plugin_value }),
+    fold_is_class(IsClass { class => fold_plugin_value }),
+    fold_is_combat_style(IsCombatStyle { combat_style => fold_plugin_value }),
+    fold_is_equipped(IsEquipped { form => fold_plugin_value }),
+    fold_is_equipped_has_keyword(IsEquippedHasKeyword { keyword => fold_keyword }),
+    fold_is_equipped_shout(IsEquippedShout { shout => fold_plugin_value }),
+    fold_is_equipped_type(IsEquippedType { type_value => fold_type_value }),
+    fold_is_in_faction(IsInFaction { faction => fold_plugin_value }),
+    fold_is_in_location(IsInLocation { location => fold_plugin_value }),
+    fold_is_movement_direction(IsMovementDirection { direction => fold_direction_value }),
+    fold_is_parent_cell(IsParentCell { cell => fold_plugin_value }),
+    fold_is_race(IsRace { race => fold_plugin_value }),
+    fold_is_voice_type(IsVoiceType { voice_type => fold_plugin_value }),
+    fold_is_world_space(IsWorldSpace { world_space => fold_plugin_value }),
+    fold_is_worn(IsWorn { form => fold_plugin_value }),
+    fold_is_worn_has_keyword(IsWornHasKeyword { keyword => fold_keyword }),
+    fold_level(Level { comparison => fold_cmp, numeric_value => fold_numeric_value }),
+    fold_random_condition(RandomCondition { random_value => fold_random_value, comparison => fold_cmp, numeric_value => fold_numeric_value }),
+    fold_plugin_value(PluginValue { form_id => fold_form_id }),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cond::{self, Not as _};
+    use pretty_assertions::assert_eq;
+
+    /// Push `NOT`s down to the leaves by De Morgan's laws.
+    struct NegationNormalForm;
+
+    impl<'a> Fold<'a> for NegationNormalForm {
+        fn fold_oar(&mut self, node: Oar<'a>) -> Oar<'a> {
+            let node = match node {
+                Oar::And(and) if and.negated => cond::or(and.conditions.into_iter().map(Oar::not)),
+                Oar::Or(or) if or.negated => cond::and(or.conditions.into_iter().map(Oar::not)),
+                node => node,
+            };
+            fold_oar(self, node)
+        }
+    }
+
+    #[test]
+    fn should_normalize_negation() {
+        let conditions = !(cond::is_sneaking() & !(cond::is_female() | cond::random(0.5)));
+
+        let expected = cond::or([
+            !cond::is_sneaking(),
+            cond::or([cond::is_female(), cond::random(0.5)]),
+        ]);
+        assert_eq!(NegationNormalForm.fold_oar(conditions), expected);
+    }
+}
@@ -1,3 +1,14 @@
 pub mod cast;
+pub mod cond;
 pub mod conditions;
 pub mod error;
+pub mod eval;
+pub mod fold;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod version;
+pub mod visit;
+pub mod visit_mut;
+
+#[cfg(all(test, not(feature = "schemars")))]
+extern crate jsonschema as _; // Only used by the schema tests.
@@ -0,0 +1,85 @@
+//! JSON Schema of the OAR `config.json`s, generated from the same types the converter writes.
+//!
+//! Editors(e.g. VS Code `json.schemas`) can validate and complete hand-edited configs with it.
+use crate::conditions::{ConditionsConfig, MainConfig};
+use schemars::{Schema, schema_for};
+
+/// JSON Schema of a section(animation root) `config.json`.
+pub fn section_config_schema() -> Schema {
+    let mut schema = schema_for!(ConditionsConfig);
+    schema.insert("title".into(), "OAR section config.json".into());
+    schema
+}
+
+/// JSON Schema of the namespace(mod root) `config.json`.
+pub fn namespace_config_schema() -> Schema {
+    let mut schema = schema_for!(MainConfig);
+    schema.insert("title".into(), "OAR namespace config.json".into());
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_generate_section_config_schema() {
+        let schema = section_config_schema().to_value();
+
+        assert_eq!(schema["title"], "OAR section config.json");
+        assert_eq!(schema["properties"]["priority"]["type"], "integer");
+        let defs = &schema["$defs"];
+        for name in [
+            "Oar",
+            "NumericValue",
+            "Keyword",
+            "PluginValue",
+            "Cmp",
+            "ActorValue",
+        ] {
+            assert!(defs.get(name).is_some(), "{name} is not defined");
+        }
+        assert_eq!(defs["Direction"]["type"], "number");
+    }
+
+    #[test]
+    fn should_reject_invalid_conditions() -> Result<(), serde_json::Error> {
+        let schema = section_config_schema().to_value();
+        let validator = match jsonschema::validator_for(&schema) {
+            Ok(validator) => validator,
+            Err(err) => panic!("{err}"),
+        };
+        let config = |condition: &str| -> Result<serde_json::Value, serde_json::Error> {
+            serde_json::from_str(&format!(
+                r#"{{ "name": "test", "conditions": [{condition}] }}"#
+            ))
+        };
+
+        let valid = [
+            r#"{ "condition": "IsSneaking" }"#,
+            r#"{ "condition": "IsActorBase", "Actor base": { "pluginName": "Skyrim.esm", "formID": "7" } }"#,
+            r#"{ "condition": "OR", "Conditions": [{ "condition": "IsFemale" }] }"#,
+            // Not listed conditions are generic ones.
+            r#"{ "condition": "IsBleedingOut", "negated": true }"#,
+        ];
+        for condition in valid {
+            let config = config(condition)?;
+            let errors: Vec<_> = validator.iter_errors(&config).collect();
+            assert!(errors.is_empty(), "{condition}: {errors:?}");
+        }
+
+        let invalid = [
+            // Missing name
+            r#"{ "Actor base": { "pluginName": "Skyrim.esm", "formID": "7" } }"#,
+            // Mistyped field
+            r#"{ "condition": "IsActorBase", "Actor base": "Skyrim.esm" }"#,
+            // Invalid child
+            r#"{ "condition": "OR", "Conditions": [{ "condition": "IsRace", "Race": 7 }] }"#,
+        ];
+        for condition in invalid {
+            assert!(!validator.is_valid(&config(condition)?), "{condition}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,303 @@
+//! OAR versions required by the conditions(`requiredVersion`).
+use crate::conditions::Oar;
+use crate::error::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// OAR version. e.g. `2.3.0.0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OarVersion {
+    /// Major version
+    pub major: u16,
+    /// Minor version
+    pub minor: u16,
+    /// Patch version
+    pub patch: u16,
+    /// Build number
+    pub build: u16,
+}
+
+impl OarVersion {
+    /// `1.0.0.0`: the first version, supporting all the DAR conditions.
+    pub const V1: Self = Self::new(1, 0, 0, 0);
+    /// `1.1.0.0`: `IsMovementDirection`
+    pub const V1_1: Self = Self::new(1, 1, 0, 0);
+    /// `2.0.0.0`: keywords written as `editorID`
+    pub const V2: Self = Self::new(2, 0, 0, 0);
+    /// `2.3.0.0`: condition presets
+    pub const V2_3: Self = Self::new(2, 3, 0, 0);
+
+    /// Create a version.
+    pub const fn new(major: u16, minor: u16, patch: u16, build: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+}
+
+impl Default for OarVersion {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+impl fmt::Display for OarVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            major,
+            minor,
+            patch,
+            build,
+        } = self;
+        write!(f, "{major}.{minor}.{patch}.{build}")
+    }
+}
+
+impl FromStr for OarVersion {
+    type Err = Error;
+
+    /// Parse `major[.minor[.patch[.build]]]`. The omitted parts are `0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::UnexpectedValue {
+            expected: "OAR version. e.g. `2.3.0.0`".to_string(),
+            actual: s.to_string(),
+        };
+
+        let mut parts = [0; 4];
+        for (index, part) in s.trim().split('.').enumerate() {
+            let slot = parts.get_mut(index).ok_or_else(invalid)?;
+            *slot = part.parse().map_err(|_| invalid())?;
+        }
+        let [major, minor, patch, build] = parts;
+        Ok(Self::new(major, minor, patch, build))
+    }
+}
+
+impl Serialize for OarVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OarVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A condition, or a field of it, supported since `version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Requirement {
+    /// Condition name. e.g. `HasKeyword`
+    pub condition: &'static str,
+    /// Field name in `config.json`(e.g. `Keyword`), or `None` for the condition itself.
+    pub field: Option<&'static str>,
+    /// Key in the object value of `field`(e.g. `editorID`), or `None` for any value.
+    pub key: Option<&'static str>,
+    /// The first OAR version supporting it
+    pub version: OarVersion,
+}
+
+impl Requirement {
+    /// The condition itself requires `version`.
+    const fn condition(condition: &'static str, version: OarVersion) -> Self {
+        Self {
+            condition,
+            field: None,
+            key: None,
+            version,
+        }
+    }
+
+    /// `field` of the condition, holding an object with `key`, requires `version`.
+    const fn field_key(
+        condition: &'static str,
+        field: &'static str,
+        key: &'static str,
+        version: OarVersion,
+    ) -> Self {
+        Self {
+            condition,
+            field: Some(field),
+            key: Some(key),
+            version,
+        }
+    }
+
+    /// Returns `true` if `condition`(serialized as in `config.json`) uses the feature.
+    fn is_used_by(&self, condition: &serde_json::Value) -> bool {
+        let Some(field) = self.field else {
+            return true;
+        };
+        match (condition.get(field), self.key) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(value), Some(key)) => value.get(key).is_some(),
+        }
+    }
+}
+
+/// Minimum OAR versions newer than [`OarVersion::V1`], by condition and by field.
+pub const CONDITION_VERSIONS: &[Requirement] = &[
+    Requirement::condition("IsMovementDirection", OarVersion::V1_1),
+    Requirement::condition("PRESET", OarVersion::V2_3),
+    // Keywords written as `editorID`
+    Requirement::field_key("HasKeyword", "Keyword", "editorID", OarVersion::V2),
+    Requirement::field_key(
+        "HasMagicEffectWithKeyword",
+        "Keyword",
+        "editorID",
+        OarVersion::V2,
+    ),
+    Requirement::field_key(
+        "HasRefType",
+        "Location ref type",
+        "editorID",
+        OarVersion::V2,
+    ),
+    Requirement::field_key(
+        "IsEquippedHasKeyword",
+        "Keyword",
+        "editorID",
+        OarVersion::V2,
+    ),
+    Requirement::field_key("IsWornHasKeyword", "Keyword", "editorID", OarVersion::V2),
+];
+
+/// OAR version required by `condition` itself(not including its children), by its name and fields.
+///
+/// See [`CONDITION_VERSIONS`].
+pub fn required_version(condition: &Oar<'_>) -> OarVersion {
+    let name = condition.condition_name();
+    // Serialized only when a field is needed.
+    let mut serialized = None;
+    CONDITION_VERSIONS
+        .iter()
+        .filter(|requirement| requirement.condition == name)
+        .filter(|requirement| {
+            requirement.field.is_none()
+                || requirement.is_used_by(serialized.get_or_insert_with(|| {
+                    serde_json::to_value(condition).unwrap_or(serde_json::Value::Null)
+                }))
+        })
+        .map(|requirement| requirement.version)
+        .max()
+        .unwrap_or(OarVersion::V1)
+}
+
+/// Set `requiredVersion` of every condition(including `AND`/`OR` children) by [`required_version`].
+///
+/// Returns the condition names and the highest version each of them required.
+pub fn set_required_versions(conditions: &mut [Oar<'_>]) -> BTreeMap<String, OarVersion> {
+    let mut versions = BTreeMap::new();
+    set_versions(conditions, &mut versions);
+    versions
+}
+
+fn set_versions(conditions: &mut [Oar<'_>], versions: &mut BTreeMap<String, OarVersion>) {
+    for condition in conditions {
+        let version = required_version(condition);
+        *condition.required_version_mut() = version.to_string().into();
+        let entry = versions
+            .entry(condition.condition_name().to_string())
+            .or_insert(version);
+        *entry = (*entry).max(version);
+
+        match condition {
+            Oar::And(and) => set_versions(&mut and.conditions, versions),
+            Oar::Or(or) => set_versions(&mut or.conditions, versions),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conditions::{HasKeyword, IsMovementDirection, Or};
+    use oar_values::{Keyword, LiteralValue};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_and_display_version() -> core::result::Result<(), Error> {
+        assert_eq!("2.3".parse::<OarVersion>()?, OarVersion::V2_3);
+        assert_eq!(OarVersion::V2_3.to_string(), "2.3.0.0");
+        assert!("2.x".parse::<OarVersion>().is_err());
+        assert!("1.0.0.0.0".parse::<OarVersion>().is_err());
+        assert!(OarVersion::V1_1 < OarVersion::V2);
+        Ok(())
+    }
+
+    #[test]
+    fn should_set_required_versions() {
+        let mut conditions = vec![Oar::Or(Or {
+            conditions: vec![
+                Oar::IsDirectionMovement(IsMovementDirection::default()),
+                Oar::HasKeyword(HasKeyword {
+                    keyword: Keyword::Literal(LiteralValue {
+                        editor_id: "WeapTypeDagger".into(),
+                    }),
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        })];
+
+        let versions = set_required_versions(&mut conditions);
+
+        let expected = BTreeMap::from([
+            ("HasKeyword".to_string(), OarVersion::V2),
+            ("IsMovementDirection".to_string(), OarVersion::V1_1),
+            ("OR".to_string(), OarVersion::V1),
+        ]);
+        assert_eq!(versions, expected);
+        let Oar::Or(or) = &mut conditions[0] else {
+            panic!("expected OR");
+        };
+        assert_eq!(or.required_version, "1.0.0.0");
+        assert_eq!(or.conditions[1].required_version_mut(), "2.0.0.0");
+    }
+
+    #[test]
+    fn should_require_versions_by_field() -> Result<(), serde_json::Error> {
+        assert_eq!(
+            required_version(&crate::cond::preset("Name")),
+            OarVersion::V2_3
+        );
+
+        for requirement in CONDITION_VERSIONS {
+            let Requirement {
+                condition,
+                field: Some(field),
+                key,
+                version,
+            } = *requirement
+            else {
+                continue;
+            };
+            let parse = |value: serde_json::Value| {
+                let json = serde_json::json!({ "condition": condition, field: value }).to_string();
+                serde_json::from_str::<Oar>(&json).map(|condition| {
+                    // The field is really in the condition.
+                    let serialized = serde_json::to_value(&condition).unwrap_or_default();
+                    assert!(serialized.get(field).is_some(), "{condition:?}: {field}");
+                    required_version(&condition)
+                })
+            };
+
+            let form = serde_json::json!({ "form": { "pluginName": "Skyrim.esm", "formID": "7" } });
+            assert_eq!(parse(form)?, OarVersion::V1, "{condition}: {field}");
+            if let Some(key) = key {
+                let value = serde_json::json!({ key: "Value" });
+                assert_eq!(parse(value)?, version, "{condition}: {field}.{key}");
+            }
+        }
+        Ok(())
+    }
+}
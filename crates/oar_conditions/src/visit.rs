@@ -0,0 +1,457 @@
+//! Read-only traversal of OAR conditions.
+//!
+//! Each method of [`Visit`] walks into the children by default.
+//! To keep walking after overriding a method, call the free function of the same name.
+//! ```
+//! use oar_conditions::cond;
+//! use oar_conditions::visit::{self, Visit};
+//! use oar_values::PluginValue;
+//!
+//! struct PluginNames<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visit<'ast> for PluginNames<'ast> {
+//!     fn visit_plugin_value(&mut self, node: &'ast PluginValue<'_>) {
+//!         self.0.push(&node.plugin_name);
+//!         visit::visit_plugin_value(self, node);
+//!     }
+//! }
+//!
+//! let conditions = cond::is_race(cond::form("Skyrim.esm", 0x13746)) | cond::is_female();
+//! let mut names = PluginNames(vec![]);
+//! names.visit_oar(&conditions);
+//! assert_eq!(names.0, ["Skyrim.esm"]);
+//! ```
+use crate::conditions::{
+    And, CompareValues, Condition, ConditionPreset, ConditionsConfig, CurrentGameTime,
+    CurrentWeather, FactionRank, HasKeyword, HasMagicEffect, HasMagicEffectWithKeyword, HasPerk,
+    HasRefType, HasSpell, IsActorBase, IsClass, IsCombatStyle, IsEquipped, IsEquippedHasKeyword,
+    IsEquippedShout, IsEquippedType, IsInFaction, IsInLocation, IsMovementDirection, IsParentCell,
+    IsRace, IsVoiceType, IsWorldSpace, IsWorn, IsWornHasKeyword, Level, MainConfig, Oar, Or,
+    PresetCondition, RandomCondition,
+};
+use oar_values::{
+    ActorValue, Cmp, DirectionValue, FormID, FormValue, GraphValue, Keyword, LiteralValue,
+    NumericValue, PluginValue, RandomValue, StaticValue, TypeValue,
+};
+
+/// Read-only visitor of OAR conditions and their values.
+pub trait Visit<'ast> {
+    /// `config.json` of an animation root(section).
+    fn visit_conditions_config(&mut self, node: &'ast ConditionsConfig<'_>) {
+        visit_conditions_config(self, node);
+    }
+
+    /// `config.json` of a namespace.
+    fn visit_main_config(&mut self, node: &'ast MainConfig<'_>) {
+        visit_main_config(self, node);
+    }
+
+    /// A condition preset of a namespace.
+    fn visit_condition_preset(&mut self, node: &'ast ConditionPreset<'_>) {
+        visit_condition_preset(self, node);
+    }
+
+    /// Any condition. Dispatches to the variant's method.
+    fn visit_oar(&mut self, node: &'ast Oar<'_>) {
+        visit_oar(self, node);
+    }
+
+    /// `AND`
+    fn visit_and(&mut self, node: &'ast And<'_>) {
+        visit_and(self, node);
+    }
+
+    /// `OR`
+    fn visit_or(&mut self, node: &'ast Or<'_>) {
+        visit_or(self, node);
+    }
+
+    /// `PRESET`
+    fn visit_preset_condition(&mut self, _node: &'ast PresetCondition<'_>) {}
+
+    /// A condition with no arguments. e.g. `IsFemale`
+    fn visit_condition(&mut self, _node: &'ast Condition<'_>) {}
+
+    /// `CompareValues`
+    fn visit_compare_values(&mut self, node: &'ast CompareValues<'_>) {
+        visit_compare_values(self, node);
+    }
+
+    /// `CurrentGameTime`
+    fn visit_current_game_time(&mut self, node: &'ast CurrentGameTime<'_>) {
+        visit_current_game_time(self, node);
+    }
+
+    /// `CurrentWeather`
+    fn visit_current_weather(&mut self, node: &'ast CurrentWeather<'_>) {
+        visit_current_weather(self, node);
+    }
+
+    /// `FactionRank`
+    fn visit_faction_rank(&mut self, node: &'ast FactionRank<'_>) {
+        visit_faction_rank(self, node);
+    }
+
+    /// `HasKeyword`
+    fn visit_has_keyword(&mut self, node: &'ast HasKeyword<'_>) {
+        visit_has_keyword(self, node);
+    }
+
+    /// `HasMagicEffect`
+    fn visit_has_magic_effect(&mut self, node: &'ast HasMagicEffect<'_>) {
+        visit_has_magic_effect(self, node);
+    }
+
+    /// `HasMagicEffectWithKeyword`
+    fn visit_has_magic_effect_with_keyword(&mut self, node: &'ast HasMagicEffectWithKeyword<'_>) {
+        visit_has_magic_effect_with_keyword(self, node);
+    }
+
+    /// `HasPerk`
+    fn visit_has_perk(&mut self, node: &'ast HasPerk<'_>) {
+        visit_has_perk(self, node);
+    }
+
+    /// `HasRefType`
+    fn visit_has_ref_type(&mut self, node: &'ast HasRefType<'_>) {
+        visit_has_ref_type(self, node);
+    }
+
+    /// `HasSpell`
+    fn visit_has_spell(&mut self, node: &'ast HasSpell<'_>) {
+        visit_has_spell(self, node);
+    }
+
+    /// `IsActorBase`
+    fn visit_is_actor_base(&mut self, node: &'ast IsActorBase<'_>) {
+        visit_is_actor_base(self, node);
+    }
+
+    /// `IsClass`
+    fn visit_is_class(&mut self, node: &'ast IsClass<'_>) {
+        visit_is_class(self, node);
+    }
+
+    /// `IsCombatStyle`
+    fn visit_is_combat_style(&mut self, node: &'ast IsCombatStyle<'_>) {
+        visit_is_combat_style(self, node);
+    }
+
+    /// `IsEquipped`
+    fn visit_is_equipped(&mut self, node: &'ast IsEquipped<'_>) {
+        visit_is_equipped(self, node);
+    }
+
+    /// `IsEquippedHasKeyword`
+    fn visit_is_equipped_has_keyword(&mut self, node: &'ast IsEquippedHasKeyword<'_>) {
+        visit_is_equipped_has_keyword(self, node);
+    }
+
+    /// `IsEquippedShout`
+    fn visit_is_equipped_shout(&mut self, node: &'ast IsEquippedShout<'_>) {
+        visit_is_equipped_shout(self, node);
+    }
+
+    /// `IsEquippedType`
+    fn visit_is_equipped_type(&mut self, node: &'ast IsEquippedType<'_>) {
+        visit_is_equipped_type(self, node);
+    }
+
+    /// `IsInFaction`
+    fn visit_is_in_faction(&mut self, node: &'ast IsInFaction<'_>) {
+        visit_is_in_faction(self, node);
+    }
+
+    /// `IsInLocation`
+    fn visit_is_in_location(&mut self, node: &'ast IsInLocation<'_>) {
+        visit_is_in_location(self, node);
+    }
+
+    /// `IsMovementDirection`
+    fn visit_is_movement_direction(&mut self, node: &'ast IsMovementDirection<'_>) {
+        visit_is_movement_direction(self, node);
+    }
+
+    /// `IsParentCell`
+    fn visit_is_parent_cell(&mut self, node: &'ast IsParentCell<'_>) {
+        visit_is_parent_cell(self, node);
+    }
+
+    /// `IsRace`
+    fn visit_is_race(&mut self, node: &'ast IsRace<'_>) {
+        visit_is_race(self, node);
+    }
+
+    /// `IsVoiceType`
+    fn visit_is_voice_type(&mut self, node: &'ast IsVoiceType<'_>) {
+        visit_is_voice_type(self, node);
+    }
+
+    /// `IsWorldSpace`
+    fn visit_is_world_space(&mut self, node: &'ast IsWorldSpace<'_>) {
+        visit_is_world_space(self, node);
+    }
+
+    /// `IsWorn`
+    fn visit_is_worn(&mut self, node: &'ast IsWorn<'_>) {
+        visit_is_worn(self, node);
+    }
+
+    /// `IsWornHasKeyword`
+    fn visit_is_worn_has_keyword(&mut self, node: &'ast IsWornHasKeyword<'_>) {
+        visit_is_worn_has_keyword(self, node);
+    }
+
+    /// `Level`
+    fn visit_level(&mut self, node: &'ast Level<'_>) {
+        visit_level(self, node);
+    }
+
+    /// `Random`
+    fn visit_random_condition(&mut self, node: &'ast RandomCondition<'_>) {
+        visit_random_condition(self, node);
+    }
+
+    /// Actor value of a numeric value.
+    fn visit_actor_value(&mut self, _node: &'ast ActorValue) {}
+
+    /// Comparison operator.
+    fn visit_cmp(&mut self, _node: &'ast Cmp) {}
+
+    /// Movement direction.
+    fn visit_direction_value(&mut self, _node: &'ast DirectionValue) {}
+
+    /// FormID of a plugin value.
+    fn visit_form_id(&mut self, _node: &'ast FormID<'_>) {}
+
+    /// A form wrapping a plugin value.
+    fn visit_form_value(&mut self, node: &'ast FormValue<'_>) {
+        visit_form_value(self, node);
+    }
+
+    /// Graph variable of a numeric value.
+    fn visit_graph_value(&mut self, _node: &'ast GraphValue<'_>) {}
+
+    /// A form or an `editorID`.
+    fn visit_keyword(&mut self, node: &'ast Keyword<'_>) {
+        visit_keyword(self, node);
+    }
+
+    /// `editorID` of a keyword.
+    fn visit_literal_value(&mut self, _node: &'ast LiteralValue<'_>) {}
+
+    /// Static, global, actor or graph value.
+    fn visit_numeric_value(&mut self, node: &'ast NumericValue<'_>) {
+        visit_numeric_value(self, node);
+    }
+
+    /// A pair of a plugin name and a FormID.
+    fn visit_plugin_value(&mut self, node: &'ast PluginValue<'_>) {
+        visit_plugin_value(self, node);
+    }
+
+    /// Range of `Random`.
+    fn visit_random_value(&mut self, _node: &'ast RandomValue) {}
+
+    /// A number.
+    fn visit_static_value(&mut self, _node: &'ast StaticValue) {}
+
+    /// Weapon type of `IsEquippedType`.
+    fn visit_type_value(&mut self, _node: &'ast TypeValue) {}
+}
+
+/// Visit the conditions of [`ConditionsConfig`].
+pub fn visit_conditions_config<'ast, V>(v: &mut V, node: &'ast ConditionsConfig<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for condition in &node.conditions {
+        v.visit_oar(condition);
+    }
+}
+
+/// Visit the condition presets of [`MainConfig`].
+pub fn visit_main_config<'ast, V>(v: &mut V, node: &'ast MainConfig<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for preset in &node.condition_presets {
+        v.visit_condition_preset(preset);
+    }
+}
+
+/// Visit the conditions of [`ConditionPreset`].
+pub fn visit_condition_preset<'ast, V>(v: &mut V, node: &'ast ConditionPreset<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for condition in &node.conditions {
+        v.visit_oar(condition);
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_oar<'ast, V>(v: &mut V, node: &'ast Oar<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Oar::And(node) => v.visit_and(node),
+        Oar::Preset(node) => v.visit_preset_condition(node),
+        Oar::Condition(node) => v.visit_condition(node),
+        Oar::CompareValues(node) => v.visit_compare_values(node),
+        Oar::CurrentGameTime(node) => v.visit_current_game_time(node),
+        Oar::CurrentWeather(node) => v.visit_current_weather(node),
+        Oar::FactionRank(node) => v.visit_faction_rank(node),
+        Oar::HasKeyword(node) => v.visit_has_keyword(node),
+        Oar::HasMagicEffect(node) => v.visit_has_magic_effect(node),
+        Oar::HasMagicEffectWithKeyword(node) => v.visit_has_magic_effect_with_keyword(node),
+        Oar::HasPerk(node) => v.visit_has_perk(node),
+        Oar::HasRefType(node) => v.visit_has_ref_type(node),
+        Oar::HasSpell(node) => v.visit_has_spell(node),
+        Oar::IsActorBase(node) => v.visit_is_actor_base(node),
+        Oar::IsClass(node) => v.visit_is_class(node),
+        Oar::IsCombatStyle(node) => v.visit_is_combat_style(node),
+        Oar::IsEquipped(node) => v.visit_is_equipped(node),
+        Oar::IsEquippedHasKeyword(node) => v.visit_is_equipped_has_keyword(node),
+        Oar::IsEquippedShout(node) => v.visit_is_equipped_shout(node),
+        Oar::IsEquippedType(node) => v.visit_is_equipped_type(node),
+        Oar::IsInFaction(node) => v.visit_is_in_faction(node),
+        Oar::IsInLocation(node) => v.visit_is_in_location(node),
+        Oar::IsParentCell(node) => v.visit_is_parent_cell(node),
+        Oar::IsRace(node) => v.visit_is_race(node),
+        Oar::IsVoiceType(node) => v.visit_is_voice_type(node),
+        Oar::IsWorldSpace(node) => v.visit_is_world_space(node),
+        Oar::IsWorn(node) => v.visit_is_worn(node),
+        Oar::IsWornHasKeyword(node) => v.visit_is_worn_has_keyword(node),
+        Oar::IsDirectionMovement(node) => v.visit_is_movement_direction(node),
+        Oar::Level(node) => v.visit_level(node),
+        Oar::Or(node) => v.visit_or(node),
+        Oar::RandomCondition(node) => v.visit_random_condition(node),
+    }
+}
+
+/// Visit the conditions of [`And`].
+pub fn visit_and<'ast, V>(v: &mut V, node: &'ast And<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for condition in &node.conditions {
+        v.visit_oar(condition);
+    }
+}
+
+/// Visit the conditions of [`Or`].
+pub fn visit_or<'ast, V>(v: &mut V, node: &'ast Or<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    for condition in &node.conditions {
+        v.visit_oar(condition);
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_keyword<'ast, V>(v: &mut V, node: &'ast Keyword<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        Keyword::Form(node) => v.visit_form_value(node),
+        Keyword::Literal(node) => v.visit_literal_value(node),
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_numeric_value<'ast, V>(v: &mut V, node: &'ast NumericValue<'_>)
+where
+    V: Visit<'ast> + ?Sized,
+{
+    match node {
+        NumericValue::StaticValue(node) => v.visit_static_value(node),
+        NumericValue::GlobalVariable(node) => v.visit_form_value(node),
+        NumericValue::ActorValue(node) => v.visit_actor_value(node),
+        NumericValue::GraphVariable(node) => v.visit_graph_value(node),
+    }
+}
+
+/// Generate functions visiting the fields of a node.
+macro_rules! visit_fields {
+    ($($fn_name:ident($ty:ident { $($field:ident => $visit:ident),+ })),+ $(,)?) => {
+        $(
+            #[doc = concat!("Visit the fields of [`", stringify!($ty), "`].")]
+            pub fn $fn_name<'ast, V>(v: &mut V, node: &'ast $ty<'_>)
+            where
+                V: Visit<'ast> + ?Sized,
+            {
+                $(v.$visit(&node.$field);)+
+            }
+        )+
+    };
+}
+
+visit_fields!(
+    visit_compare_values(CompareValues { value_a => visit_numeric_value, comparison => visit_cmp, value_b => visit_numeric_value }),
+    visit_current_game_time(CurrentGameTime { comparison => visit_cmp, numeric_value => visit_numeric_value }),
+    visit_current_weather(CurrentWeather { weather => visit_plugin_value }),
+    visit_faction_rank(FactionRank { faction => visit_plugin_value, comparison => visit_cmp, numeric_value => visit_numeric_value }),
+    visit_has_keyword(HasKeyword { keyword => visit_keyword }),
+    visit_has_magic_effect(HasMagicEffect { magic_effect => visit_plugin_value }),
+    visit_has_magic_effect_with_keyword(HasMagicEffectWithKeyword { keyword => visit_keyword }),
+    visit_has_perk(HasPerk { perk => visit_plugin_value }),
+    visit_has_ref_type(HasRefType { location_ref_type => visit_keyword }),
+    visit_has_spell(HasSpell { spell => visit_plugin_value }),
+    visit_is_actor_base(IsActorBase { actor_base => visit_plugin_value }),
+    visit_is_class(IsClass { class => visit_plugin_value }),
+    visit_is_combat_style(IsCombatStyle { combat_style => visit_plugin_value }),
+    visit_is_equipped(IsEquipped { form => visit_plugin_value }),
+    visit_is_equipped_has_keyword(IsEquippedHasKeyword { keyword => visit_keyword }),
+    visit_is_equipped_shout(IsEquippedShout { shout => visit_plugin_value }),
+    visit_is_equipped_type(IsEquippedType { type_value => visit_type_value }),
+    visit_is_in_faction(IsInFaction { faction => visit_plugin_value }),
+    visit_is_in_location(IsInLocation { location => visit_plugin_value }),
+    visit_is_movement_direction(IsMovementDirection { direction => visit_direction_value }),
+    visit_is_parent_cell(IsParentCell { cell => visit_plugin_value }),
+    visit_is_race(IsRace { race => visit_plugin_value }),
+    visit_is_voice_type(IsVoiceType { voice_type => visit_plugin_value }),
+    visit_is_world_space(IsWorldSpace { world_space => visit_plugin_value }),
+    visit_is_worn(IsWorn { form => visit_plugin_value }),
+    visit_is_worn_has_keyword(IsWornHasKeyword { keyword => visit_keyword }),
+    visit_level(Level { comparison => visit_cmp, numeric_value => visit_numeric_value }),
+    visit_random_condition(RandomCondition { random_value => visit_random_value, comparison => visit_cmp, numeric_value => visit_numeric_value }),
+    visit_form_value(FormValue { form => visit_plugin_value }),
+    visit_plugin_value(PluginValue { form_id => visit_form_id }),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cond;
+    use oar_values::Cmp;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Default)]
+    struct FormIds<'ast>(Vec<&'ast str>);
+
+    impl<'ast> Visit<'ast> for FormIds<'ast> {
+        fn visit_form_id(&mut self, node: &'ast FormID<'_>) {
+            self.0.push(node.as_str());
+        }
+    }
+
+    #[test]
+    fn should_collect_form_ids() {
+        let global = NumericValue::GlobalVariable(FormValue {
+            form: cond::form("Skyrim.esm", 0x39),
+        });
+        let config = ConditionsConfig::builder("test")
+            .condition(cond::is_race(cond::form("Skyrim.esm", 0x13746)) | cond::is_female())
+            .condition(cond::has_keyword(cond::form("Skyrim.esm", 0x1E713)))
+            .condition(cond::compare_values(cond::value(1.0), Cmp::Lt, global))
+            .build();
+
+        let mut form_ids = FormIds::default();
+        form_ids.visit_conditions_config(&config);
+        assert_eq!(form_ids.0, ["13746", "1E713", "39"]);
+    }
+}
@@ -0,0 +1,428 @@
+//! In-place traversal of OAR conditions.
+//!
+//! Each method of [`VisitMut`] walks into the children by default.
+//! To keep walking after overriding a method, call the free function of the same name.
+//! ```
+//! use oar_conditions::cond;
+//! use oar_conditions::visit_mut::{self, VisitMut};
+//! use oar_values::PluginValue;
+//!
+//! struct RenamePlugin;
+//!
+//! impl VisitMut for RenamePlugin {
+//!     fn visit_plugin_value_mut(&mut self, node: &mut PluginValue<'_>) {
+//!         if node.plugin_name == "Old.esp" {
+//!             node.plugin_name = "New.esp".into();
+//!         }
+//!         visit_mut::visit_plugin_value_mut(self, node);
+//!     }
+//! }
+//!
+//! let mut conditions = cond::is_worn(cond::form("Old.esp", 0x801)) & cond::is_female();
+//! RenamePlugin.visit_oar_mut(&mut conditions);
+//! assert_eq!(conditions, cond::is_worn(cond::form("New.esp", 0x801)) & cond::is_female());
+//! ```
+use crate::conditions::{
+    And, CompareValues, Condition, ConditionPreset, ConditionsConfig, CurrentGameTime,
+    CurrentWeather, FactionRank, HasKeyword, HasMagicEffect, HasMagicEffectWithKeyword, HasPerk,
+    HasRefType, HasSpell, IsActorBase, IsClass, IsCombatStyle, IsEquipped, IsEquippedHasKeyword,
+    IsEquippedShout, IsEquippedType, IsInFaction, IsInLocation, IsMovementDirection, IsParentCell,
+    IsRace, IsVoiceType, IsWorldSpace, IsWorn, IsWornHasKeyword, Level, MainConfig, Oar, Or,
+    PresetCondition, RandomCondition,
+};
+use oar_values::{
+    ActorValue, Cmp, DirectionValue, FormID, FormValue, GraphValue, Keyword, LiteralValue,
+    NumericValue, PluginValue, RandomValue, StaticValue, TypeValue,
+};
+
+/// In-place visitor of OAR conditions and their values.
+pub trait VisitMut {
+    /// `config.json` of an animation root(section).
+    fn visit_conditions_config_mut(&mut self, node: &mut ConditionsConfig<'_>) {
+        visit_conditions_config_mut(self, node);
+    }
+
+    /// `config.json` of a namespace.
+    fn visit_main_config_mut(&mut self, node: &mut MainConfig<'_>) {
+        visit_main_config_mut(self, node);
+    }
+
+    /// A condition preset of a namespace.
+    fn visit_condition_preset_mut(&mut self, node: &mut ConditionPreset<'_>) {
+        visit_condition_preset_mut(self, node);
+    }
+
+    /// Any condition. Dispatches to the variant's method.
+    fn visit_oar_mut(&mut self, node: &mut Oar<'_>) {
+        visit_oar_mut(self, node);
+    }
+
+    /// `AND`
+    fn visit_and_mut(&mut self, node: &mut And<'_>) {
+        visit_and_mut(self, node);
+    }
+
+    /// `OR`
+    fn visit_or_mut(&mut self, node: &mut Or<'_>) {
+        visit_or_mut(self, node);
+    }
+
+    /// `PRESET`
+    fn visit_preset_condition_mut(&mut self, _node: &mut PresetCondition<'_>) {}
+
+    /// A condition with no arguments. e.g. `IsFemale`
+    fn visit_condition_mut(&mut self, _node: &mut Condition<'_>) {}
+
+    /// `CompareValues`
+    fn visit_compare_values_mut(&mut self, node: &mut CompareValues<'_>) {
+        visit_compare_values_mut(self, node);
+    }
+
+    /// `CurrentGameTime`
+    fn visit_current_game_time_mut(&mut self, node: &mut CurrentGameTime<'_>) {
+        visit_current_game_time_mut(self, node);
+    }
+
+    /// `CurrentWeather`
+    fn visit_current_weather_mut(&mut self, node: &mut CurrentWeather<'_>) {
+        visit_current_weather_mut(self, node);
+    }
+
+    /// `FactionRank`
+    fn visit_faction_rank_mut(&mut self, node: &mut FactionRank<'_>) {
+        visit_faction_rank_mut(self, node);
+    }
+
+    /// `HasKeyword`
+    fn visit_has_keyword_mut(&mut self, node: &mut HasKeyword<'_>) {
+        visit_has_keyword_mut(self, node);
+    }
+
+    /// `HasMagicEffect`
+    fn visit_has_magic_effect_mut(&mut self, node: &mut HasMagicEffect<'_>) {
+        visit_has_magic_effect_mut(self, node);
+    }
+
+    /// `HasMagicEffectWithKeyword`
+    fn visit_has_magic_effect_with_keyword_mut(
+        &mut self,
+        node: &mut HasMagicEffectWithKeyword<'_>,
+    ) {
+        visit_has_magic_effect_with_keyword_mut(self, node);
+    }
+
+    /// `HasPerk`
+    fn visit_has_perk_mut(&mut self, node: &mut HasPerk<'_>) {
+        visit_has_perk_mut(self, node);
+    }
+
+    /// `HasRefType`
+    fn visit_has_ref_type_mut(&mut self, node: &mut HasRefType<'_>) {
+        visit_has_ref_type_mut(self, node);
+    }
+
+    /// `HasSpell`
+    fn visit_has_spell_mut(&mut self, node: &mut HasSpell<'_>) {
+        visit_has_spell_mut(self, node);
+    }
+
+    /// `IsActorBase`
+    fn visit_is_actor_base_mut(&mut self, node: &mut IsActorBase<'_>) {
+        visit_is_actor_base_mut(self, node);
+    }
+
+    /// `IsClass`
+    fn visit_is_class_mut(&mut self, node: &mut IsClass<'_>) {
+        visit_is_class_mut(self, node);
+    }
+
+    /// `IsCombatStyle`
+    fn visit_is_combat_style_mut(&mut self, node: &mut IsCombatStyle<'_>) {
+        visit_is_combat_style_mut(self, node);
+    }
+
+    /// `IsEquipped`
+    fn visit_is_equipped_mut(&mut self, node: &mut IsEquipped<'_>) {
+        visit_is_equipped_mut(self, node);
+    }
+
+    /// `IsEquippedHasKeyword`
+    fn visit_is_equipped_has_keyword_mut(&mut self, node: &mut IsEquippedHasKeyword<'_>) {
+        visit_is_equipped_has_keyword_mut(self, node);
+    }
+
+    /// `IsEquippedShout`
+    fn visit_is_equipped_shout_mut(&mut self, node: &mut IsEquippedShout<'_>) {
+        visit_is_equipped_shout_mut(self, node);
+    }
+
+    /// `IsEquippedType`
+    fn visit_is_equipped_type_mut(&mut self, node: &mut IsEquippedType<'_>) {
+        visit_is_equipped_type_mut(self, node);
+    }
+
+    /// `IsInFaction`
+    fn visit_is_in_faction_mut(&mut self, node: &mut IsInFaction<'_>) {
+        visit_is_in_faction_mut(self, node);
+    }
+
+    /// `IsInLocation`
+    fn visit_is_in_location_mut(&mut self, node: &mut IsInLocation<'_>) {
+        visit_is_in_location_mut(self, node);
+    }
+
+    /// `IsMovementDirection`
+    fn visit_is_movement_direction_mut(&mut self, node: &mut IsMovementDirection<'_>) {
+        visit_is_movement_direction_mut(self, node);
+    }
+
+    /// `IsParentCell`
+    fn visit_is_parent_cell_mut(&mut self, node: &mut IsParentCell<'_>) {
+        visit_is_parent_cell_mut(self, node);
+    }
+
+    /// `IsRace`
+    fn visit_is_race_mut(&mut self, node: &mut IsRace<'_>) {
+        visit_is_race_mut(self, node);
+    }
+
+    /// `IsVoiceType`
+    fn visit_is_voice_type_mut(&mut self, node: &mut IsVoiceType<'_>) {
+        visit_is_voice_type_mut(self, node);
+    }
+
+    /// `IsWorldSpace`
+    fn visit_is_world_space_mut(&mut self, node: &mut IsWorldSpace<'_>) {
+        visit_is_world_space_mut(self, node);
+    }
+
+    /// `IsWorn`
+    fn visit_is_worn_mut(&mut self, node: &mut IsWorn<'_>) {
+        visit_is_worn_mut(self, node);
+    }
+
+    /// `IsWornHasKeyword`
+    fn visit_is_worn_has_keyword_mut(&mut self, node: &mut IsWornHasKeyword<'_>) {
+        visit_is_worn_has_keyword_mut(self, node);
+    }
+
+    /// `Level`
+    fn visit_level_mut(&mut self, node: &mut Level<'_>) {
+        visit_level_mut(self, node);
+    }
+
+    /// `Random`
+    fn visit_random_condition_mut(&mut self, node: &mut RandomCondition<'_>) {
+        visit_random_condition_mut(self, node);
+    }
+
+    /// Actor value of a numeric value.
+    fn visit_actor_value_mut(&mut self, _node: &mut ActorValue) {}
+
+    /// Comparison operator.
+    fn visit_cmp_mut(&mut self, _node: &mut Cmp) {}
+
+    /// Movement direction.
+    fn visit_direction_value_mut(&mut self, _node: &mut DirectionValue) {}
+
+    /// FormID of a plugin value.
+    fn visit_form_id_mut(&mut self, _node: &mut FormID<'_>) {}
+
+    /// A form wrapping a plugin value.
+    fn visit_form_value_mut(&mut self, node: &mut FormValue<'_>) {
+        visit_form_value_mut(self, node);
+    }
+
+    /// Graph variable of a numeric value.
+    fn visit_graph_value_mut(&mut self, _node: &mut GraphValue<'_>) {}
+
+    /// A form or an `editorID`.
+    fn visit_keyword_mut(&mut self, node: &mut Keyword<'_>) {
+        visit_keyword_mut(self, node);
+    }
+
+    /// `editorID` of a keyword.
+    fn visit_literal_value_mut(&mut self, _node: &mut LiteralValue<'_>) {}
+
+    /// Static, global, actor or graph value.
+    fn visit_numeric_value_mut(&mut self, node: &mut NumericValue<'_>) {
+        visit_numeric_value_mut(self, node);
+    }
+
+    /// A pair of a plugin name and a FormID.
+    fn visit_plugin_value_mut(&mut self, node: &mut PluginValue<'_>) {
+        visit_plugin_value_mut(self, node);
+    }
+
+    /// Range of `Random`.
+    fn visit_random_value_mut(&mut self, _node: &mut RandomValue) {}
+
+    /// A number.
+    fn visit_static_value_mut(&mut self, _node: &mut StaticValue) {}
+
+    /// Weapon type of `IsEquippedType`.
+    fn visit_type_value_mut(&mut self, _node: &mut TypeValue) {}
+}
+
+/// Visit the conditions of [`ConditionsConfig`].
+pub fn visit_conditions_config_mut<V>(v: &mut V, node: &mut ConditionsConfig<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    for condition in &mut node.conditions {
+        v.visit_oar_mut(condition);
+    }
+}
+
+/// Visit the condition presets of [`MainConfig`].
+pub fn visit_main_config_mut<V>(v: &mut V, node: &mut MainConfig<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    for preset in &mut node.condition_presets {
+        v.visit_condition_preset_mut(preset);
+    }
+}
+
+/// Visit the conditions of [`ConditionPreset`].
+pub fn visit_condition_preset_mut<V>(v: &mut V, node: &mut ConditionPreset<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    for condition in &mut node.conditions {
+        v.visit_oar_mut(condition);
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_oar_mut<V>(v: &mut V, node: &mut Oar<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Oar::And(node) => v.visit_and_mut(node),
+        Oar::Preset(node) => v.visit_preset_condition_mut(node),
+        Oar::Condition(node) => v.visit_condition_mut(node),
+        Oar::CompareValues(node) => v.visit_compare_values_mut(node),
+        Oar::CurrentGameTime(node) => v.visit_current_game_time_mut(node),
+        Oar::CurrentWeather(node) => v.visit_current_weather_mut(node),
+        Oar::FactionRank(node) => v.visit_faction_rank_mut(node),
+        Oar::HasKeyword(node) => v.visit_has_keyword_mut(node),
+        Oar::HasMagicEffect(node) => v.visit_has_magic_effect_mut(node),
+        Oar::HasMagicEffectWithKeyword(node) => v.visit_has_magic_effect_with_keyword_mut(node),
+        Oar::HasPerk(node) => v.visit_has_perk_mut(node),
+        Oar::HasRefType(node) => v.visit_has_ref_type_mut(node),
+        Oar::HasSpell(node) => v.visit_has_spell_mut(node),
+        Oar::IsActorBase(node) => v.visit_is_actor_base_mut(node),
+        Oar::IsClass(node) => v.visit_is_class_mut(node),
+        Oar::IsCombatStyle(node) => v.visit_is_combat_style_mut(node),
+        Oar::IsEquipped(node) => v.visit_is_equipped_mut(node),
+        Oar::IsEquippedHasKeyword(node) => v.visit_is_equipped_has_keyword_mut(node),
+        Oar::IsEquippedShout(node) => v.visit_is_equipped_shout_mut(node),
+        Oar::IsEquippedType(node) => v.visit_is_equipped_type_mut(node),
+        Oar::IsInFaction(node) => v.visit_is_in_faction_mut(node),
+        Oar::IsInLocation(node) => v.visit_is_in_location_mut(node),
+        Oar::IsParentCell(node) => v.visit_is_parent_cell_mut(node),
+        Oar::IsRace(node) => v.visit_is_race_mut(node),
+        Oar::IsVoiceType(node) => v.visit_is_voice_type_mut(node),
+        Oar::IsWorldSpace(node) => v.visit_is_world_space_mut(node),
+        Oar::IsWorn(node) => v.visit_is_worn_mut(node),
+        Oar::IsWornHasKeyword(node) => v.visit_is_worn_has_keyword_mut(node),
+        Oar::IsDirectionMovement(node) => v.visit_is_movement_direction_mut(node),
+        Oar::Level(node) => v.visit_level_mut(node),
+        Oar::Or(node) => v.visit_or_mut(node),
+        Oar::RandomCondition(node) => v.visit_random_condition_mut(node),
+    }
+}
+
+/// Visit the conditions of [`And`].
+pub fn visit_and_mut<V>(v: &mut V, node: &mut And<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    for condition in &mut node.conditions {
+        v.visit_oar_mut(condition);
+    }
+}
+
+/// Visit the conditions of [`Or`].
+pub fn visit_or_mut<V>(v: &mut V, node: &mut Or<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    for condition in &mut node.conditions {
+        v.visit_oar_mut(condition);
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_keyword_mut<V>(v: &mut V, node: &mut Keyword<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        Keyword::Form(node) => v.visit_form_value_mut(node),
+        Keyword::Literal(node) => v.visit_literal_value_mut(node),
+    }
+}
+
+/// Dispatch to the method of the variant.
+pub fn visit_numeric_value_mut<V>(v: &mut V, node: &mut NumericValue<'_>)
+where
+    V: VisitMut + ?Sized,
+{
+    match node {
+        NumericValue::StaticValue(node) => v.visit_static_value_mut(node),
+        NumericValue::GlobalVariable(node) => v.visit_form_value_mut(node),
+        NumericValue::ActorValue(node) => v.visit_actor_value_mut(node),
+        NumericValue::GraphVariable(node) => v.visit_graph_value_mut(node),
+    }
+}
+
+/// Generate functions visiting the fields of a node.
+macro_rules! visit_fields_mut {
+    ($($fn_name:ident($ty:ident { $($field:ident => $visit:ident),+ })),+ $(,)?) => {
+        $(
+            #[doc = concat!("Visit the fields of [`", stringify!($ty), "`].")]
+            pub fn $fn_name<V>(v: &mut V, node: &mut $ty<'_>)
+            where
+                V: VisitMut + ?Sized,
+            {
+                $(v.$visit(&mut node.$field);)+
+            }
+        )+
+    };
+}
+
+visit_fields_mut!(
+    visit_compare_values_mut(CompareValues { value_a => visit_numeric_value_mut, comparison => visit_cmp_mut, value_b => visit_numeric_value_mut }),
+    visit_current_game_time_mut(CurrentGameTime { comparison => visit_cmp_mut, numeric_value => visit_numeric_value_mut }),
+    visit_current_weather_mut(CurrentWeather { weather => visit_plugin_value_mut }),
+    visit_faction_rank_mut(FactionRank { faction => visit_plugin_value_mut, comparison => visit_cmp_mut, numeric_value => visit_numeric_value_mut }),
+    visit_has_keyword_mut(HasKeyword { keyword => visit_keyword_mut }),
+    visit_has_magic_effect_mut(HasMagicEffect { magic_effect => visit_plugin_value_mut }),
+    visit_has_magic_effect_with_keyword_mut(HasMagicEffectWithKeyword { keyword => visit_keyword_mut }),
+    visit_has_perk_mut(HasPerk { perk => visit_plugin_value_mut }),
+    visit_has_ref_type_mut(HasRefType { location_ref_type => visit_keyword_mut }),
+    visit_has_spell_mut(HasSpell { spell => visit_plugin_value_mut }),
+    visit_is_actor_base_mut(IsActorBase { actor_base => visit_plugin_value_mut }),
+    visit_is_class_mut(IsClass { class => visit_plugin_value_mut }),
+    visit_is_combat_style_mut(IsCombatStyle { combat_style => visit_plugin_value_mut }),
+    visit_is_equipped_mut(IsEquipped { form => visit_plugin_value_mut }),
+    visit_is_equipped_has_keyword_mut(IsEquippedHasKeyword { keyword => visit_keyword_mut }),
+    visit_is_equipped_shout_mut(IsEquippedShout { shout => visit_plugin_value_mut }),
+    visit_is_equipped_type_mut(IsEquippedType { type_value => visit_type_value_mut }),
+    visit_is_in_faction_mut(IsInFaction { faction => visit_plugin_value_mut }),
+    visit_is_in_location_mut(IsInLocation { location => visit_plugin_value_mut }),
+    visit_is_movement_direction_mut(IsMovementDirection { direction => visit_direction_value_mut }),
+    visit_is_parent_cell_mut(IsParentCell { cell => visit_plugin_value_mut }),
+    visit_is_race_mut(IsRace { race => visit_plugin_value_mut }),
+    visit_is_voice_type_mut(IsVoiceType { voice_type => visit_plugin_value_mut }),
+    visit_is_world_space_mut(IsWorldSpace { world_space => visit_plugin_value_mut }),
+    visit_is_worn_mut(IsWorn { form => visit_plugin_value_mut }),
+    visit_is_worn_has_keyword_mut(IsWornHasKeyword { keyword => visit_keyword_mut }),
+    visit_level_mut(Level { comparison => visit_cmp_mut, numeric_value => visit_numeric_value_mut }),
+    visit_random_condition_mut(RandomCondition { random_value => visit_random_value_mut, comparison => visit_cmp_mut, numeric_value => visit_numeric_value_mut }),
+    visit_form_value_mut(FormValue { form => visit_plugin_value_mut }),
+    visit_plugin_value_mut(PluginValue { form_id => visit_form_id_mut }),
+);
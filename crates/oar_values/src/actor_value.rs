@@ -1,13 +1,15 @@
 //! Person and its internal value
+use crate::ActorValueIndex;
 use serde::{Deserialize, Serialize};
 
 /// Person and its internal value
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ActorValue {
-    /// default: 0
+    /// Actor value index. default: 0(Aggression)
     #[serde(default)]
     #[serde(rename = "actorValue")]
-    pub actor_value: i64, // FIXME?: correct?
+    pub actor_value: ActorValueIndex,
     /// OAR GUI selection => config.json value
     /// - Actor Value => "Value"
     /// - Base Actor Value => "Base"
@@ -25,6 +27,7 @@ pub struct ActorValue {
 ///
 /// default: `ActorValue`
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum ActorValueType {
     /// Value
     #[default]
@@ -0,0 +1,542 @@
+//! Skyrim actor value indices(`actorValue` of OAR)
+use crate::ValueError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_untagged::UntaggedEnumVisitor;
+use std::fmt;
+use std::str::FromStr;
+
+/// Generate [`ActorValueIndex`] and the table of its variants.
+macro_rules! actor_value_index {
+    ($($(#[$attr:meta])* $name:ident,)+) => {
+        /// Skyrim actor value index. e.g. `24`(Health)
+        ///
+        /// (De)serialized as the number, same as OAR `actorValue`.
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum ActorValueIndex {
+            #[default]
+            $($(#[$attr])* $name,)+
+            /// Index not in the table(e.g. added by a newer game version). Kept as is.
+            Other(u32),
+        }
+
+        impl ActorValueIndex {
+            /// All the known actor values in index order.
+            pub const ALL: &[Self] = &[$(Self::$name,)+];
+
+            /// Actor value index. e.g. `24`
+            pub const fn index(self) -> u32 {
+                #[repr(u32)]
+                enum Index {
+                    $($name,)+
+                }
+
+                match self {
+                    $(Self::$name => Index::$name as u32,)+
+                    Self::Other(index) => index,
+                }
+            }
+
+            /// Name of the actor value. e.g. `"Health"`, `None` if unknown.
+            pub const fn name(self) -> Option<&'static str> {
+                match self {
+                    $(Self::$name => Some(stringify!($name)),)+
+                    Self::Other(_) => None,
+                }
+            }
+        }
+    };
+}
+
+actor_value_index! {
+    /// 0
+    Aggression,
+    /// 1
+    Confidence,
+    /// 2
+    Energy,
+    /// 3
+    Morality,
+    /// 4
+    Mood,
+    /// 5
+    Assistance,
+    /// 6
+    OneHanded,
+    /// 7
+    TwoHanded,
+    /// 8
+    Archery,
+    /// 9
+    Block,
+    /// 10
+    Smithing,
+    /// 11
+    HeavyArmor,
+    /// 12
+    LightArmor,
+    /// 13
+    Pickpocket,
+    /// 14
+    Lockpicking,
+    /// 15
+    Sneak,
+    /// 16
+    Alchemy,
+    /// 17
+    Speech,
+    /// 18
+    Alteration,
+    /// 19
+    Conjuration,
+    /// 20
+    Destruction,
+    /// 21
+    Illusion,
+    /// 22
+    Restoration,
+    /// 23
+    Enchanting,
+    /// 24
+    Health,
+    /// 25
+    Magicka,
+    /// 26
+    Stamina,
+    /// 27
+    HealRate,
+    /// 28
+    MagickaRate,
+    /// 29
+    StaminaRate,
+    /// 30
+    SpeedMult,
+    /// 31
+    InventoryWeight,
+    /// 32
+    CarryWeight,
+    /// 33
+    CriticalChance,
+    /// 34
+    MeleeDamage,
+    /// 35
+    UnarmedDamage,
+    /// 36
+    Mass,
+    /// 37
+    VoicePoints,
+    /// 38
+    VoiceRate,
+    /// 39
+    DamageResist,
+    /// 40
+    PoisonResist,
+    /// 41
+    ResistFire,
+    /// 42
+    ResistShock,
+    /// 43
+    ResistFrost,
+    /// 44
+    ResistMagic,
+    /// 45
+    ResistDisease,
+    /// 46
+    PerceptionCondition,
+    /// 47
+    EnduranceCondition,
+    /// 48
+    LeftAttackCondition,
+    /// 49
+    RightAttackCondition,
+    /// 50
+    LeftMobilityCondition,
+    /// 51
+    RightMobilityCondition,
+    /// 52
+    BrainCondition,
+    /// 53
+    Paralysis,
+    /// 54
+    Invisibility,
+    /// 55
+    NightEye,
+    /// 56
+    DetectLifeRange,
+    /// 57
+    WaterBreathing,
+    /// 58
+    WaterWalking,
+    /// 59
+    IgnoreCrippledLimbs,
+    /// 60
+    Fame,
+    /// 61
+    Infamy,
+    /// 62
+    JumpingBonus,
+    /// 63
+    WardPower,
+    /// 64
+    RightItemCharge,
+    /// 65
+    ArmorPerks,
+    /// 66
+    ShieldPerks,
+    /// 67
+    WardDeflection,
+    /// 68
+    Variable01,
+    /// 69
+    Variable02,
+    /// 70
+    Variable03,
+    /// 71
+    Variable04,
+    /// 72
+    Variable05,
+    /// 73
+    Variable06,
+    /// 74
+    Variable07,
+    /// 75
+    Variable08,
+    /// 76
+    Variable09,
+    /// 77
+    Variable10,
+    /// 78
+    BowSpeedBonus,
+    /// 79
+    FavorActive,
+    /// 80
+    FavorsPerDay,
+    /// 81
+    FavorsPerDayTimer,
+    /// 82
+    LeftItemCharge,
+    /// 83
+    AbsorbChance,
+    /// 84
+    Blindness,
+    /// 85
+    WeaponSpeedMult,
+    /// 86
+    ShoutRecoveryMult,
+    /// 87
+    BowStaggerBonus,
+    /// 88
+    Telekinesis,
+    /// 89
+    FavorPointsBonus,
+    /// 90
+    LastBribedIntimidated,
+    /// 91
+    LastFlattered,
+    /// 92
+    MovementNoiseMult,
+    /// 93
+    BypassVendorStolenCheck,
+    /// 94
+    BypassVendorKeywordCheck,
+    /// 95
+    WaitingForPlayer,
+    /// 96
+    OneHandedModifier,
+    /// 97
+    TwoHandedModifier,
+    /// 98
+    MarksmanModifier,
+    /// 99
+    BlockModifier,
+    /// 100
+    SmithingModifier,
+    /// 101
+    HeavyArmorModifier,
+    /// 102
+    LightArmorModifier,
+    /// 103
+    PickpocketModifier,
+    /// 104
+    LockpickingModifier,
+    /// 105
+    SneakingModifier,
+    /// 106
+    AlchemyModifier,
+    /// 107
+    SpeechcraftModifier,
+    /// 108
+    AlterationModifier,
+    /// 109
+    ConjurationModifier,
+    /// 110
+    DestructionModifier,
+    /// 111
+    IllusionModifier,
+    /// 112
+    RestorationModifier,
+    /// 113
+    EnchantingModifier,
+    /// 114
+    OneHandedSkillAdvance,
+    /// 115
+    TwoHandedSkillAdvance,
+    /// 116
+    MarksmanSkillAdvance,
+    /// 117
+    BlockSkillAdvance,
+    /// 118
+    SmithingSkillAdvance,
+    /// 119
+    HeavyArmorSkillAdvance,
+    /// 120
+    LightArmorSkillAdvance,
+    /// 121
+    PickpocketSkillAdvance,
+    /// 122
+    LockpickingSkillAdvance,
+    /// 123
+    SneakingSkillAdvance,
+    /// 124
+    AlchemySkillAdvance,
+    /// 125
+    SpeechcraftSkillAdvance,
+    /// 126
+    AlterationSkillAdvance,
+    /// 127
+    ConjurationSkillAdvance,
+    /// 128
+    DestructionSkillAdvance,
+    /// 129
+    IllusionSkillAdvance,
+    /// 130
+    RestorationSkillAdvance,
+    /// 131
+    EnchantingSkillAdvance,
+    /// 132
+    LeftWeaponSpeedMultiply,
+    /// 133
+    DragonSouls,
+    /// 134
+    CombatHealthRegenMultiply,
+    /// 135
+    OneHandedPowerModifier,
+    /// 136
+    TwoHandedPowerModifier,
+    /// 137
+    MarksmanPowerModifier,
+    /// 138
+    BlockPowerModifier,
+    /// 139
+    SmithingPowerModifier,
+    /// 140
+    HeavyArmorPowerModifier,
+    /// 141
+    LightArmorPowerModifier,
+    /// 142
+    PickpocketPowerModifier,
+    /// 143
+    LockpickingPowerModifier,
+    /// 144
+    SneakingPowerModifier,
+    /// 145
+    AlchemyPowerModifier,
+    /// 146
+    SpeechcraftPowerModifier,
+    /// 147
+    AlterationPowerModifier,
+    /// 148
+    ConjurationPowerModifier,
+    /// 149
+    DestructionPowerModifier,
+    /// 150
+    IllusionPowerModifier,
+    /// 151
+    RestorationPowerModifier,
+    /// 152
+    EnchantingPowerModifier,
+    /// 153
+    DragonRend,
+    /// 154
+    AttackDamageMult,
+    /// 155
+    HealRateMult,
+    /// 156
+    MagickaRateMult,
+    /// 157
+    StaminaRateMult,
+    /// 158
+    WerewolfPerks,
+    /// 159
+    VampirePerks,
+    /// 160
+    GrabActorOffset,
+    /// 161
+    Grabbed,
+    /// 162
+    Deprecated05,
+    /// 163
+    ReflectDamage,
+}
+
+impl ActorValueIndex {
+    /// Get the known actor value of `index`.
+    pub fn from_index(index: i64) -> Option<Self> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| Self::ALL.get(index).copied())
+    }
+
+    /// Get the known actor value of `index`, or [`Self::Other`].
+    pub fn from_raw(index: u32) -> Self {
+        Self::from_index(index.into()).unwrap_or(Self::Other(index))
+    }
+}
+
+impl TryFrom<f64> for ActorValueIndex {
+    type Error = ValueError;
+
+    /// Only integral values in the range are accepted. e.g. `24.0`, but not `24.5`
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        let invalid = || ValueError::CastError {
+            expected: format!("actor value index(integer 0..={})", Self::ALL.len() - 1),
+            actual: value.to_string(),
+        };
+        if value.fract() != 0.0 || !(0.0..Self::ALL.len() as f64).contains(&value) {
+            return Err(invalid());
+        }
+        Self::from_index(value as i64).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for ActorValueIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{}", self.index()),
+        }
+    }
+}
+
+impl FromStr for ActorValueIndex {
+    type Err = ValueError;
+
+    /// Parse the name(case insensitive) or the index. e.g. `"health"`, `"24"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(value) = Self::ALL.iter().find(|value| {
+            value
+                .name()
+                .is_some_and(|name| name.eq_ignore_ascii_case(s))
+        }) {
+            return Ok(*value);
+        }
+        s.parse::<f64>()
+            .map_err(|_| ValueError::CastError {
+                expected: "actor value name or index. e.g. `Health`, `24`".into(),
+                actual: s.to_string(),
+            })
+            .and_then(Self::try_from)
+    }
+}
+
+impl Serialize for ActorValueIndex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i64(self.index().into())
+    }
+}
+
+impl<'de> Deserialize<'de> for ActorValueIndex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Unknown indices are kept as `Other` not to fail the whole config.json.
+        let invalid = |unexpected| {
+            serde::de::Error::invalid_value(unexpected, &"actor value index(non-negative integer)")
+        };
+        UntaggedEnumVisitor::new()
+            .i64(|int| {
+                u32::try_from(int)
+                    .map(Self::from_raw)
+                    .map_err(|_| invalid(serde::de::Unexpected::Signed(int)))
+            })
+            .u64(|int| {
+                u32::try_from(int)
+                    .map(Self::from_raw)
+                    .map_err(|_| invalid(serde::de::Unexpected::Unsigned(int)))
+            })
+            .f64(|float| {
+                if float.fract() != 0.0 || !(0.0..=f64::from(u32::MAX)).contains(&float) {
+                    return Err(invalid(serde::de::Unexpected::Float(float)));
+                }
+                Ok(Self::from_raw(float as u32))
+            })
+            .deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ActorValueIndex {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ActorValueIndex".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Skyrim actor value index. e.g. 24: Health, 25: Magicka, 26: Stamina",
+            "type": "integer",
+            "minimum": 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_convert_index_and_name() -> Result<(), ValueError> {
+        assert_eq!(ActorValueIndex::ALL.len(), 164);
+        assert_eq!(ActorValueIndex::Health.index(), 24);
+        assert_eq!(
+            ActorValueIndex::from_index(163),
+            Some(ActorValueIndex::ReflectDamage)
+        );
+        assert_eq!(ActorValueIndex::from_index(164), None);
+        assert_eq!(ActorValueIndex::try_from(26.0)?, ActorValueIndex::Stamina);
+        assert!(ActorValueIndex::try_from(24.5).is_err());
+        assert_eq!(
+            "onehanded".parse::<ActorValueIndex>()?,
+            ActorValueIndex::OneHanded
+        );
+        assert_eq!(ActorValueIndex::Magicka.to_string(), "Magicka");
+        assert_eq!(ActorValueIndex::from_raw(24), ActorValueIndex::Health);
+        assert_eq!(ActorValueIndex::from_raw(164), ActorValueIndex::Other(164));
+        assert_eq!(ActorValueIndex::Other(200).index(), 200);
+        assert_eq!(ActorValueIndex::Other(200).to_string(), "200");
+        assert!(ActorValueIndex::try_from(164.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_deserialize_number() -> Result<(), serde_json::Error> {
+        let value: ActorValueIndex = serde_json::from_str("25")?;
+        assert_eq!(value, ActorValueIndex::Magicka);
+        assert_eq!(serde_json::to_string(&value)?, "25");
+        assert!(serde_json::from_str::<ActorValueIndex>("-1").is_err());
+        assert!(serde_json::from_str::<ActorValueIndex>("24.5").is_err());
+
+        let value: ActorValueIndex = serde_json::from_str("200")?;
+        assert_eq!(value, ActorValueIndex::Other(200));
+        assert_eq!(serde_json::to_string(&value)?, "200");
+        assert_eq!(
+            serde_json::from_str::<ActorValueIndex>("200.0")?,
+            ActorValueIndex::Other(200)
+        );
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 /// - Ge: Greater than or equal(">=")
 /// - Le: Lesser than or equal("<="),
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Cmp {
     #[serde(rename = "==")]
     #[default]
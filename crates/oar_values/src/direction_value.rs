@@ -7,6 +7,7 @@ use super::ValueError;
 
 /// Actor's Direction
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DirectionValue {
     /// Actor's Direction value
     pub value: Direction,
@@ -76,6 +77,22 @@ impl Serialize for Direction {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Direction {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Direction".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Actor's direction. 0.0: None, 1.0: Forward, 2.0: Right, 3.0: Back, 4.0: Left",
+            "type": "number",
+            "minimum": 0.0,
+            "exclusiveMaximum": 5.0,
+        })
+    }
+}
+
 // NOTE: Numeric comparison with float(f32) does not work correctly, so cast from f64 to i64 and deserialize
 // See: https://github.com/rust-lang/rust/issues/41620
 impl<'de> Deserialize<'de> for Direction {
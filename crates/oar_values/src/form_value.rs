@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 /// Wrapper for wrapping pluginValue with a key called `form`
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FormValue<'a> {
     /// A combination of the plugin name and the ID in it.
     pub form: PluginValue<'a>,
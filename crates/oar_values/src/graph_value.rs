@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 /// Pair str & Int | Float | Bool
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GraphValue<'a> {
     /// string (`hkbBehaviorGraphStringData.variableNames`)
     #[serde(rename = "graphVariable")]
@@ -15,6 +16,7 @@ pub struct GraphValue<'a> {
 
 /// Float | Int | Bool
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum GraphVariableType {
     /// Floating point number
     #[default]
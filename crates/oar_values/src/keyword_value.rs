@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 // NOTE: Changing the order of enums will cause Deserialize to error.
 /// Trigger keywords
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Keyword<'a> {
     /// plugin value
@@ -1,5 +1,6 @@
 //! DAR Condition values
 mod actor_value;
+mod actor_value_index;
 mod comparison;
 mod direction_value;
 mod errors;
@@ -14,6 +15,7 @@ mod static_value;
 mod type_value;
 
 pub use self::actor_value::{ActorValue, ActorValueType};
+pub use self::actor_value_index::ActorValueIndex;
 pub use self::comparison::Cmp;
 pub use self::direction_value::{Direction, DirectionValue};
 pub use self::errors::ValueError;
@@ -23,7 +25,7 @@ pub use self::graph_value::GraphVariableType;
 pub use self::keyword_value::Keyword;
 pub use self::literal_value::LiteralValue;
 pub use self::numeric_value::NumericValue;
-pub use self::plugin_value::{FormID, PluginValue};
+pub use self::plugin_value::{FormID, FormIdStyle, PluginKind, PluginValue};
 pub use self::random_value::RandomValue;
 pub use self::static_value::StaticValue;
 pub use self::type_value::TypeValue;
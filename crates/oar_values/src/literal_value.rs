@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 /// Wrapper `editor_id`
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LiteralValue<'a> {
     /// Editor ID
     #[serde(rename = "editorID")]
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 /// In fact, it can be variously accepted rather than Numeric,
 /// but the GUI description of OAR says Numeric Value, so we follow it.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum NumericValue<'a> {
     /// Just f32 value
@@ -4,6 +4,7 @@ use std::borrow::Cow;
 
 /// A combination of the plugin name and the ID in it.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PluginValue<'a> {
     /// e.g. `Skyrim.esm`
     #[serde(rename = "pluginName")]
@@ -30,6 +31,7 @@ pub struct PluginValue<'a> {
 
 /// Non prefix(0x) hexadecimal ID
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FormID<'a>(Cow<'a, str>);
 
 impl<'a> FormID<'a> {
@@ -53,6 +55,123 @@ impl<'a> FormID<'a> {
             Err("invalid hex string")
         }
     }
+
+    /// Non prefix hexadecimal string
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Kind of a plugin, which decides the range of the FormIDs it defines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginKind {
+    /// `.esm` or the master flag: `0x000000`-`0xFFFFFF`
+    Master,
+    /// `.esp`: `0x000000`-`0xFFFFFF`
+    Plugin,
+    /// `.esl` or the light flag: `0x000`-`0xFFF`(`FE xxx yyy` in the load order)
+    Light,
+}
+
+impl PluginKind {
+    /// Guess the kind by the extension of `plugin_name`.
+    ///
+    /// NOTE: `.esp`/`.esm` flagged as light can only be found by reading the plugin header.
+    pub fn from_name(plugin_name: &str) -> Self {
+        let extension = plugin_name.rsplit_once('.').map(|(_, extension)| extension);
+        match extension {
+            Some(extension) if extension.eq_ignore_ascii_case("esl") => Self::Light,
+            Some(extension) if extension.eq_ignore_ascii_case("esm") => Self::Master,
+            _ => Self::Plugin,
+        }
+    }
+}
+
+/// How to write canonical FormIDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormIdStyle {
+    /// Write the hex digits in uppercase. e.g. `13BBF`
+    pub uppercase: bool,
+    /// Pad with zeros to at least this number of digits. e.g. `6`: `013BBF`
+    pub width: usize,
+}
+
+impl Default for FormIdStyle {
+    fn default() -> Self {
+        Self {
+            uppercase: true,
+            width: 0,
+        }
+    }
+}
+
+/// Object IDs below this are reserved for the engine(defined only in `Skyrim.esm`).
+const FIRST_PLUGIN_OBJECT_ID: u32 = 0x800;
+
+impl FormID<'_> {
+    /// Strip the load order byte(s) by `kind`, and write the rest in `style`.
+    ///
+    /// e.g. `0A012345` -> `12345` for a regular plugin, `FE012800` -> `800` for a light plugin.
+    ///
+    /// Returns the warning if the ID can't be valid for a plugin of `kind`. The ID written then is
+    /// - light plugin out of `0x000-0xFFF`: the whole ID(with the load order byte). e.g. `1800` -> `1800`
+    /// - non light plugin with `FE`: the ID without the load order byte. e.g. `FE012800` -> `12800`
+    /// - non `Skyrim.esm` below `0x800`: the ID without the load order byte. e.g. `0A000007` -> `7`
+    pub fn canonicalize(
+        &self,
+        plugin_name: &str,
+        kind: PluginKind,
+        style: FormIdStyle,
+    ) -> (FormID<'static>, Option<String>) {
+        let Ok(form_id) = u32::from_str_radix(self.as_str(), 16) else {
+            let form_id = FormID(Cow::Owned(self.as_str().to_string()));
+            return (form_id, Some("Invalid FormID.".to_string()));
+        };
+
+        let load_order = form_id >> 24;
+        let (object_id, warning) = match kind {
+            PluginKind::Light if load_order == 0xFE || form_id <= 0xFFF => (form_id & 0xFFF, None),
+            PluginKind::Light => (
+                form_id,
+                Some(format!(
+                    "Exceeds the light plugin range 0x000-0xFFF, but {plugin_name} is a light plugin."
+                )),
+            ),
+            PluginKind::Master | PluginKind::Plugin if load_order == 0xFE => (
+                form_id & 0x00FF_FFFF,
+                Some(format!(
+                    "Looks like a light plugin FormID(FE xxx), but {plugin_name} is not a light plugin."
+                )),
+            ),
+            PluginKind::Master | PluginKind::Plugin => {
+                let object_id = form_id & 0x00FF_FFFF;
+                let warning = (object_id < FIRST_PLUGIN_OBJECT_ID
+                    && !plugin_name.eq_ignore_ascii_case("Skyrim.esm"))
+                .then(|| {
+                    format!(
+                        "Object IDs below 0x800 are reserved for Skyrim.esm, not {plugin_name}."
+                    )
+                });
+                (object_id, warning)
+            }
+        };
+
+        let width = style.width;
+        let hex = match style.uppercase {
+            true => format!("{object_id:0width$X}"),
+            false => format!("{object_id:0width$x}"),
+        };
+        (FormID(Cow::Owned(hex)), warning)
+    }
+}
+
+impl From<String> for FormID<'_> {
+    /// - NOTE: non cast to hex
+    fn from(value: String) -> Self {
+        Self(value.into())
+    }
 }
 
 impl<'a> From<&'a str> for FormID<'a> {
@@ -84,6 +203,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn should_canonicalize_form_id() {
+        let canonicalize = |plugin: &str, form_id: &str, style| {
+            let kind = PluginKind::from_name(plugin);
+            let (form_id, warning) = FormID::from(form_id).canonicalize(plugin, kind, style);
+            (form_id.as_str().to_string(), warning.is_some())
+        };
+        let style = FormIdStyle::default();
+
+        assert_eq!(
+            canonicalize("Mod.esp", "a012345", style),
+            ("12345".into(), false)
+        );
+        assert_eq!(
+            canonicalize("Mini.esl", "fe012800", style),
+            ("800".into(), false)
+        );
+        assert_eq!(
+            canonicalize("Mini.esl", "1800", style),
+            ("1800".into(), true)
+        );
+        assert_eq!(
+            canonicalize("Mod.esp", "fe012800", style),
+            ("12800".into(), true)
+        );
+        assert_eq!(canonicalize("Mod.esp", "7", style), ("7".into(), true));
+        assert_eq!(canonicalize("Skyrim.esm", "7", style), ("7".into(), false));
+
+        let padded = FormIdStyle {
+            uppercase: false,
+            width: 8,
+        };
+        assert_eq!(
+            canonicalize("Skyrim.esm", "13BBF", padded),
+            ("00013bbf".into(), false)
+        );
+    }
+
+    #[test]
+    fn should_canonicalize_form_id_with_warnings() {
+        let canonicalize = |plugin: &str, kind, form_id: &str| {
+            let style = FormIdStyle::default();
+            let (form_id, warning) = FormID::from(form_id).canonicalize(plugin, kind, style);
+            (form_id.as_str().to_string(), warning.is_some())
+        };
+
+        // Light: kept with the load order byte.
+        assert_eq!(
+            canonicalize("Mini.esl", PluginKind::Light, "0a012800"),
+            ("A012800".into(), true)
+        );
+        // `FE` for a non light plugin: stripped of the load order byte.
+        assert_eq!(
+            canonicalize("Mod.esm", PluginKind::Master, "fe012800"),
+            ("12800".into(), true)
+        );
+        // Reserved object ID: stripped of the load order byte.
+        assert_eq!(
+            canonicalize("Mod.esp", PluginKind::Plugin, "0a000007"),
+            ("7".into(), true)
+        );
+    }
+
     #[test]
     fn should_deserialize_plugin_value() -> Result<(), serde_json::Error> {
         let actual: PluginValue = serde_json::from_str(
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// This struct has `min` and `max` fields to define the range of the random value.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RandomValue {
     /// The minimum value of the range.
     #[serde(default)]
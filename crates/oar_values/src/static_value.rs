@@ -15,6 +15,7 @@
 ///       "
 /// ```
 #[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StaticValue {
     // NOTE: Even if you change this to Cow<'a, str>, during JSON serialization
     // we cannot insert the value as a number without quotes.
@@ -6,6 +6,7 @@ use serde_untagged::UntaggedEnumVisitor;
 
 /// Wrapper for [`WeaponType`]
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TypeValue {
     /// Weapon type value
     pub value: WeaponType,
@@ -130,6 +131,22 @@ impl Serialize for WeaponType {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for WeaponType {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "WeaponType".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Weapon type. -1.0: Other, 0.0: Unarmed, 1.0: Sword, ... 18.0: Torch",
+            "type": "number",
+            "minimum": -1.0,
+            "exclusiveMaximum": 19.0,
+        })
+    }
+}
+
 // NOTE: Numeric comparison with float(f32) does not work correctly, so cast from f64 to i64 and deserialize
 // See: https://github.com/rust-lang/rust/issues/41620
 impl<'de> Deserialize<'de> for WeaponType {
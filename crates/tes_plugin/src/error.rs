@@ -0,0 +1,16 @@
+//! Errors of the plugin reader.
+use std::path::PathBuf;
+
+/// Errors that can occur while reading plugins.
+#[derive(Debug, snafu::Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    #[snafu(display("Failed to read {}: {source}", path.display()))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Plugin {name} is not found in {}", data_dir.display()))]
+    PluginNotFound { name: String, data_dir: PathBuf },
+}
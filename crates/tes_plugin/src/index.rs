@@ -0,0 +1,123 @@
+//! Plugins of a `Data` dir, read on first use.
+use crate::error::Error;
+use crate::plugin::Plugin;
+use crate::record::RecordType;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// A read plugin, or the reason it could not be read.
+type Loaded = Result<Arc<Plugin>, Arc<Error>>;
+
+/// Plugins of a `Data` dir, read on first use.
+///
+/// Reading is blocking(a master can be hundreds of MB), so async callers should read in
+/// `spawn_blocking` first and then use [`Self::loaded`].
+/// A plugin is read at most once, even if it failed.
+/// While a plugin is being read, only the callers of the same plugin wait for it.
+#[derive(Debug)]
+pub struct PluginIndex {
+    data_dir: PathBuf,
+    /// lowercase plugin name -> plugin
+    plugins: Mutex<HashMap<String, Arc<OnceLock<Loaded>>>>,
+}
+
+impl PluginIndex {
+    /// Create an index of the plugins in `data_dir`. Nothing is read yet.
+    pub fn new(data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            data_dir: data_dir.into(),
+            plugins: Mutex::default(),
+        }
+    }
+
+    /// `Data` dir
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Get the plugin named `name`(case insensitive), reading it if not yet.
+    ///
+    /// # Errors
+    /// The plugin is not found or invalid.
+    pub fn plugin(&self, name: &str) -> Loaded {
+        // Don't hold the map lock while reading, or all the other plugins wait for this one.
+        let slot = Arc::clone(self.lock().entry(name.to_ascii_lowercase()).or_default());
+        slot.get_or_init(|| self.read(name).map(Arc::new).map_err(Arc::new))
+            .clone()
+    }
+
+    /// Get the plugin named `name`(case insensitive) only if it has already been read.
+    ///
+    /// Never blocks on reading, so this is safe to call from async tasks.
+    pub fn loaded(&self, name: &str) -> Option<Loaded> {
+        self.lock()
+            .get(&name.to_ascii_lowercase())
+            .and_then(|slot| slot.get().cloned())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<OnceLock<Loaded>>>> {
+        self.plugins.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record type of `form_id` defined by `plugin`, or `None` if the plugin doesn't define it.
+    ///
+    /// # Errors
+    /// The plugin is not found or invalid.
+    pub fn lookup(&self, plugin: &str, form_id: u32) -> Result<Option<RecordType>, Arc<Error>> {
+        Ok(self.plugin(plugin)?.get(form_id))
+    }
+
+    fn read(&self, name: &str) -> Result<Plugin, Error> {
+        let path = self.find(name).ok_or_else(|| Error::PluginNotFound {
+            name: name.to_string(),
+            data_dir: self.data_dir.clone(),
+        })?;
+        Plugin::read(path)
+    }
+
+    /// Path of `name` in the data dir, ignoring the case(Windows file names are case insensitive).
+    fn find(&self, name: &str) -> Option<PathBuf> {
+        let path = self.data_dir.join(name);
+        if path.is_file() {
+            return Some(path);
+        }
+        std::fs::read_dir(&self.data_dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .is_some_and(|file_name| file_name.eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::tests::sample_plugin;
+    use temp_dir::TempDir;
+
+    #[test]
+    fn should_lookup_forms_in_data_dir() -> std::io::Result<()> {
+        let data_dir = TempDir::new()?;
+        std::fs::write(data_dir.path().join("Sample.esp"), sample_plugin(0))?;
+        let index = PluginIndex::new(data_dir.path());
+
+        assert!(index.loaded("Sample.esp").is_none());
+        assert!(matches!(
+            index.lookup("sample.ESP", 0x0100_0800),
+            Ok(Some(RecordType::KYWD))
+        ));
+        assert!(matches!(index.lookup("Sample.esp", 0x0100_0801), Ok(None)));
+        assert!(matches!(index.loaded("SAMPLE.esp"), Some(Ok(_))));
+        assert!(matches!(
+            index.lookup("Missing.esm", 0x800),
+            Err(err) if matches!(*err, Error::PluginNotFound { .. })
+        ));
+        Ok(())
+    }
+}
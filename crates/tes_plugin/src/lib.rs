@@ -0,0 +1,15 @@
+//! Minimal reader of TES4(Skyrim SE/AE) plugins(`.esp`/`.esm`/`.esl`).
+//!
+//! Reads only what is needed to validate the forms referenced by DAR conditions:
+//! the header(masters & flags) and the record type of each form the plugin defines.
+//! Record data other than the header is skipped, so compressed records need no decompression.
+
+pub mod error;
+pub mod index;
+pub mod plugin;
+pub mod record;
+
+pub use self::error::Error;
+pub use self::index::PluginIndex;
+pub use self::plugin::{Header, Plugin};
+pub use self::record::RecordType;
@@ -0,0 +1,291 @@
+//! Plugin header & record index.
+use crate::error::{Error, ReadFileSnafu};
+use crate::record::RecordType;
+use snafu::ResultExt as _;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::Path;
+
+/// Size of record & group headers(Skyrim)
+const HEADER_SIZE: usize = 24;
+
+/// `TES4` flag: master file(`.esm`)
+const FLAG_MASTER: u32 = 0x1;
+/// `TES4` flag: light plugin(`.esl`)
+const FLAG_LIGHT: u32 = 0x200;
+/// Record flag: data is zlib compressed
+const FLAG_COMPRESSED: u32 = 0x0004_0000;
+
+/// `TES4` record of a plugin.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Header {
+    /// `HEDR` version. e.g. `1.71`
+    pub version: f32,
+    /// `TES4` record flags
+    pub flags: u32,
+    /// `CNAM`
+    pub author: Option<String>,
+    /// `SNAM`
+    pub description: Option<String>,
+    /// `MAST`s in load order. The index is the upper byte of the FormIDs referring to them.
+    pub masters: Vec<String>,
+}
+
+/// A plugin with the record type of each form it defines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plugin {
+    /// File name. e.g. `Skyrim.esm`
+    pub name: String,
+    /// Header
+    pub header: Header,
+    /// Object ID(FormID without the master index) -> record type
+    ///
+    /// Overrides of master records are not included.
+    records: HashMap<u32, RecordType>,
+}
+
+impl Plugin {
+    /// Read the plugin at `path`.
+    ///
+    /// # Errors
+    /// Failed to read, or it is not a TES4 plugin.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let file = File::open(path).context(ReadFileSnafu { path })?;
+        Self::from_reader(name, BufReader::new(file)).context(ReadFileSnafu { path })
+    }
+
+    /// Parse a plugin named `name` from `reader`.
+    ///
+    /// # Errors
+    /// Failed to read, or it is not a TES4 plugin([`io::ErrorKind::InvalidData`]).
+    pub fn from_reader<R>(name: impl Into<String>, mut reader: BufReader<R>) -> io::Result<Self>
+    where
+        R: Read + Seek,
+    {
+        let name = name.into();
+        let header = match read_record_header(&mut reader)? {
+            Some(record) if record.kind == RecordType::TES4 => {
+                let mut data = vec![0; record.size as usize];
+                reader.read_exact(&mut data)?;
+                parse_header(record.flags, &data)
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "not a TES4 plugin",
+                ));
+            }
+        };
+
+        let mut plugin = Self {
+            name,
+            header,
+            records: HashMap::new(),
+        };
+        let own_index = plugin.header.masters.len() as u32;
+        while let Some(record) = read_record_header(&mut reader)? {
+            // Enter the group: its records follow the header.
+            if record.kind == RecordType::GRUP {
+                continue;
+            }
+            if record.form_id >> 24 == own_index {
+                let object_id = plugin.object_id(record.form_id);
+                plugin.records.insert(object_id, record.kind);
+            }
+            reader.seek_relative(i64::from(record.size))?;
+        }
+        Ok(plugin)
+    }
+
+    /// Is this a master file?(`.esm` or the master flag)
+    pub fn is_master(&self) -> bool {
+        self.header.flags & FLAG_MASTER != 0 || has_extension(&self.name, "esm")
+    }
+
+    /// Is this a light plugin?(`.esl` or the light flag)
+    pub fn is_light(&self) -> bool {
+        self.header.flags & FLAG_LIGHT != 0 || has_extension(&self.name, "esl")
+    }
+
+    /// FormID without the master index, in the light plugin range(`0x000`-`0xFFF`) if light.
+    ///
+    /// e.g. `0xFE001800` -> `0x800` for a light plugin, `0x0100ABCD` -> `0xABCD` otherwise.
+    pub fn object_id(&self, form_id: u32) -> u32 {
+        match self.is_light() {
+            true => form_id & 0xFFF,
+            false => form_id & 0x00FF_FFFF,
+        }
+    }
+
+    /// Record type of the form defined by this plugin.
+    pub fn get(&self, form_id: u32) -> Option<RecordType> {
+        self.records.get(&self.object_id(form_id)).copied()
+    }
+
+    /// Number of the forms defined by this plugin.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if this plugin defines no form.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+/// Record or group header.
+struct RecordHeader {
+    kind: RecordType,
+    /// Data size of a record, or whole size of a group(including the header).
+    size: u32,
+    flags: u32,
+    form_id: u32,
+}
+
+/// Read the next header, or `None` at the end.
+fn read_record_header<R: Read>(reader: &mut R) -> io::Result<Option<RecordHeader>> {
+    let mut buf = [0; HEADER_SIZE];
+    let mut filled = 0;
+    while filled < HEADER_SIZE {
+        match reader.read(&mut buf[filled..])? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            n => filled += n,
+        }
+    }
+
+    let u32_at = |at: usize| u32::from_le_bytes([buf[at], buf[at + 1], buf[at + 2], buf[at + 3]]);
+    let kind = RecordType([buf[0], buf[1], buf[2], buf[3]]);
+    Ok(Some(RecordHeader {
+        kind,
+        size: match kind == RecordType::GRUP {
+            true => u32_at(4).saturating_sub(HEADER_SIZE as u32),
+            false => u32_at(4),
+        },
+        flags: u32_at(8),
+        form_id: u32_at(12),
+    }))
+}
+
+/// Parse the subrecords of `TES4`.
+fn parse_header(flags: u32, data: &[u8]) -> Header {
+    let mut header = Header {
+        flags,
+        ..Default::default()
+    };
+    if flags & FLAG_COMPRESSED != 0 {
+        return header;
+    }
+
+    let mut rest = data;
+    let mut large_size = None;
+    while rest.len() >= 6 {
+        let kind = [rest[0], rest[1], rest[2], rest[3]];
+        let size = large_size
+            .take()
+            .unwrap_or_else(|| usize::from(u16::from_le_bytes([rest[4], rest[5]])));
+        let Some(field) = rest.get(6..6 + size) else {
+            break;
+        };
+        rest = &rest[6 + size..];
+
+        match &kind {
+            b"XXXX" if field.len() == 4 => {
+                large_size =
+                    Some(u32::from_le_bytes([field[0], field[1], field[2], field[3]]) as usize);
+            }
+            b"HEDR" if field.len() >= 4 => {
+                header.version = f32::from_le_bytes([field[0], field[1], field[2], field[3]]);
+            }
+            b"CNAM" => header.author = Some(zstring(field)),
+            b"SNAM" => header.description = Some(zstring(field)),
+            b"MAST" => header.masters.push(zstring(field)),
+            _ => {}
+        }
+    }
+    header
+}
+
+/// Null terminated string
+fn zstring(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).to_string()
+}
+
+fn has_extension(name: &str, ext: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn subrecord(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut bytes = kind.to_vec();
+        bytes.extend((data.len() as u16).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    fn record(kind: &[u8; 4], flags: u32, form_id: u32, data: &[u8]) -> Vec<u8> {
+        let mut bytes = kind.to_vec();
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(flags.to_le_bytes());
+        bytes.extend(form_id.to_le_bytes());
+        bytes.extend([0; 8]);
+        bytes.extend(data);
+        bytes
+    }
+
+    fn group(label: &[u8; 4], records: &[u8]) -> Vec<u8> {
+        let mut bytes = b"GRUP".to_vec();
+        bytes.extend(((records.len() + HEADER_SIZE) as u32).to_le_bytes());
+        bytes.extend(label);
+        bytes.extend([0; 12]);
+        bytes.extend(records);
+        bytes
+    }
+
+    /// A plugin with `Skyrim.esm` as master, defining `KYWD 0x800` and overriding `RACE 0x13746`.
+    pub(crate) fn sample_plugin(flags: u32) -> Vec<u8> {
+        let mut tes4 = subrecord(b"HEDR", &[0x48, 0xE1, 0xDA, 0x3F, 0, 0, 0, 0, 0, 0, 0, 0]);
+        tes4.extend(subrecord(b"CNAM", b"me\0"));
+        tes4.extend(subrecord(b"MAST", b"Skyrim.esm\0"));
+        tes4.extend(subrecord(b"DATA", &[0; 8]));
+
+        let mut bytes = record(b"TES4", flags, 0, &tes4);
+        bytes.extend(group(b"KYWD", &record(b"KYWD", 0, 0x0100_0800, b"data")));
+        bytes.extend(group(b"RACE", &record(b"RACE", 0, 0x0001_3746, b"")));
+        bytes
+    }
+
+    #[test]
+    fn should_read_header_and_records() -> io::Result<()> {
+        let bytes = sample_plugin(FLAG_LIGHT);
+        let plugin = Plugin::from_reader("Sample.esp", BufReader::new(Cursor::new(bytes)))?;
+
+        assert_eq!(plugin.header.masters, ["Skyrim.esm"]);
+        assert_eq!(plugin.header.author.as_deref(), Some("me"));
+        assert!(plugin.is_light());
+        assert!(!plugin.is_master());
+        assert_eq!(plugin.len(), 1);
+        assert_eq!(plugin.get(0xFE00_0800), Some(RecordType::KYWD));
+        assert_eq!(plugin.get(0x13746), None);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_non_plugin() {
+        let result = Plugin::from_reader("foo.esp", BufReader::new(Cursor::new(b"not a plugin")));
+        assert!(result.is_err());
+    }
+}
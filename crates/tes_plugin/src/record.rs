@@ -0,0 +1,62 @@
+//! Four-character record type. e.g. `KYWD`
+use core::fmt;
+
+/// Four-character record type. e.g. `KYWD`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordType(pub [u8; 4]);
+
+impl RecordType {
+    /// Plugin header
+    pub const TES4: Self = Self(*b"TES4");
+    /// Group of records
+    pub const GRUP: Self = Self(*b"GRUP");
+
+    /// Armor
+    pub const ARMO: Self = Self(*b"ARMO");
+    /// Cell
+    pub const CELL: Self = Self(*b"CELL");
+    /// Class
+    pub const CLAS: Self = Self(*b"CLAS");
+    /// Combat style
+    pub const CSTY: Self = Self(*b"CSTY");
+    /// Faction
+    pub const FACT: Self = Self(*b"FACT");
+    /// Global variable
+    pub const GLOB: Self = Self(*b"GLOB");
+    /// Keyword
+    pub const KYWD: Self = Self(*b"KYWD");
+    /// Location reference type
+    pub const LCRT: Self = Self(*b"LCRT");
+    /// Location
+    pub const LCTN: Self = Self(*b"LCTN");
+    /// Magic effect
+    pub const MGEF: Self = Self(*b"MGEF");
+    /// Actor base
+    pub const NPC_: Self = Self(*b"NPC_");
+    /// Perk
+    pub const PERK: Self = Self(*b"PERK");
+    /// Race
+    pub const RACE: Self = Self(*b"RACE");
+    /// Shout
+    pub const SHOU: Self = Self(*b"SHOU");
+    /// Spell
+    pub const SPEL: Self = Self(*b"SPEL");
+    /// Voice type
+    pub const VTYP: Self = Self(*b"VTYP");
+    /// World space
+    pub const WRLD: Self = Self(*b"WRLD");
+    /// Weather
+    pub const WTHR: Self = Self(*b"WTHR");
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl fmt::Debug for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RecordType({self})")
+    }
+}
@@ -1,7 +1,38 @@
 use crate::convert_option::GuiConverterOptions;
-use dar2oar_core::{Closure, convert_dar_to_oar, remove_oar, unhide_dar};
+use dar2oar_core::error::ConvertError;
+use dar2oar_core::fs::hide_journal::UnhideReport;
+use dar2oar_core::{
+    CancellationToken, Closure, ConvertReport, ProgressEvent, RemoveOarOptions, convert_dar_to_oar,
+    remove_oar, unhide_dar, unhide_dar_from_journal,
+};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
 use std::time::Instant;
-use tauri::{Emitter as _, Window};
+use tauri::{Emitter as _, State, Window};
+
+/// Cancellation token shared by the long-running commands.
+///
+/// Each cancellable command runs with its own child token, so starting a command never detaches a running one.
+/// `cancel_task` cancels every running command, and the later commands get a fresh parent.
+#[derive(Debug, Default)]
+pub(crate) struct TaskCancelToken(Mutex<CancellationToken>);
+
+impl TaskCancelToken {
+    /// Returns a token for a new task, cancelled by the next [`Self::cancel`].
+    fn child(&self) -> CancellationToken {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .child_token()
+    }
+
+    /// Cancel all the running tasks (if any).
+    fn cancel(&self) {
+        let mut token = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        token.cancel();
+        *token = CancellationToken::new();
+    }
+}
 
 /// Early return with Err() and write log error.
 macro_rules! bail {
@@ -11,11 +42,20 @@ macro_rules! bail {
     }};
 }
 
+/// Write log error and return the error as is.
+///
+/// [`ConvertError`] is serialized as a [`Diagnostic`](dar2oar_core::error::Diagnostic),
+/// so the frontend can localize it by its code.
+fn log_err(err: ConvertError) -> ConvertError {
+    tracing::error!("{err}");
+    err
+}
+
 /// Measure the elapsed time and return the result of the given asynchronous function.
 macro_rules! time {
     ($name:literal, $expr:expr) => {{
         let start = Instant::now();
-        let res = $expr.await.or_else(|err| bail!(err));
+        let res = $expr.await.map_err(log_err);
         let elapsed = start.elapsed();
         tracing::info!(
             "{} time: {}.{}secs.",
@@ -29,12 +69,13 @@ macro_rules! time {
 
 /// Cast the conversion options in the GUI and perform the conversion.
 macro_rules! dar_to_oar {
-    ($options:ident, $sender:expr) => {
+    ($options:ident, $sender:expr, $cancel_token:expr) => {
         convert_dar_to_oar(
             GuiConverterOptions::to_convert_options($options)
                 .await
-                .or_else(|err| bail!(err))?,
+                .map_err(log_err)?,
             $sender,
+            $cancel_token,
         )
     };
 }
@@ -50,7 +91,10 @@ struct Payload {
     index: usize,
 }
 
-/// Closure that reports the number of files
+/// Closure that reports the progress
+///
+/// - `sender!(window, "event name")`: emits [`Payload`] for each index.
+/// - `sender!(window, "event name", ProgressEvent)`: emits each [`ProgressEvent`] as is (and logs warnings).
 macro_rules! sender {
     ($window:ident, $emit_name:literal) => {
         move |index: usize| {
@@ -59,18 +103,31 @@ macro_rules! sender {
             };
         }
     };
+    ($window:ident, $emit_name:literal, ProgressEvent) => {
+        move |event: ProgressEvent| {
+            if let ProgressEvent::Warning { path, message } = &event {
+                tracing::warn!("{}: {message}", path.display());
+            }
+            if let Err(err) = $window.emit($emit_name, event) {
+                tracing::error!("{}", err);
+            };
+        }
+    };
 }
 
 #[tauri::command]
-pub(crate) async fn convert_dar2oar(options: GuiConverterOptions) -> Result<(), String> {
+pub(crate) async fn convert_dar2oar(
+    cancel_token: State<'_, TaskCancelToken>,
+    options: GuiConverterOptions,
+) -> Result<ConvertReport, ConvertError> {
     let start = Instant::now();
 
     let options = GuiConverterOptions::to_convert_options(options)
         .await
-        .or_else(|err| bail!(err))?;
-    let res = convert_dar_to_oar(options, Closure::default)
+        .map_err(log_err)?;
+    let res = convert_dar_to_oar(options, Closure::default_event, cancel_token.child())
         .await
-        .or_else(|err| bail!(err));
+        .map_err(log_err);
 
     let elapsed = start.elapsed();
     tracing::info!(
@@ -85,10 +142,22 @@ pub(crate) async fn convert_dar2oar(options: GuiConverterOptions) -> Result<(),
 #[tauri::command]
 pub(crate) async fn convert_dar2oar_with_progress(
     window: Window,
+    cancel_token: State<'_, TaskCancelToken>,
     options: GuiConverterOptions,
-) -> Result<(), String> {
-    let sender = sender!(window, "/dar2oar/progress/converter");
-    time!("Conversion with progress", dar_to_oar!(options, sender))
+) -> Result<ConvertReport, ConvertError> {
+    let sender = sender!(window, "/dar2oar/progress/converter", ProgressEvent);
+    time!(
+        "Conversion with progress",
+        dar_to_oar!(options, sender, cancel_token.child())
+    )
+}
+
+/// Cancel the running conversion, `remove_oar_dir` or `unhide_dar_dir`.
+#[tauri::command]
+pub(crate) async fn cancel_task(cancel_token: State<'_, TaskCancelToken>) -> Result<(), String> {
+    tracing::info!("Cancel requested.");
+    cancel_token.cancel();
+    Ok(())
 }
 
 #[tauri::command]
@@ -108,16 +177,48 @@ pub(crate) async fn write_file(path: &str, content: &str) -> Result<(), String>
     std::fs::write(path, content).or_else(|err| bail!(err))
 }
 
+/// Remove the OAR namespaces generated by dar2oar. Returns the removed (or to be removed) paths.
 #[tauri::command]
-pub(crate) async fn remove_oar_dir(window: Window, path: &str) -> Result<(), String> {
+pub(crate) async fn remove_oar_dir(
+    window: Window,
+    cancel_token: State<'_, TaskCancelToken>,
+    path: &str,
+    force: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<Vec<PathBuf>, ConvertError> {
     let sender = sender!(window, "/dar2oar/progress/remove-oar");
-    time!("remove_oar", remove_oar(path, sender))
+    let options = RemoveOarOptions {
+        force: force.unwrap_or_default(),
+        dry_run: dry_run.unwrap_or_default(),
+    };
+    time!(
+        "remove_oar",
+        remove_oar(path, options, sender, cancel_token.child())
+    )
 }
 
+/// Unhide DAR files. With `from_journal`, reverse only the renames made by the converter and return the report.
 #[tauri::command]
-pub(crate) async fn unhide_dar_dir(window: Window, dar_dir: &str) -> Result<(), String> {
+pub(crate) async fn unhide_dar_dir(
+    window: Window,
+    cancel_token: State<'_, TaskCancelToken>,
+    dar_dir: &str,
+    from_journal: Option<bool>,
+) -> Result<Option<UnhideReport>, ConvertError> {
     let sender = sender!(window, "/dar2oar/progress/unhide-dar");
-    time!("unhide_dar", unhide_dar(dar_dir, sender))
+    if from_journal.unwrap_or_default() {
+        let report = time!(
+            "unhide_dar_from_journal",
+            unhide_dar_from_journal(dar_dir, sender, cancel_token.child())
+        )?;
+        return Ok(Some(report));
+    }
+
+    time!(
+        "unhide_dar",
+        unhide_dar(dar_dir, sender, cancel_token.child())
+    )?;
+    Ok(None)
 }
 
 #[tauri::command]
@@ -125,7 +226,9 @@ pub(crate) async fn generate_mapping_table(
     path: &std::path::Path,
     strategy: mapping_table::builder::MappingStrategy,
 ) -> Result<mapping_table::MappingTable, String> {
-    mapping_table::builder::generate_mapping_table(path, strategy).or_else(|err| bail!(err))
+    mapping_table::builder::generate_mapping_table(path, &strategy)
+        .map(|generated| generated.table)
+        .or_else(|err| bail!(err))
 }
 
 #[tauri::command]
@@ -1,5 +1,8 @@
 use dar2oar_core::error::Result;
-use dar2oar_core::{ConvertOptions, read_mapping_table};
+use dar2oar_core::{
+    ConvertOptions, FormIdStyle, OarVersion, SectionNaming, read_editor_id_dictionary,
+    read_mapping_table,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +15,14 @@ pub(crate) struct GuiConverterOptions {
     pub(crate) mod_description: Option<String>,
     pub(crate) mapping_path: Option<String>,
     pub(crate) mapping_1person_path: Option<String>,
+    pub(crate) section_naming: Option<SectionNaming>,
+    pub(crate) editor_id_path: Option<String>,
+    pub(crate) data_dir: Option<String>,
+    pub(crate) target_oar_version: Option<OarVersion>,
+    pub(crate) form_id_style: Option<FormIdStyle>,
+    pub(crate) extract_presets: Option<bool>,
+    pub(crate) collapse_variants: Option<bool>,
+    pub(crate) dedupe_motions: Option<bool>,
     pub(crate) run_parallel: Option<bool>,
     pub(crate) hide_dar: Option<bool>,
 }
@@ -27,6 +38,14 @@ impl GuiConverterOptions {
             mod_description: description,
             mapping_path,
             mapping_1person_path,
+            section_naming,
+            editor_id_path,
+            data_dir,
+            target_oar_version,
+            form_id_style,
+            extract_presets,
+            collapse_variants,
+            dedupe_motions,
             run_parallel,
             hide_dar,
         } = options;
@@ -39,6 +58,10 @@ impl GuiConverterOptions {
             Some(path) => Some(read_mapping_table(path).await?),
             None => None,
         };
+        let editor_ids = match editor_id_path {
+            Some(path) => Some(read_editor_id_dictionary(path).await?),
+            None => None,
+        };
 
         Ok(ConvertOptions {
             dar_dir,
@@ -48,6 +71,14 @@ impl GuiConverterOptions {
             description,
             section_table,
             section_1person_table,
+            section_naming: section_naming.unwrap_or_default(),
+            editor_ids,
+            target_oar_version,
+            form_id_style: form_id_style.unwrap_or_default(),
+            data_dir,
+            extract_presets: extract_presets.unwrap_or(false),
+            collapse_variants: collapse_variants.unwrap_or(false),
+            dedupe_motions: dedupe_motions.unwrap_or(false),
             run_parallel: run_parallel.unwrap_or(false),
             hide_dar: hide_dar.unwrap_or(false),
         })
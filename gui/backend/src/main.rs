@@ -20,7 +20,9 @@ fn main() {
                 .with_state_flags(StateFlags::all() & !StateFlags::VISIBLE)
                 .build(),
         )
+        .manage(crate::cmd::TaskCancelToken::default())
         .invoke_handler(tauri::generate_handler![
+            crate::cmd::cancel_task,
             crate::cmd::change_log_level,
             crate::cmd::convert_dar2oar,
             crate::cmd::convert_dar2oar_with_progress,
@@ -0,0 +1,24 @@
+import CancelIcon from '@mui/icons-material/Cancel';
+import { ButtonWithToolTip } from '@/components/atoms/ButtonWithToolTip';
+import { useTranslation } from '@/components/hooks/useTranslation';
+import { NOTIFY } from '@/lib/notify';
+import { cancelTask } from '@/services/api/convert';
+
+import type { ButtonProps } from '@mui/material';
+
+type Props = ButtonProps;
+
+export const CancelTaskButton = ({ ...props }: Props) => {
+  const { t } = useTranslation();
+  const handleClick = () => NOTIFY.asyncTry(async () => await cancelTask());
+
+  return (
+    <ButtonWithToolTip
+      {...props}
+      buttonName={t('cancel-btn')}
+      icon={<CancelIcon />}
+      onClick={handleClick}
+      tooltipTitle={t('cancel-task-tooltip')}
+    />
+  );
+};
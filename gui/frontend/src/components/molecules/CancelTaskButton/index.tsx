@@ -0,0 +1 @@
+export { CancelTaskButton } from './CancelTaskButton';
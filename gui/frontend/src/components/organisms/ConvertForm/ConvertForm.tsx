@@ -9,10 +9,11 @@ import { InputModInfoField } from './InputModInfoField';
 import { InputPathField } from './InputPathField';
 import { useTranslation } from '@/components/hooks/useTranslation';
 import { ConvertNav, ConvertNavPadding } from '@/components/organisms/ConvertForm/ConvertNav';
+import { NOTIFY } from '@/lib/notify';
 import { STORAGE } from '@/lib/storage';
 import { PRIVATE_CACHE_OBJ, PUB_CACHE_OBJ } from '@/lib/storage/cacheKeys';
 import { convertDar2oar } from '@/services/api/convert';
-import { progressListener } from '@/services/api/event';
+import { conversionListener } from '@/services/api/event';
 import { LOG, type LogLevel } from '@/services/api/log';
 
 export type FormProps = {
@@ -23,10 +24,18 @@ export type FormProps = {
   modDescription: string;
   mappingPath: string;
   mapping1personPath: string;
+  editorIdPath: string;
+  dataDir: string;
   loading: boolean;
+  /** Number of running `remove_oar_dir`/`unhide_dar_dir` tasks */
+  runningTasks: number;
   logLevel: LogLevel;
   runParallel: boolean;
   hideDar: boolean;
+  conditionNames: boolean;
+  extractPresets: boolean;
+  collapseVariants: boolean;
+  dedupeMotions: boolean;
   showProgress: boolean;
   inferPath: boolean;
   progress: number;
@@ -40,10 +49,17 @@ const defaultFormValues = (): FormProps => ({
   modDescription: STORAGE.getOrDefault(PRIVATE_CACHE_OBJ.modDescription),
   mappingPath: STORAGE.getOrDefault(PRIVATE_CACHE_OBJ.mappingPath),
   mapping1personPath: STORAGE.getOrDefault(PRIVATE_CACHE_OBJ.mapping1personPath),
+  editorIdPath: STORAGE.getOrDefault(PRIVATE_CACHE_OBJ.editorIdPath),
+  dataDir: STORAGE.getOrDefault(PRIVATE_CACHE_OBJ.dataDir),
   loading: false,
+  runningTasks: 0,
   logLevel: LOG.get(),
   runParallel: STORAGE.get(PUB_CACHE_OBJ.runParallel) === 'true',
   hideDar: STORAGE.get(PUB_CACHE_OBJ.hideDar) === 'true',
+  conditionNames: STORAGE.get(PUB_CACHE_OBJ.conditionNames) === 'true',
+  extractPresets: STORAGE.get(PUB_CACHE_OBJ.extractPresets) === 'true',
+  collapseVariants: STORAGE.get(PUB_CACHE_OBJ.collapseVariants) === 'true',
+  dedupeMotions: STORAGE.get(PUB_CACHE_OBJ.dedupeMotions) === 'true',
   showProgress: STORAGE.get(PUB_CACHE_OBJ.showProgress) === 'true',
   inferPath: STORAGE.get(PUB_CACHE_OBJ.inferPath) === 'true',
   progress: 0,
@@ -54,6 +70,8 @@ const PATH_FORM_VALUES = [
   'dst',
   'mapping1personPath',
   'mappingPath',
+  'editorIdPath',
+  'dataDir',
   'modAuthor',
   'modName',
   'modDescription',
@@ -93,10 +111,11 @@ export function ConvertForm() {
     const task = async () => await convertDar2oar(formProps);
     const start = Date.now();
 
-    await progressListener('/dar2oar/progress/converter', task, {
+    await conversionListener('/dar2oar/progress/converter', task, {
       setLoading,
       setProgress: (percentage: number) => setValue('progress', percentage),
       success: () => t('conversion-complete') + ` (${((Date.now() - start) / 1000).toFixed(2)}s)`,
+      onWarnings: (report) => NOTIFY.warn(`${t('conversion-warnings')}: ${report.warnings}`),
     });
   };
 
@@ -3,6 +3,7 @@ import { Box, type SxProps, type Theme } from '@mui/material';
 import { Controller, useFormContext, useWatch } from 'react-hook-form';
 import { ConvertButton } from '@/components/atoms/ConvertButton';
 import { LinearWithValueLabel } from '@/components/atoms/LinearWithValueLabel';
+import { CancelTaskButton } from '@/components/molecules/CancelTaskButton';
 import { LogDirButton } from '@/components/molecules/LogDirButton';
 import { LogFileButton } from '@/components/molecules/LogFileButton';
 import { LogLevelList } from '@/components/organisms/LogLevelList';
@@ -28,7 +29,7 @@ const sx: SxProps<Theme> = {
 
 export const ConvertNav = () => {
   const { control } = useFormContext<FormProps>();
-  const { progress, showProgress } = useWatch<FormProps>();
+  const { progress, runningTasks, showProgress } = useWatch<FormProps>();
 
   return (
     <Controller
@@ -42,6 +43,7 @@ export const ConvertNav = () => {
             <LogFileButton />
             <UnhideDarButton />
             <RemoveOarButton />
+            {value || (runningTasks ?? 0) > 0 ? <CancelTaskButton /> : null}
             <ConvertButton loading={value} progress={progress ?? 0} />
           </Box>
           {showProgress ? <LinearWithValueLabel progress={progress ?? 0} /> : null}
@@ -1,5 +1,9 @@
 import AutoFixNormalIcon from '@mui/icons-material/AutoFixNormal';
+import CasinoIcon from '@mui/icons-material/Casino';
+import CompressIcon from '@mui/icons-material/Compress';
+import DriveFileRenameOutlineIcon from '@mui/icons-material/DriveFileRenameOutline';
 import DynamicFeedIcon from '@mui/icons-material/DynamicFeed';
+import LibraryBooksIcon from '@mui/icons-material/LibraryBooks';
 import SlideshowIcon from '@mui/icons-material/Slideshow';
 import VisibilityOffIcon from '@mui/icons-material/VisibilityOff';
 import { useTranslation } from '@/components/hooks/useTranslation';
@@ -23,6 +27,30 @@ export const useCheckFields = () => {
       name: 'hideDar',
       tooltipText: t('hide-dar-btn-tooltip'),
     },
+    {
+      icon: <DriveFileRenameOutlineIcon />,
+      label: t('condition-names-btn'),
+      name: 'conditionNames',
+      tooltipText: t('condition-names-btn-tooltip'),
+    },
+    {
+      icon: <LibraryBooksIcon />,
+      label: t('extract-presets-btn'),
+      name: 'extractPresets',
+      tooltipText: t('extract-presets-btn-tooltip'),
+    },
+    {
+      icon: <CasinoIcon />,
+      label: t('collapse-variants-btn'),
+      name: 'collapseVariants',
+      tooltipText: t('collapse-variants-btn-tooltip'),
+    },
+    {
+      icon: <CompressIcon />,
+      label: t('dedupe-motions-btn'),
+      name: 'dedupeMotions',
+      tooltipText: t('dedupe-motions-btn-tooltip'),
+    },
 
     {
       icon: <SlideshowIcon />,
@@ -70,5 +70,21 @@ export const useInputPathFields = () => {
       isDir: false,
       textFieldVariant: 'filled',
     },
+    {
+      helperText: t('convert-form-editor-id-helper'),
+      label: t('convert-form-editor-id-label'),
+      name: 'editorIdPath',
+      placeholder: './editor_ids.csv',
+      isDir: false,
+      textFieldVariant: 'filled',
+    },
+    {
+      helperText: t('convert-form-data-dir-helper'),
+      label: t('convert-form-data-dir-label'),
+      name: 'dataDir',
+      placeholder: '[...]/Skyrim Special Edition/Data',
+      isDir: true,
+      textFieldVariant: 'filled',
+    },
   ] satisfies ComponentPropsWithRef<typeof InputPathField>[];
 };
@@ -0,0 +1,24 @@
+import { useCallback, useState } from 'react';
+import { useFormContext } from 'react-hook-form';
+
+import type { FormProps } from '../ConvertForm';
+
+/**
+ * Loading state of a cancellable support task(e.g. `remove_oar_dir`).
+ *
+ * The task is also counted in `runningTasks` of the form, so that the cancel button is shown while it runs.
+ */
+export const useTaskLoading = () => {
+  const [loading, setLocalLoading] = useState(false);
+  const { getValues, setValue } = useFormContext<FormProps>();
+
+  const setLoading = useCallback(
+    (loading: boolean) => {
+      setLocalLoading(loading);
+      setValue('runningTasks', Math.max(0, getValues('runningTasks') + (loading ? 1 : -1)));
+    },
+    [getValues, setValue],
+  );
+
+  return [loading, setLoading] as const;
+};
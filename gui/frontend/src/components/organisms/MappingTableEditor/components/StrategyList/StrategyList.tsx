@@ -24,6 +24,10 @@ export const StrategyList = () => {
     { value: 'txt_stem', label: t('strategy-txt-stem') },
     { value: 'txt_stem_stripped', label: t('strategy-txt-stem-stripped') },
     { value: 'dir_pattern', label: t('strategy-dir-pattern') },
+    { value: 'readme_txt', label: t('strategy-readme-txt') },
+    { value: 'conditions_comment', label: t('strategy-conditions-comment') },
+    { value: 'hkx_subfolder', label: t('strategy-hkx-subfolder') },
+    { value: 'fomod', label: t('strategy-fomod') },
   ] as const;
 
   return (
@@ -101,5 +105,20 @@ Output:
   666003 Sit
   1A2B Attack
 `}</pre>
+
+    <hr />
+
+    <p>
+      <b>{t('strategy-readme-txt')}</b>: {t('strategy-readme-txt-desc')}
+    </p>
+    <p>
+      <b>{t('strategy-conditions-comment')}</b>: {t('strategy-conditions-comment-desc')}
+    </p>
+    <p>
+      <b>{t('strategy-hkx-subfolder')}</b>: {t('strategy-hkx-subfolder-desc')}
+    </p>
+    <p>
+      <b>{t('strategy-fomod')}</b>: {t('strategy-fomod-desc')}
+    </p>
   </div>
 );
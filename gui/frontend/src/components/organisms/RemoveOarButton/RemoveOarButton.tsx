@@ -4,6 +4,7 @@ import { useWatch } from 'react-hook-form';
 import { ButtonWithToolTip } from '@/components/atoms/ButtonWithToolTip';
 import { CircularProgressWithLabel } from '@/components/atoms/CircularProgressWithLabel';
 import { useTranslation } from '@/components/hooks/useTranslation';
+import { useTaskLoading } from '@/components/organisms/ConvertForm/hooks/useTaskLoading';
 import { NOTIFY } from '@/lib/notify';
 import { removeOarDir } from '@/services/api/convert';
 import { progressListener } from '@/services/api/event';
@@ -12,7 +13,7 @@ import type { FormProps } from '../ConvertForm/ConvertForm';
 
 export const RemoveOarButton = () => {
   const { t } = useTranslation();
-  const [loading, setLoading] = useState(false);
+  const [loading, setLoading] = useTaskLoading();
   const [progress, setProgress] = useState(0);
   const { src: darPath, dst: oarPath } = useWatch<FormProps>();
 
@@ -23,16 +24,18 @@ export const RemoveOarButton = () => {
     }
     const path = oarPath === '' ? darPath : oarPath;
     const start = Date.now();
+    let removedCount = 0;
 
     await progressListener(
       '/dar2oar/progress/remove-oar',
       async () => {
-        await removeOarDir(path ?? '');
+        removedCount = (await removeOarDir(path ?? '')).length;
       },
       {
         setLoading,
         setProgress,
-        success: () => t('remove-oar-success') + ` (${((Date.now() - start) / 1000).toFixed(2)}s)`,
+        success: () =>
+          `${t('remove-oar-success')}: ${removedCount} (${((Date.now() - start) / 1000).toFixed(2)}s)`,
         error: `${path}:\n${t('remove-oar-failed')}`,
       },
     );
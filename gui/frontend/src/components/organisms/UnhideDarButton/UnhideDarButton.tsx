@@ -4,6 +4,7 @@ import { useWatch } from 'react-hook-form';
 import { ButtonWithToolTip } from '@/components/atoms/ButtonWithToolTip';
 import { CircularProgressWithLabel } from '@/components/atoms/CircularProgressWithLabel';
 import { useTranslation } from '@/components/hooks/useTranslation';
+import { useTaskLoading } from '@/components/organisms/ConvertForm/hooks/useTaskLoading';
 import { NOTIFY } from '@/lib/notify';
 import { unhideDarDir } from '@/services/api/convert';
 import { progressListener } from '@/services/api/event';
@@ -12,7 +13,7 @@ import type { FormProps } from '../ConvertForm/ConvertForm';
 
 export const UnhideDarButton = () => {
   const { t } = useTranslation();
-  const [loading, setLoading] = useState(false);
+  const [loading, setLoading] = useTaskLoading();
   const [progress, setProgress] = useState(0);
   const { src: path } = useWatch<FormProps>();
 
@@ -10,19 +10,27 @@ const MAPPING_TABLE_PRIVATE_CACHE_KEYS_OBJ = {
 
 const FORM_PUB_CACHE_KEYS_OBJ = {
   hideDar: 'hideDar',
+  conditionNames: 'conditionNames',
+  extractPresets: 'extractPresets',
+  collapseVariants: 'collapseVariants',
+  dedupeMotions: 'dedupeMotions',
   runParallel: 'runParallel',
   showProgress: 'showProgress',
   inferPath: 'inferPath',
 } as const;
 
 const FORM_PRIVATE_CACHE_KEYS_OBJ = {
+  cachedDataDir: 'cached-dataDir',
   cachedDst: 'cached-dst',
+  cachedEditorIdPath: 'cached-editorIdPath',
   cachedMapping1PersonPath: 'cached-mapping1personPath',
   cachedMappingPath: 'cached-mappingPath',
   cachedModAuthor: 'cached-modName',
   cachedModName: 'cached-modAuthor',
   cachedSrc: 'cached-src',
+  dataDir: 'dataDir',
   dst: 'dst',
+  editorIdPath: 'editorIdPath',
   mapping1personPath: 'mapping1personPath',
   mappingPath: 'mappingPath',
   modAuthor: 'modAuthor',
@@ -9,8 +9,24 @@ type ConverterOptions = {
   modDescription?: string;
   mappingPath?: string;
   mapping1personPath?: string;
+  /** `plugin,FormID,EditorID` dictionary(CSV or JSON exported from xEdit) */
+  editorIdPath?: string;
+  /** Skyrim `Data` dir to check the forms in the conditions against its plugins */
+  dataDir?: string;
+  /** OAR version the output targets(e.g. `1.0.0.0`). Conditions requiring a newer OAR are warned. */
+  targetOarVersion?: string;
+  /** How to write the FormIDs(default: uppercase without zero padding). */
+  formIdStyle?: { uppercase?: boolean; width?: number };
   runParallel?: boolean;
   hideDar?: boolean;
+  /** Derive the section names from the conditions when they are not in the mapping table. */
+  conditionNames?: boolean;
+  /** Hoist the condition blocks repeated across sections into OAR condition presets. */
+  extractPresets?: boolean;
+  /** Merge the sections that differ only by `Random()` thresholds into OAR animation variants. */
+  collapseVariants?: boolean;
+  /** Refer to another section's byte-identical motion files via `overrideAnimationsFolder` instead of copying them. */
+  dedupeMotions?: boolean;
   showProgress?: boolean;
 };
 
@@ -38,8 +54,16 @@ export async function convertDar2oar(props: ConverterOptions): Promise<void> {
       modDescription: emptyToUndefined(props.modDescription),
       mappingPath: emptyToUndefined(props.mappingPath),
       mapping1personPath: emptyToUndefined(props.mapping1personPath),
+      editorIdPath: emptyToUndefined(props.editorIdPath),
+      dataDir: emptyToUndefined(props.dataDir),
+      targetOarVersion: emptyToUndefined(props.targetOarVersion),
+      formIdStyle: props.formIdStyle,
       runParallel: props.runParallel ?? false,
       hideDar: props.hideDar ?? false,
+      sectionNaming: props.conditionNames === true ? 'conditions' : 'priority',
+      extractPresets: props.extractPresets ?? false,
+      collapseVariants: props.collapseVariants ?? false,
+      dedupeMotions: props.dedupeMotions ?? false,
     },
   };
 
@@ -50,29 +74,56 @@ export async function convertDar2oar(props: ConverterOptions): Promise<void> {
   await invoke(cmd, args);
 }
 
+/** Result of `unhideDarDir` with `fromJournal`. */
+export type UnhideReport = {
+  restored: string[];
+  conflicts: { hidden: string; unhidden: string; kind: 'already_exists' | 'missing_hidden' }[];
+};
+
 /**
- * Add `.mohidden` to DAR's files.
+ * Remove `.mohidden` from DAR's files.
  * @param {string} darDir - A string representing the directory path of the DAR directory that needs to be
  * unhidden.
+ * @param {boolean} fromJournal - Reverse only the renames recorded by the converter's `hideDar`.
+ * @returns The report if `fromJournal`, otherwise `null`.
  * @throws
  */
-export async function unhideDarDir(darDir: string) {
+export async function unhideDarDir(darDir: string, fromJournal = false): Promise<UnhideReport | null> {
   if (darDir === '') {
     throw new Error('darDir is empty string.');
   }
-  await invoke('unhide_dar_dir', { darDir });
+  return await invoke<UnhideReport | null>('unhide_dar_dir', { darDir, fromJournal });
 }
 
+type RemoveOarOptions = {
+  /** Remove whole `OpenAnimationReplacer` directories, including mods not generated by dar2oar. */
+  force?: boolean;
+  /** Only list the paths that would be removed. */
+  dryRun?: boolean;
+};
+
 /**
- * The function `removeOarDir` is an asynchronous function that removes a specified directory and throws an error if the
- * path is empty.
+ * The function `removeOarDir` is an asynchronous function that removes the OAR directories generated by dar2oar
+ * and throws an error if the path is empty.
  * @param {string} path - The `path` parameter is a string that specifies the directory path of the DAR or OAR directory
  * that needs to be removed.
+ * @param {RemoveOarOptions} options - Remove options.
+ * @returns {Promise<string[]>} The removed paths (or the paths that would be removed with `dryRun`).
  * @throws
  */
-export async function removeOarDir(path: string) {
+export async function removeOarDir(path: string, options: RemoveOarOptions = {}): Promise<string[]> {
   if (path === '') {
     throw new Error('Specified path is empty string.');
   }
-  await invoke('remove_oar_dir', { path });
+  return await invoke<string[]>('remove_oar_dir', { path, force: options.force, dryRun: options.dryRun });
+}
+
+/**
+ * Cancel the running conversion, `unhideDarDir` or `removeOarDir`.
+ *
+ * The partially converted output is discarded, leaving the existing OAR directories untouched.
+ * @throws
+ */
+export async function cancelTask() {
+  await invoke('cancel_task');
 }
@@ -0,0 +1,33 @@
+import { t } from 'i18next';
+import { OBJECT } from '@/lib/object-utils';
+
+/** Error returned by the converter commands. (Specification decided by backend `Diagnostic`) */
+export type Diagnostic = {
+  /** Stable code. e.g. `D2O0012` */
+  code: string;
+  message: string;
+  path: string | null;
+  help: string | null;
+  related?: Diagnostic[];
+};
+
+const isDiagnostic = (err: unknown): err is Diagnostic =>
+  typeof err === 'object' &&
+  OBJECT.isPropertyAccessible(err) &&
+  typeof err.code === 'string' &&
+  typeof err.message === 'string';
+
+/**
+ * Error message for the user.
+ *
+ * A `Diagnostic` is localized by the `error-<code>` key, falling back to the backend message.
+ */
+export const formatError = (err: unknown): string => {
+  if (!isDiagnostic(err)) {
+    return `${err}`;
+  }
+
+  const message = t(`error-${err.code}`, { defaultValue: err.message, path: err.path ?? '' });
+  const help = err.help === null ? '' : `\n${err.help}`;
+  return `[${err.code}] ${message}${help}`;
+};
@@ -1,5 +1,6 @@
 import { listen } from '@tauri-apps/api/event';
 import { NOTIFY } from '@/lib/notify';
+import { formatError } from './error';
 
 import type { EventCallback, EventName } from '@tauri-apps/api/event';
 import type { ReactNode } from 'react';
@@ -55,14 +56,10 @@ type Payload = { index: number };
 export async function progressListener(
   eventName: EventName,
   promiseFn: () => Promise<void>,
-  { setLoading, setProgress, success, error }: ListenerProps,
+  props: ListenerProps,
 ) {
-  setLoading(true);
-  setProgress(0);
-
   /** All File & dir counts */
   let maxNum = 0;
-  let unlisten: (() => void) | null = null;
   const eventHandler: EventCallback<Payload> = (event) => {
     /** file count to % */
     const toPercentage = (num: number) => (num * 100) / maxNum;
@@ -71,13 +68,88 @@ export async function progressListener(
       maxNum = event.payload.index;
     } else {
       const percent = toPercentage(event.payload.index);
-      setProgress(percent);
+      props.setProgress(percent);
     }
   };
 
+  await listenWhile(eventName, eventHandler, promiseFn, props);
+}
+
+/** Summary of a conversion. */
+export type ConvertReport = {
+  conditions: number;
+  motions: number;
+  memos: number;
+  warnings: number;
+  /** Number of sections merged into animation variants of another section */
+  variants: number;
+  /** Number of sections referring to another section's motion files */
+  deduplicated: number;
+  bytesSaved: number;
+};
+
+/** Progress event of the converter. (Specification decided by backend `ProgressEvent`) */
+export type ProgressEvent =
+  | { type: 'started'; total: number }
+  | { type: 'fileStarted'; path: string }
+  | { type: 'fileDone'; path: string; kind: 'conditions' | 'motion' | 'memo' }
+  | { type: 'warning'; path: string; message: string }
+  | { type: 'finished'; report: ConvertReport };
+
+type ConversionListenerProps = ListenerProps & {
+  /** Called with the conversion summary if there were warnings. */
+  onWarnings?: (report: ConvertReport) => void;
+};
+
+/**
+ * Tauri Progress Event Listener for the converter's `ProgressEvent`.
+ * # No exception
+ */
+export async function conversionListener(
+  eventName: EventName,
+  promiseFn: () => Promise<void>,
+  props: ConversionListenerProps,
+) {
+  let total = 0;
+  let done = 0;
+  const eventHandler: EventCallback<ProgressEvent> = ({ payload }) => {
+    switch (payload.type) {
+      case 'started':
+        total = payload.total;
+        break;
+      case 'fileDone':
+        done += 1;
+        if (total !== 0) {
+          props.setProgress((done * 100) / total);
+        }
+        break;
+      case 'finished':
+        if (payload.report.warnings > 0) {
+          props.onWarnings?.(payload.report);
+        }
+        break;
+      default:
+        break;
+    }
+  };
+
+  await listenWhile(eventName, eventHandler, promiseFn, props);
+}
+
+/** Listen to `eventName` while `promiseFn` is running, then notify the result. */
+async function listenWhile<T>(
+  eventName: EventName,
+  eventHandler: EventCallback<T>,
+  promiseFn: () => Promise<void>,
+  { setLoading, setProgress, success, error }: ListenerProps,
+) {
+  setLoading(true);
+  setProgress(0);
+
+  let unlisten: (() => void) | null = null;
   try {
     // Setup before run Promise(For event hook)
-    unlisten = await listen<Payload>(eventName, eventHandler);
+    unlisten = await listen<T>(eventName, eventHandler);
 
     await promiseFn();
 
@@ -85,7 +157,7 @@ export async function progressListener(
     setProgress(100);
   } catch (err) {
     setProgress(0); // To avoid display `NaN`
-    NOTIFY.error(error ?? `${err}`);
+    NOTIFY.error(error ?? formatError(err));
   } finally {
     if (unlisten) {
       unlisten();
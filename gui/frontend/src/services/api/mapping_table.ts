@@ -3,7 +3,15 @@ import { z } from 'zod';
 
 export type MappingTable = Record<string, string>;
 
-export const strategySchema = z.enum(['txt_stem', 'txt_stem_stripped', 'dir_pattern']);
+export const strategySchema = z.enum([
+  'txt_stem',
+  'txt_stem_stripped',
+  'dir_pattern',
+  'readme_txt',
+  'conditions_comment',
+  'hkx_subfolder',
+  'fomod',
+]);
 export type MappingTableGenStrategy = z.infer<typeof strategySchema>;
 
 /**
@@ -35,11 +43,11 @@ export const mappingTableFromStr = (text: string): MappingTable => {
     const trimmed = line.trim();
     if (!trimmed) continue;
 
-    const [priority, ...rest] = trimmed.split(/\s+/);
-    if (!priority) continue;
+    // `priority name` or `plugin|FormID name` (only the plugin may contain spaces)
+    const match = /^(.+?\.es[mpl]\|\S+|\S+)\s*(.*)$/i.exec(trimmed);
+    if (!match?.[1]) continue;
 
-    const name = rest.join(' ').trim();
-    map[priority] = name;
+    map[match[1]] = (match[2] ?? '').trim();
   }
 
   return map;